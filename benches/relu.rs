@@ -8,8 +8,10 @@ extern crate rand;
 use test::Bencher;
 use co::prelude::*;
 use co_nn::*;
+use co_nn::frameworks::native::helper;
+use co_nn::frameworks::native::simd::{self, Function};
 
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rand, Rng};
 
 fn backend() -> Backend<Native> {
     Backend::<Native>::default().unwrap()
@@ -79,3 +81,66 @@ fn bench_10_relu_grad_10000_native(b: &mut Bencher) {
     let mut func = || { let _ = backend.relu_grad_plain(&mut x, &mut dx, &mut out, &mut dout); };
     { func(); bench_profile(b, func, 10); }
 }
+
+fn slices<T: Rand + Default + Clone>(size: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let mut rng = thread_rng();
+    let x = rng.gen_iter::<T>().take(size).collect::<Vec<T>>();
+    let dx = rng.gen_iter::<T>().take(size).collect::<Vec<T>>();
+    (x, dx, vec![T::default(); size])
+}
+
+#[bench]
+fn bench_10_relu_10000_scalar(b: &mut Bencher) {
+    let (x, _, mut out) = slices::<f32>(10000);
+    let mut func = || { for (o, i) in out.iter_mut().zip(x.iter()) { *o = helper::relu(i); } };
+    { func(); bench_profile(b, func, 10); }
+}
+
+#[bench]
+fn bench_10_relu_10000_simd(b: &mut Bencher) {
+    let (x, _, mut out) = slices::<f32>(10000);
+    let mut func = || { simd::forward(Function::Relu, &x, &mut out); };
+    { func(); bench_profile(b, func, 10); }
+}
+
+#[bench]
+fn bench_10_relu_grad_10000_scalar(b: &mut Bencher) {
+    let (x, dx, mut out) = slices::<f32>(10000);
+    let mut func = || { for (o, (i, d)) in out.iter_mut().zip(x.iter().zip(dx.iter())) { *o = helper::relu_grad(i, d); } };
+    { func(); bench_profile(b, func, 10); }
+}
+
+#[bench]
+fn bench_10_relu_grad_10000_simd(b: &mut Bencher) {
+    let (x, dx, mut out) = slices::<f32>(10000);
+    let mut func = || { simd::backward(Function::Relu, &x, &dx, &mut out); };
+    { func(); bench_profile(b, func, 10); }
+}
+
+#[bench]
+fn bench_10_relu_10000_scalar_f64(b: &mut Bencher) {
+    let (x, _, mut out) = slices::<f64>(10000);
+    let mut func = || { for (o, i) in out.iter_mut().zip(x.iter()) { *o = helper::relu(i); } };
+    { func(); bench_profile(b, func, 10); }
+}
+
+#[bench]
+fn bench_10_relu_10000_simd_f64(b: &mut Bencher) {
+    let (x, _, mut out) = slices::<f64>(10000);
+    let mut func = || { simd::forward(Function::Relu, &x, &mut out); };
+    { func(); bench_profile(b, func, 10); }
+}
+
+#[bench]
+fn bench_10_relu_grad_10000_scalar_f64(b: &mut Bencher) {
+    let (x, dx, mut out) = slices::<f64>(10000);
+    let mut func = || { for (o, (i, d)) in out.iter_mut().zip(x.iter().zip(dx.iter())) { *o = helper::relu_grad(i, d); } };
+    { func(); bench_profile(b, func, 10); }
+}
+
+#[bench]
+fn bench_10_relu_grad_10000_simd_f64(b: &mut Bencher) {
+    let (x, dx, mut out) = slices::<f64>(10000);
+    let mut func = || { simd::backward(Function::Relu, &x, &dx, &mut out); };
+    { func(); bench_profile(b, func, 10); }
+}
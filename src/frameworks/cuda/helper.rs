@@ -711,6 +711,100 @@ macro_rules! impl_ops_softmax_for {
                     }
                 }))
             }
+
+            fn softmax_axis(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+
+                self.softmax_axis_plain(x, result, axis)
+            }
+
+            fn softmax_axis_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let (desc, calls, slice_len) = try!(x.cudnn_tensor_desc_softmax_axis(axis));
+                if x.desc().size() != result.desc().size() {
+                    return Err(::co::error::Error::Plugin(::co::plugin::Error::Operation("CUDA softmax requires input and output of the same size.")));
+                }
+                let src = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x, self.device()) }) as *const $t;
+                let dest = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr_mut(result, self.device()) }) as *mut $t;
+
+                for slice in 0..calls {
+                    let offset = (slice * slice_len) as isize;
+                    let scal_params: ::cudnn::utils::ScalParams<$t> = ::cudnn::utils::ScalParams::default();
+                    try!(match CUDNN.softmax_forward(
+                        &desc, // src_desc
+                        unsafe { src.offset(offset) } as *const ::libc::c_void, //src_data
+                        &desc, // dest_desc
+                        unsafe { dest.offset(offset) } as *mut ::libc::c_void, // dest_data
+                        scal_params
+                    ) {
+                        Ok(_) => Ok(()),
+                        Err(_) => {
+                            Err(::co::plugin::Error::Operation("Unable to execute CUDA cuDNN softmax Forward."))
+                        }
+                    });
+                }
+                Ok(())
+            }
+
+            fn softmax_axis_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+
+                self.softmax_axis_grad_plain(x, x_diff, result_diff, axis)
+            }
+
+            fn softmax_axis_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let (desc, calls, slice_len) = try!(x.cudnn_tensor_desc_softmax_axis(axis));
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(::co::error::Error::Plugin(::co::plugin::Error::Operation("CUDA softmax requires inputs and output of the same size.")));
+                }
+                let src = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x, self.device()) }) as *const $t;
+                let src_diff = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x_diff, self.device()) }) as *const $t;
+                let dest_diff = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr_mut(result_diff, self.device()) }) as *mut $t;
+
+                for slice in 0..calls {
+                    let offset = (slice * slice_len) as isize;
+                    let scal_params: ::cudnn::utils::ScalParams<$t> = ::cudnn::utils::ScalParams::default();
+                    try!(match CUDNN.softmax_backward(
+                        &desc, // src_desc
+                        unsafe { src.offset(offset) } as *const ::libc::c_void, //src_data
+                        &desc, // src_diff_desc
+                        unsafe { src_diff.offset(offset) } as *const ::libc::c_void, //src_diff_data
+                        &desc, // dest_diff_desc
+                        unsafe { dest_diff.offset(offset) } as *mut ::libc::c_void, // dest_diff_data
+                        scal_params
+                    ) {
+                        Ok(_) => Ok(()),
+                        Err(_) => {
+                            Err(::co::plugin::Error::Operation("Unable to execute CUDA cuDNN softmax Backward."))
+                        }
+                    });
+                }
+                Ok(())
+            }
         }
     )
 }
@@ -787,6 +881,100 @@ macro_rules! impl_ops_log_softmax_for {
                     }
                 }))
             }
+
+            fn log_softmax_axis(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+
+                self.log_softmax_axis_plain(x, result, axis)
+            }
+
+            fn log_softmax_axis_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let (desc, calls, slice_len) = try!(x.cudnn_tensor_desc_softmax_axis(axis));
+                if x.desc().size() != result.desc().size() {
+                    return Err(::co::error::Error::Plugin(::co::plugin::Error::Operation("CUDA logarithmic softmax requires input and output of the same size.")));
+                }
+                let src = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x, self.device()) }) as *const $t;
+                let dest = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr_mut(result, self.device()) }) as *mut $t;
+
+                for slice in 0..calls {
+                    let offset = (slice * slice_len) as isize;
+                    let scal_params: ::cudnn::utils::ScalParams<$t> = ::cudnn::utils::ScalParams::default();
+                    try!(match CUDNN.log_softmax_forward(
+                        &desc, // src_desc
+                        unsafe { src.offset(offset) } as *const ::libc::c_void, //src_data
+                        &desc, // dest_desc
+                        unsafe { dest.offset(offset) } as *mut ::libc::c_void, // dest_data
+                        scal_params
+                    ) {
+                        Ok(_) => Ok(()),
+                        Err(_) => {
+                            Err(::co::plugin::Error::Operation("Unable to execute CUDA cuDNN logarithmic softmax Forward."))
+                        }
+                    });
+                }
+                Ok(())
+            }
+
+            fn log_softmax_axis_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+
+                self.log_softmax_axis_grad_plain(x, x_diff, result_diff, axis)
+            }
+
+            fn log_softmax_axis_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let (desc, calls, slice_len) = try!(x.cudnn_tensor_desc_softmax_axis(axis));
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(::co::error::Error::Plugin(::co::plugin::Error::Operation("CUDA logarithmic softmax requires inputs and output of the same size.")));
+                }
+                let src = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x, self.device()) }) as *const $t;
+                let src_diff = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x_diff, self.device()) }) as *const $t;
+                let dest_diff = try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr_mut(result_diff, self.device()) }) as *mut $t;
+
+                for slice in 0..calls {
+                    let offset = (slice * slice_len) as isize;
+                    let scal_params: ::cudnn::utils::ScalParams<$t> = ::cudnn::utils::ScalParams::default();
+                    try!(match CUDNN.log_softmax_backward(
+                        &desc, // src_desc
+                        unsafe { src.offset(offset) } as *const ::libc::c_void, //src_data
+                        &desc, // src_diff_desc
+                        unsafe { src_diff.offset(offset) } as *const ::libc::c_void, //src_diff_data
+                        &desc, // dest_diff_desc
+                        unsafe { dest_diff.offset(offset) } as *mut ::libc::c_void, // dest_diff_data
+                        scal_params
+                    ) {
+                        Ok(_) => Ok(()),
+                        Err(_) => {
+                            Err(::co::plugin::Error::Operation("Unable to execute CUDA cuDNN logarithmic softmax Backward."))
+                        }
+                    });
+                }
+                Ok(())
+            }
         }
     )
 }
@@ -900,7 +1088,21 @@ macro_rules! impl_ops_pooling_for {
                 padding: &[i32],
                 stride: &[i32],
             ) -> Result<Self::CPOOL, ::co::error::Error> {
-                let pooling_avg = ::cudnn::PoolingDescriptor::new(::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_AVERAGE_COUNT_EXCLUDE_PADDING, window, padding, stride).unwrap();
+                ::plugin::Pooling::<$t>::new_pooling_avg_config(self, window, padding, stride, ::plugin::PoolingAvgMode::ExcludePadding)
+            }
+
+            fn new_pooling_avg_config(
+                &self,
+                window: &[i32],
+                padding: &[i32],
+                stride: &[i32],
+                avg_mode: ::plugin::PoolingAvgMode,
+            ) -> Result<Self::CPOOL, ::co::error::Error> {
+                let avg_mode = match avg_mode {
+                    ::plugin::PoolingAvgMode::IncludePadding => ::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_AVERAGE_COUNT_INCLUDE_PADDING,
+                    ::plugin::PoolingAvgMode::ExcludePadding => ::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_AVERAGE_COUNT_EXCLUDE_PADDING,
+                };
+                let pooling_avg = ::cudnn::PoolingDescriptor::new(avg_mode, window, padding, stride).unwrap();
                 let pooling_max = ::cudnn::PoolingDescriptor::new(::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_MAX, window, padding, stride).unwrap();
                 Ok(::cudnn::utils::PoolingConfig::new(pooling_avg, pooling_max))
             }
@@ -986,6 +1188,86 @@ macro_rules! impl_ops_pooling_for {
                     }
                 }))
             }
+
+            fn pooling_avg(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+
+                self.pooling_avg_plain(x, result, config)
+            }
+
+            fn pooling_avg_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                let scal_params: ::cudnn::utils::ScalParams<$t> = ::cudnn::utils::ScalParams::default();
+
+                Ok(try!(match CUDNN.pooling_avg_forward(
+                    config,
+                    &try!(x.cudnn_tensor_desc()), // src_desc
+                    try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x, self.device()) }), //src_data
+                    &try!(result.cudnn_tensor_desc()), // dest_desc
+                    try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr_mut(result, self.device()) }), // dest_data
+                    scal_params
+                ) {
+                    Ok(_) => Ok(()),
+                    Err(_) => {
+                        Err(::co::plugin::Error::Operation("Unable to execute CUDA cuDNN average pooling Forward."))
+                    }
+                }))
+            }
+
+            fn pooling_avg_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+
+                self.pooling_avg_grad_plain(x, x_diff, result, result_diff, config)
+            }
+
+            fn pooling_avg_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                let scal_params: ::cudnn::utils::ScalParams<$t> = ::cudnn::utils::ScalParams::default();
+
+                Ok(try!(match CUDNN.pooling_avg_backward(
+                    config,
+                    &try!(x.cudnn_tensor_desc()), // src_desc
+                    try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x, self.device()) }), //src_data
+                    &try!(x_diff.cudnn_tensor_desc()), // src_diff_desc
+                    try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(x_diff, self.device()) }), //src_diff_data
+                    &try!(result.cudnn_tensor_desc()), // dest_desc
+                    try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr(result, self.device()) }), // dest_data
+                    &try!(result_diff.cudnn_tensor_desc()), // dest_diff_desc
+                    try!(unsafe { ::frameworks::cuda::helper::receive_memory_ptr_mut(result_diff, self.device()) }), // dest_diff_data
+                    scal_params
+                ) {
+                    Ok(_) => Ok(()),
+                    Err(_) => {
+                        Err(::co::plugin::Error::Operation("Unable to execute CUDA cuDNN average pooling Backward."))
+                    }
+                }))
+            }
         }
     )
 }
@@ -17,6 +17,11 @@ pub trait ICudnnDesc<T> {
     /// Creates a TensorDescriptor similar to `cudnn_tensor_desc`,
     /// but will create a fitting 4D tensor if the actual tensor would be 1D-3D.
     fn cudnn_tensor_desc_softmax(&self) -> Result<TensorDescriptor, PluginError>;
+    /// Creates a TensorDescriptor for a softmax along `axis`.
+    ///
+    /// The descriptor covers one or more consecutive slices in front of `axis`, and is returned
+    /// together with the number of descriptor calls needed and the number of elements each one covers.
+    fn cudnn_tensor_desc_softmax_axis(&self, axis: usize) -> Result<(TensorDescriptor, usize, usize), PluginError>;
     /// Creates a TensorDescriptor similar to `cudnn_tensor_desc`,
     /// but will create a fitting 3D tensor if the actual tensor would be 1D/2D.
     ///
@@ -62,6 +67,29 @@ macro_rules! impl_icudnndesc_for_sharedtensor {
                 }
             }
 
+            fn cudnn_tensor_desc_softmax_axis(&self, axis: usize) -> Result<(TensorDescriptor, usize, usize), PluginError> {
+                let actual_desc = self.desc().clone();
+                if axis >= actual_desc.len() {
+                    return Err(PluginError::Plugin("Softmax axis is out of range for the Tensor."));
+                }
+                let outer = actual_desc[..axis].iter().fold(1, |acc, d| acc * d);
+                let inner = actual_desc[axis + 1..].iter().fold(1, |acc, d| acc * d);
+                // cuDNN normalizes over the channels of every instance. Without trailing dimensions
+                // all slices are `outer` packed instances of `actual_desc[axis]` channels, otherwise
+                // a slice is described as `inner` instances of `actual_desc[axis]` strided channels.
+                let (dims, stride, calls) = if inner == 1 {
+                    (vec![outer, actual_desc[axis], 1, 1], vec![actual_desc[axis], 1, 1, 1], 1)
+                } else {
+                    (vec![inner, actual_desc[axis], 1, 1], vec![1, inner, 1, 1], outer)
+                };
+                match TensorDescriptor::new(&dims.dims_i32(), &stride.dims_i32(), $cutype) {
+                    Ok(desc) => Ok((desc, calls, dims[0] * actual_desc[axis])),
+                    Err(_) => {
+                        Err(PluginError::Plugin("Unable to create CuDNN TensorDescriptor."))
+                    }
+                }
+            }
+
             fn cudnn_tensor_desc_flat(&self) -> Result<TensorDescriptor, PluginError> {
                 let actual_desc = self.desc().clone();
                 let mut override_desc = match actual_desc.len() {
@@ -121,6 +149,7 @@ impl ConvForwardAlgo {
             ConvForwardAlgo::FFT => ::cudnn::cudnnConvolutionFwdAlgo_t::CUDNN_CONVOLUTION_FWD_ALGO_FFT,
             ConvForwardAlgo::FFTTiling => ::cudnn::cudnnConvolutionFwdAlgo_t::CUDNN_CONVOLUTION_FWD_ALGO_FFT_TILING,
             ConvForwardAlgo::Direct => ::cudnn::cudnnConvolutionFwdAlgo_t::CUDNN_CONVOLUTION_FWD_ALGO_DIRECT,
+            ConvForwardAlgo::Winograd => return Err(::co::error::Error::Plugin(::co::plugin::Error::Plugin("cuDNN does not provide the Winograd convolution forward algorithm."))),
         })
     }
 
@@ -161,6 +190,7 @@ impl ConvBackwardFilterAlgo {
     fn as_cudnn(&self) -> Result<cudnnConvolutionBwdFilterAlgo_t, ::co::error::Error> {
         Ok(match *self {
             ConvBackwardFilterAlgo::Auto => return Err(::co::error::Error::Plugin(::co::plugin::Error::Plugin("Can't create cuDNN convolution backward filter algorithm from ConvBackwardFilterAlgo::Auto. Use `find_cudnn_algo` to find an algorithm."))),
+            ConvBackwardFilterAlgo::GEMM => return Err(::co::error::Error::Plugin(::co::plugin::Error::Plugin("cuDNN does not provide the explicit GEMM convolution backward filter algorithm."))),
             ConvBackwardFilterAlgo::ImplicitGEMM => ::cudnn::cudnnConvolutionBwdFilterAlgo_t::CUDNN_CONVOLUTION_BWD_FILTER_ALGO_1,
             ConvBackwardFilterAlgo::ImplicitGEMMSum => ::cudnn::cudnnConvolutionBwdFilterAlgo_t::CUDNN_CONVOLUTION_BWD_FILTER_ALGO_0,
             ConvBackwardFilterAlgo::ImplicitPrecompiledGEMMSum => ::cudnn::cudnnConvolutionBwdFilterAlgo_t::CUDNN_CONVOLUTION_BWD_FILTER_ALGO_3,
@@ -203,6 +233,7 @@ impl ConvBackwardDataAlgo {
     fn as_cudnn(&self) -> Result<cudnnConvolutionBwdDataAlgo_t, ::co::error::Error> {
         Ok(match *self {
             ConvBackwardDataAlgo::Auto => return Err(::co::error::Error::Plugin(::co::plugin::Error::Plugin("Can't create cuDNN convolution backward data algorithm from ConvBackwardDataAlgo::Auto. Use `find_cudnn_algo` to find an algorithm."))),
+            ConvBackwardDataAlgo::GEMM => return Err(::co::error::Error::Plugin(::co::plugin::Error::Plugin("cuDNN does not provide the explicit GEMM convolution backward data algorithm."))),
             ConvBackwardDataAlgo::ImplicitGEMM => ::cudnn::cudnnConvolutionBwdDataAlgo_t::CUDNN_CONVOLUTION_BWD_DATA_ALGO_1,
             ConvBackwardDataAlgo::ImplicitGEMMSum => ::cudnn::cudnnConvolutionBwdDataAlgo_t::CUDNN_CONVOLUTION_BWD_DATA_ALGO_0,
             ConvBackwardDataAlgo::FFT => ::cudnn::cudnnConvolutionBwdDataAlgo_t::CUDNN_CONVOLUTION_BWD_DATA_ALGO_FFT,
@@ -259,7 +290,15 @@ macro_rules! impl_convolution_for_cuda_backend {
                 algo_bwd_data: ConvBackwardDataAlgo,
                 stride: &[i32],
                 zero_padding: &[i32],
+                dilation: &[i32],
+                groups: usize,
             ) -> Result<Self::CC, ::co::error::Error> {
+                if dilation.iter().any(|&d| d != 1) {
+                    return Err(::co::error::Error::Plugin(::co::plugin::Error::Plugin("cuDNN v3 does not support dilated convolutions.")));
+                }
+                if groups != 1 {
+                    return Err(::co::error::Error::Plugin(::co::plugin::Error::Plugin("cuDNN v3 does not support grouped convolutions.")));
+                }
                 let src_desc = try!(src.cudnn_tensor_desc());
                 let dest_desc = try!(dest.cudnn_tensor_desc());
                 let filter_desc = try!(filter.cudnn_filter_desc());
@@ -269,7 +308,7 @@ macro_rules! impl_convolution_for_cuda_backend {
                 let useable_algo_bwd_filter = try!(algo_bwd_filter.find_cudnn_algo(&filter_desc, &conv_desc, &src_desc, &dest_desc));
                 let useable_algo_bwd_data = try!(algo_bwd_data.find_cudnn_algo(&filter_desc, &conv_desc, &src_desc, &dest_desc));
 
-                let mut workspace_size_fwd = API::get_convolution_forward_workspace_size(*CUDNN.id_c(), useable_algo_fwd.as_cudnn().unwrap(), *filter_desc.id_c(), *conv_desc.id_c(), *src_desc.id_c(), *dest_desc.id_c()).unwrap();
+                let mut workspace_size_fwd = API::get_convolution_forward_workspace_size(*CUDNN.id_c(), try!(useable_algo_fwd.as_cudnn()), *filter_desc.id_c(), *conv_desc.id_c(), *src_desc.id_c(), *dest_desc.id_c()).unwrap();
                 let mut workspace_size_bwd_filter = API::get_convolution_backward_filter_workspace_size(*CUDNN.id_c(), useable_algo_bwd_filter.as_cudnn().unwrap(), *filter_desc.id_c(), *conv_desc.id_c(), *src_desc.id_c(), *dest_desc.id_c()).unwrap();
                 let mut workspace_size_bwd_data = API::get_convolution_backward_data_workspace_size(*CUDNN.id_c(), useable_algo_bwd_data.as_cudnn().unwrap(), *filter_desc.id_c(), *conv_desc.id_c(), *src_desc.id_c(), *dest_desc.id_c()).unwrap();
 
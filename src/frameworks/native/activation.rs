@@ -0,0 +1,176 @@
+//! Provides the native kernels of the activation functions of `ActivationMode`.
+//!
+//! Sigmoid, ReLU and tanh run on the vectorized kernels of the [simd][simd] module,
+//! all parametric functions are computed element-wise across the native threads.
+//! The clipped ReLU passes gradients only where its output lies strictly between zero and the ceiling.
+//!
+//! [simd]: ../simd/index.html
+
+use co::plugin::numeric_helpers::Float;
+use co::plugin::Error as PluginError;
+use plugin::ActivationMode;
+use super::parallel::{self, ThreadPool};
+use super::simd::{self, Activations, Function};
+
+/// The `lambda` of the scaled exponential linear unit.
+const SELU_LAMBDA: f64 = 1.0507009873554804934193349852946;
+/// The `alpha` of the scaled exponential linear unit.
+const SELU_ALPHA: f64 = 1.6732632423543772848170429916717;
+/// The `sqrt(2 / pi)` of the tanh approximation of the Gaussian error linear unit.
+const GELU_SCALE: f64 = 0.7978845608028654;
+/// The cubic coefficient of the tanh approximation of the Gaussian error linear unit.
+const GELU_CUBIC: f64 = 0.044715;
+
+/// Returns the vectorized kernel of `mode`, if there is one.
+fn simd_function(mode: ActivationMode) -> Option<Function> {
+    match mode {
+        ActivationMode::Sigmoid => Some(Function::Sigmoid),
+        ActivationMode::Relu => Some(Function::Relu),
+        ActivationMode::Tanh => Some(Function::Tanh),
+        _ => None,
+    }
+}
+
+/// Checks that the parameters of `mode` describe a valid activation function.
+pub fn check_mode(mode: ActivationMode) -> Result<(), PluginError> {
+    match mode {
+        ActivationMode::ClippedRelu(ceiling) if !ceiling.is_finite() || ceiling <= 0f64 => Err(PluginError::Operation("Native clipped ReLU requires a finite, positive ceiling.")),
+        ActivationMode::LeakyRelu(alpha) if !alpha.is_finite() => Err(PluginError::Operation("Native LeakyRelu requires a finite alpha.")),
+        ActivationMode::Elu(alpha) if !alpha.is_finite() || alpha < 0f64 => Err(PluginError::Operation("Native Elu requires a finite, non-negative alpha.")),
+        ActivationMode::Swish(beta) if !beta.is_finite() => Err(PluginError::Operation("Native Swish requires a finite beta.")),
+        _ => Ok(()),
+    }
+}
+
+fn cast<T: Float>(value: f64) -> T {
+    T::from(value).unwrap()
+}
+
+fn logistic<T: Float>(x: T) -> T {
+    T::one() / (T::one() + (-x).exp())
+}
+
+/// Returns the activation function `mode` at `x`.
+fn activate<T: Float>(mode: ActivationMode, x: T) -> T {
+    let zero = T::zero();
+    match mode {
+        ActivationMode::Sigmoid => logistic(x),
+        ActivationMode::Relu => x.max(zero),
+        ActivationMode::ClippedRelu(ceiling) => x.max(zero).min(cast(ceiling)),
+        ActivationMode::Tanh => x.tanh(),
+        ActivationMode::LeakyRelu(alpha) => if x > zero { x } else { cast::<T>(alpha) * x },
+        ActivationMode::Elu(alpha) => if x > zero { x } else { cast::<T>(alpha) * x.exp_m1() },
+        ActivationMode::Selu => cast::<T>(SELU_LAMBDA) * if x > zero { x } else { cast::<T>(SELU_ALPHA) * x.exp_m1() },
+        ActivationMode::Gelu => {
+            let inner = cast::<T>(GELU_SCALE) * (x + cast::<T>(GELU_CUBIC) * x * x * x);
+            cast::<T>(0.5) * x * (T::one() + inner.tanh())
+        },
+        // rewritten so that neither large positive nor large negative inputs overflow.
+        ActivationMode::Softplus => x.max(zero) + (-x.abs()).exp().ln_1p(),
+        ActivationMode::Swish(beta) => x * logistic(cast::<T>(beta) * x),
+    }
+}
+
+/// Returns the derivative of the activation function `mode` at the input `x` with the output `y`.
+fn derivative<T: Float>(mode: ActivationMode, x: T, y: T) -> T {
+    let zero = T::zero();
+    match mode {
+        ActivationMode::Sigmoid => y * (T::one() - y),
+        ActivationMode::Relu => if x > zero { T::one() } else { zero },
+        ActivationMode::ClippedRelu(ceiling) => if x > zero && x < cast(ceiling) { T::one() } else { zero },
+        ActivationMode::Tanh => T::one() - y * y,
+        ActivationMode::LeakyRelu(alpha) => if x > zero { T::one() } else { cast(alpha) },
+        ActivationMode::Elu(alpha) => if x > zero { T::one() } else { y + cast(alpha) },
+        ActivationMode::Selu => if x > zero { cast(SELU_LAMBDA) } else { y + cast(SELU_LAMBDA * SELU_ALPHA) },
+        ActivationMode::Gelu => {
+            let (scale, cubic) = (cast::<T>(GELU_SCALE), cast::<T>(GELU_CUBIC));
+            let t = (scale * (x + cubic * x * x * x)).tanh();
+            let half = cast::<T>(0.5);
+            half * (T::one() + t) + half * x * (T::one() - t * t) * scale * (T::one() + cast::<T>(3f64) * cubic * x * x)
+        },
+        ActivationMode::Softplus => logistic(x),
+        ActivationMode::Swish(beta) => {
+            let s = logistic(cast::<T>(beta) * x);
+            s + cast::<T>(beta) * y * (T::one() - s)
+        },
+    }
+}
+
+/// Returns the derivative of the activation function `mode` at the output `y`.
+///
+/// Only defined for the modes whose derivative is a function of their output.
+fn output_derivative<T: Float>(mode: ActivationMode, y: T) -> T {
+    let zero = T::zero();
+    match mode {
+        ActivationMode::ClippedRelu(ceiling) => if y > zero && y < cast(ceiling) { T::one() } else { zero },
+        ActivationMode::LeakyRelu(alpha) => if y > zero { T::one() } else { cast(alpha) },
+        ActivationMode::Elu(alpha) => if y > zero { T::one() } else { y + cast(alpha) },
+        ActivationMode::Selu => if y > zero { cast(SELU_LAMBDA) } else { y + cast(SELU_LAMBDA * SELU_ALPHA) },
+        // the logistic function of the input is `1 - e^-y`.
+        ActivationMode::Softplus => -(-y).exp_m1(),
+        _ => unreachable!(),
+    }
+}
+
+/// Writes the activation function `mode` of every element of `x` into `y`.
+pub fn forward<T: Activations>(pool: &ThreadPool, mode: ActivationMode, x: &[T], y: &mut [T]) -> Result<(), PluginError> {
+    try!(check_mode(mode));
+    let len = ::std::cmp::min(x.len(), y.len());
+    if let Some(function) = simd_function(mode) {
+        parallel::for_each_chunk_mut(pool, &mut y[..len], 1, |offset, chunk| simd::forward(function, &x[offset..], chunk));
+        return Ok(());
+    }
+    parallel::map(pool, &x[..len], &mut y[..len], |x| activate(mode, *x));
+    Ok(())
+}
+
+/// Replaces every element of `x` with the activation function `mode` of it.
+pub fn forward_in_place<T: Activations>(pool: &ThreadPool, mode: ActivationMode, x: &mut [T]) -> Result<(), PluginError> {
+    try!(check_mode(mode));
+    if let Some(function) = simd_function(mode) {
+        parallel::for_each_chunk_mut(pool, x, 1, |_, chunk| simd::forward_in_place(function, chunk));
+        return Ok(());
+    }
+    parallel::map_in_place(pool, x, |x| activate(mode, *x));
+    Ok(())
+}
+
+/// Writes the gradient of `mode` at the inputs `x`, outputs `y` and output gradients `dy` into `dx`.
+pub fn backward<T: Activations>(pool: &ThreadPool, mode: ActivationMode, x: &[T], y: &[T], dy: &[T], dx: &mut [T]) -> Result<(), PluginError> {
+    try!(check_mode(mode));
+    if let Some(function) = simd_function(mode) {
+        let len = ::std::cmp::min(::std::cmp::min(y.len(), dy.len()), dx.len());
+        parallel::for_each_chunk_mut(pool, &mut dx[..len], 1, |offset, chunk| simd::backward(function, &y[offset..], &dy[offset..], chunk));
+        return Ok(());
+    }
+    let len = ::std::cmp::min(::std::cmp::min(x.len(), y.len()), ::std::cmp::min(dy.len(), dx.len()));
+    parallel::for_each_chunk_mut(pool, &mut dx[..len], 1, |offset, chunk| {
+        for (index, out) in chunk.iter_mut().enumerate() {
+            let i = offset + index;
+            *out = derivative(mode, x[i], y[i]) * dy[i];
+        }
+    });
+    Ok(())
+}
+
+/// Replaces every output gradient of `dy` with the gradient of `mode` at the outputs `y`.
+///
+/// Fails for the modes whose gradient is not a function of their output.
+pub fn backward_in_place<T: Activations>(pool: &ThreadPool, mode: ActivationMode, y: &[T], dy: &mut [T]) -> Result<(), PluginError> {
+    try!(check_mode(mode));
+    match mode {
+        ActivationMode::Gelu => return Err(PluginError::Operation("Native pointwise Gelu gradient requires the input, use the non-pointwise gradient.")),
+        ActivationMode::Swish(_) => return Err(PluginError::Operation("Native pointwise Swish gradient requires the input, use the non-pointwise gradient.")),
+        ActivationMode::LeakyRelu(alpha) if alpha < 0f64 => {
+            return Err(PluginError::Operation("Native pointwise LeakyRelu gradient requires a non-negative alpha, use the non-pointwise gradient."))
+        },
+        _ => (),
+    }
+    if let Some(function) = simd_function(mode) {
+        let len = ::std::cmp::min(y.len(), dy.len());
+        parallel::for_each_chunk_mut(pool, &mut dy[..len], 1, |offset, chunk| simd::backward_in_place(function, &y[offset..], chunk));
+        return Ok(());
+    }
+    parallel::zip_map_in_place(pool, y, dy, |y, dy| output_derivative(mode, *y) * *dy);
+    Ok(())
+}
@@ -0,0 +1,825 @@
+//! Provides the native convolution algorithms.
+//!
+//! Every pass is available as explicit GEMM over an im2col column buffer, as implicit GEMM
+//! directly on the tensors and as product of 2D Fourier transforms.
+//! The forward pass of 3x3 filters with a stride and dilation of 1 is also available as Winograd F(2x2, 3x3)
+//! and all passes of depthwise 3x3 filters as direct loops over one channel at a time.
+//! `Auto` algorithms are resolved by timing every native candidate on the configured shapes.
+
+use co::plugin::numeric_helpers::Float;
+use co::plugin::Error as PluginError;
+use plugin::{ConvForwardAlgo, ConvBackwardFilterAlgo, ConvBackwardDataAlgo};
+use super::helper::{self, ConvAlgo, ConvolutionConfig};
+use super::parallel::{self, ThreadPool};
+use std::time::Instant;
+
+/// The `(batch, height, width, output height, output width)` of a convolution.
+pub type ConvDims = (usize, usize, usize, usize, usize);
+
+/// Returns the native algorithms that can compute `algo` for `config`.
+pub fn forward_candidates(algo: ConvForwardAlgo, config: &ConvolutionConfig) -> Result<Vec<ConvAlgo>, PluginError> {
+    match algo {
+        ConvForwardAlgo::Auto => {
+            let mut algos = vec![ConvAlgo::GEMM, ConvAlgo::ImplicitGEMM, ConvAlgo::FFT];
+            if config.supports_winograd() {
+                algos.push(ConvAlgo::Winograd);
+            }
+            if config.supports_depthwise() {
+                algos.push(ConvAlgo::Depthwise);
+            }
+            Ok(algos)
+        },
+        // the column buffer is the native form of precompiled indices.
+        ConvForwardAlgo::GEMM | ConvForwardAlgo::ImplicitPrecompiledGEMM => Ok(vec![ConvAlgo::GEMM]),
+        ConvForwardAlgo::ImplicitGEMM => Ok(vec![ConvAlgo::ImplicitGEMM]),
+        ConvForwardAlgo::FFT => Ok(vec![ConvAlgo::FFT]),
+        ConvForwardAlgo::FFTTiling => Err(PluginError::Plugin("Native convolution does not support the FFTTiling forward algorithm.")),
+        ConvForwardAlgo::Direct if config.supports_depthwise() => Ok(vec![ConvAlgo::Depthwise]),
+        ConvForwardAlgo::Direct => Err(PluginError::Plugin("Native convolution only supports the Direct forward algorithm for depthwise 3x3 filters.")),
+        ConvForwardAlgo::Winograd if config.supports_winograd() => Ok(vec![ConvAlgo::Winograd]),
+        ConvForwardAlgo::Winograd => Err(PluginError::Plugin("Native Winograd convolution requires a 3x3 filter with a stride and dilation of 1.")),
+    }
+}
+
+/// Returns the native algorithms that can compute `algo` for `config`.
+pub fn backward_filter_candidates(algo: ConvBackwardFilterAlgo, config: &ConvolutionConfig) -> Result<Vec<ConvAlgo>, PluginError> {
+    match algo {
+        ConvBackwardFilterAlgo::Auto if config.supports_depthwise() => Ok(vec![ConvAlgo::GEMM, ConvAlgo::ImplicitGEMM, ConvAlgo::FFT, ConvAlgo::Depthwise]),
+        ConvBackwardFilterAlgo::Auto => Ok(vec![ConvAlgo::GEMM, ConvAlgo::ImplicitGEMM, ConvAlgo::FFT]),
+        ConvBackwardFilterAlgo::GEMM => Ok(vec![ConvAlgo::GEMM]),
+        ConvBackwardFilterAlgo::ImplicitGEMM => Ok(vec![ConvAlgo::ImplicitGEMM]),
+        ConvBackwardFilterAlgo::FFT => Ok(vec![ConvAlgo::FFT]),
+        ConvBackwardFilterAlgo::ImplicitGEMMSum => Err(PluginError::Plugin("Native convolution does not support the non-deterministic ImplicitGEMMSum backward filter algorithm.")),
+        ConvBackwardFilterAlgo::ImplicitPrecompiledGEMMSum => Err(PluginError::Plugin("Native convolution does not support the non-deterministic ImplicitPrecompiledGEMMSum backward filter algorithm.")),
+    }
+}
+
+/// Returns the native algorithms that can compute `algo` for `config`.
+pub fn backward_data_candidates(algo: ConvBackwardDataAlgo, config: &ConvolutionConfig) -> Result<Vec<ConvAlgo>, PluginError> {
+    match algo {
+        ConvBackwardDataAlgo::Auto if config.supports_depthwise() => Ok(vec![ConvAlgo::GEMM, ConvAlgo::ImplicitGEMM, ConvAlgo::FFT, ConvAlgo::Depthwise]),
+        ConvBackwardDataAlgo::Auto => Ok(vec![ConvAlgo::GEMM, ConvAlgo::ImplicitGEMM, ConvAlgo::FFT]),
+        ConvBackwardDataAlgo::GEMM => Ok(vec![ConvAlgo::GEMM]),
+        ConvBackwardDataAlgo::ImplicitGEMM => Ok(vec![ConvAlgo::ImplicitGEMM]),
+        ConvBackwardDataAlgo::FFT => Ok(vec![ConvAlgo::FFT]),
+        ConvBackwardDataAlgo::ImplicitGEMMSum => Err(PluginError::Plugin("Native convolution does not support the non-deterministic ImplicitGEMMSum backward data algorithm.")),
+        ConvBackwardDataAlgo::FFTTiling => Err(PluginError::Plugin("Native convolution does not support the FFTTiling backward data algorithm.")),
+    }
+}
+
+/// The number of timed runs of every candidate, of which the fastest counts.
+const TIMED_RUNS: usize = 3;
+
+/// Returns the candidate for which `run` takes the least time.
+///
+/// Every candidate runs once untimed to warm up its caches and workspace, then `TIMED_RUNS` times.
+fn fastest<F: FnMut(ConvAlgo)>(candidates: &[ConvAlgo], mut run: F) -> ConvAlgo {
+    if candidates.len() == 1 {
+        return candidates[0];
+    }
+    candidates.iter().map(|&algo| {
+        run(algo);
+        let best = (0..TIMED_RUNS).map(|_| {
+            let start = Instant::now();
+            run(algo);
+            start.elapsed()
+        }).min().unwrap();
+        (best, algo)
+    }).min_by_key(|&(elapsed, _)| elapsed).unwrap().1
+}
+
+/// Sets the algorithms of `config` for the requested algorithms and the output shape `dest`.
+///
+/// Requests with several native candidates are resolved by timing each candidate,
+/// buffers for the timing are only allocated if a request has several candidates.
+pub fn select_algos<T: Float + Send + Sync>(pool: &ThreadPool, config: &mut ConvolutionConfig, dest: &[usize], algo_fwd: ConvForwardAlgo,
+                                            algo_bwd_filter: ConvBackwardFilterAlgo, algo_bwd_data: ConvBackwardDataAlgo) -> Result<(), PluginError> {
+    let forward_algos = try!(forward_candidates(algo_fwd, config));
+    let filter_algos = try!(backward_filter_candidates(algo_bwd_filter, config));
+    let data_algos = try!(backward_data_candidates(algo_bwd_data, config));
+    let src = config.input_shape.clone();
+    let dims = try!(config.check_shapes(&src, dest, &config.filter_shape));
+    if forward_algos.len() == 1 && filter_algos.len() == 1 && data_algos.len() == 1 {
+        config.forward_algo = forward_algos[0];
+        config.backward_filter_algo = filter_algos[0];
+        config.backward_data_algo = data_algos[0];
+        return Ok(());
+    }
+    let len = |shape: &[usize]| shape.iter().fold(1, |acc, d| acc * d);
+    // only the candidates of requests with several of them run here.
+    let workspace_len = [&forward_algos, &filter_algos, &data_algos].iter()
+        .filter(|algos| algos.len() > 1)
+        .flat_map(|algos| algos.iter())
+        .map(|&algo| config.algo_workspace_len(algo, dims.1, dims.2))
+        .fold(0, ::std::cmp::max);
+    let mut input = vec![T::one(); len(&src)];
+    let mut weights = vec![T::one(); len(&config.filter_shape)];
+    let mut output = vec![T::one(); len(dest)];
+    let mut workspace = vec![T::zero(); workspace_len];
+
+    let forward_algo = fastest(&forward_algos, |algo| run_forward(pool, config, algo, dims, &weights, &input, &mut output, &mut workspace));
+    let filter_algo = fastest(&filter_algos, |algo| run_backward_filter(pool, config, algo, dims, &input, &output, &mut weights, &mut workspace));
+    let data_algo = fastest(&data_algos, |algo| run_backward_data(pool, config, algo, dims, &weights, &output, &mut input, &mut workspace));
+    config.forward_algo = forward_algo;
+    config.backward_filter_algo = filter_algo;
+    config.backward_data_algo = data_algo;
+    Ok(())
+}
+
+/// Computes the convolution of `input` with `weights` into `output` with the forward algorithm of `config`.
+pub fn forward<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, weights: &[T], input: &[T], output: &mut [T], workspace: &mut [T]) {
+    run_forward(pool, config, config.forward_algo, dims, weights, input, output, workspace)
+}
+
+/// Computes the gradient with respect to the filter into `weights_diff` with the filter algorithm of `config`.
+pub fn backward_filter<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, input: &[T], output_diff: &[T], weights_diff: &mut [T], workspace: &mut [T]) {
+    run_backward_filter(pool, config, config.backward_filter_algo, dims, input, output_diff, weights_diff, workspace)
+}
+
+/// Computes the gradient with respect to the data into `input_diff` with the data algorithm of `config`.
+pub fn backward_data<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, weights: &[T], output_diff: &[T], input_diff: &mut [T], workspace: &mut [T]) {
+    run_backward_data(pool, config, config.backward_data_algo, dims, weights, output_diff, input_diff, workspace)
+}
+
+fn run_forward<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, algo: ConvAlgo, dims: ConvDims, weights: &[T], input: &[T], output: &mut [T], workspace: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    // the channels of one group, which lie next to each other in the input, output and filter.
+    let (out_channels, channels) = (config.filter_shape[0] / config.groups, config.filter_shape[1]);
+    let col_rows = channels * config.filter_shape[2] * config.filter_shape[3];
+    let (in_size, out_size, filter_size) = (channels * height * width, out_channels * out_h * out_w, out_channels * col_rows);
+    match algo {
+        ConvAlgo::GEMM => {
+            let col = &mut workspace[..config.col_len(height, width)];
+            for n in 0..batch {
+                for g in 0..config.groups {
+                    let group = n * config.groups + g;
+                    helper::im2col(&input[group * in_size..(group + 1) * in_size], height, width, config, col);
+                    helper::gemm(pool, false, false, out_channels, out_h * out_w, col_rows, T::one(),
+                                 &weights[g * filter_size..(g + 1) * filter_size], col, T::zero(),
+                                 &mut output[group * out_size..(group + 1) * out_size]);
+                }
+            }
+        },
+        ConvAlgo::ImplicitGEMM => forward_implicit(pool, config, dims, weights, input, output),
+        ConvAlgo::FFT => forward_fft(config, dims, weights, input, output, workspace),
+        ConvAlgo::Winograd => forward_winograd(pool, config, dims, weights, input, output, workspace),
+        ConvAlgo::Depthwise => forward_depthwise(pool, config, dims, weights, input, output),
+    }
+}
+
+fn run_backward_filter<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, algo: ConvAlgo, dims: ConvDims, input: &[T], output_diff: &[T], weights_diff: &mut [T], workspace: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels) = (config.filter_shape[0] / config.groups, config.filter_shape[1]);
+    let col_rows = channels * config.filter_shape[2] * config.filter_shape[3];
+    let (in_size, out_size, filter_size) = (channels * height * width, out_channels * out_h * out_w, out_channels * col_rows);
+    match algo {
+        ConvAlgo::GEMM => {
+            let col = &mut workspace[..config.col_len(height, width)];
+            for n in 0..batch {
+                for g in 0..config.groups {
+                    let group = n * config.groups + g;
+                    helper::im2col(&input[group * in_size..(group + 1) * in_size], height, width, config, col);
+                    helper::gemm(pool, false, true, out_channels, col_rows, out_h * out_w, T::one(),
+                                 &output_diff[group * out_size..(group + 1) * out_size], col,
+                                 if n == 0 { T::zero() } else { T::one() }, &mut weights_diff[g * filter_size..(g + 1) * filter_size]);
+                }
+            }
+        },
+        ConvAlgo::ImplicitGEMM => backward_filter_implicit(pool, config, dims, input, output_diff, weights_diff),
+        ConvAlgo::FFT => backward_filter_fft(config, dims, input, output_diff, weights_diff, workspace),
+        ConvAlgo::Winograd => unreachable!("Winograd is only selected for the forward pass."),
+        ConvAlgo::Depthwise => backward_filter_depthwise(pool, config, dims, input, output_diff, weights_diff),
+    }
+}
+
+fn run_backward_data<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, algo: ConvAlgo, dims: ConvDims, weights: &[T], output_diff: &[T], input_diff: &mut [T], workspace: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels) = (config.filter_shape[0] / config.groups, config.filter_shape[1]);
+    let col_rows = channels * config.filter_shape[2] * config.filter_shape[3];
+    let (in_size, out_size, filter_size) = (channels * height * width, out_channels * out_h * out_w, out_channels * col_rows);
+    match algo {
+        ConvAlgo::GEMM => {
+            let col = &mut workspace[..config.col_len(height, width)];
+            for n in 0..batch {
+                for g in 0..config.groups {
+                    let group = n * config.groups + g;
+                    helper::gemm(pool, true, false, col_rows, out_h * out_w, out_channels, T::one(), &weights[g * filter_size..(g + 1) * filter_size],
+                                 &output_diff[group * out_size..(group + 1) * out_size], T::zero(), col);
+                    helper::col2im(col, height, width, config, &mut input_diff[group * in_size..(group + 1) * in_size]);
+                }
+            }
+        },
+        ConvAlgo::ImplicitGEMM => backward_data_implicit(pool, config, dims, weights, output_diff, input_diff),
+        ConvAlgo::FFT => backward_data_fft(config, dims, weights, output_diff, input_diff, workspace),
+        ConvAlgo::Winograd => unreachable!("Winograd is only selected for the forward pass."),
+        ConvAlgo::Depthwise => backward_data_depthwise(pool, config, dims, weights, output_diff, input_diff),
+    }
+}
+
+/// Returns the input position of the dilated filter offset `offset` at output position `out`,
+/// or `None` if it lies in the zero padding.
+#[inline]
+fn input_index(out: usize, offset: usize, stride: usize, padding: usize, size: usize) -> Option<usize> {
+    let position = out * stride + offset;
+    if position < padding || position - padding >= size {
+        None
+    } else {
+        Some(position - padding)
+    }
+}
+
+fn forward_implicit<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, weights: &[T], input: &[T], output: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels, filter_h, filter_w) = (config.filter_shape[0], config.filter_shape[1], config.filter_shape[2], config.filter_shape[3]);
+    let (groups, group_out_channels) = (config.groups, out_channels / config.groups);
+    let plane = out_h * out_w;
+    parallel::for_each_chunk_mut(pool, &mut output[..batch * out_channels * plane], plane, |offset, chunk| {
+        for (index, out) in chunk.chunks_mut(plane).enumerate() {
+            let (n, k) = ((offset / plane + index) / out_channels, (offset / plane + index) % out_channels);
+            for oh in 0..out_h {
+                for ow in 0..out_w {
+                    let mut sum = T::zero();
+                    for c in 0..channels {
+                        let image = &input[((n * groups + k / group_out_channels) * channels + c) * height * width..];
+                        let filter = &weights[(k * channels + c) * filter_h * filter_w..];
+                        for i in 0..filter_h {
+                            if let Some(h) = input_index(oh, i * config.dilation[0], config.stride[0], config.padding[0], height) {
+                                for j in 0..filter_w {
+                                    if let Some(w) = input_index(ow, j * config.dilation[1], config.stride[1], config.padding[1], width) {
+                                        sum = sum + filter[i * filter_w + j] * image[h * width + w];
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    out[oh * out_w + ow] = sum;
+                }
+            }
+        }
+    });
+}
+
+fn backward_filter_implicit<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, input: &[T], output_diff: &[T], weights_diff: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels, filter_h, filter_w) = (config.filter_shape[0], config.filter_shape[1], config.filter_shape[2], config.filter_shape[3]);
+    let (groups, group_out_channels) = (config.groups, out_channels / config.groups);
+    let filter_size = filter_h * filter_w;
+    parallel::for_each_chunk_mut(pool, &mut weights_diff[..out_channels * channels * filter_size], filter_size, |offset, chunk| {
+        for (index, filter) in chunk.chunks_mut(filter_size).enumerate() {
+            let (k, c) = ((offset / filter_size + index) / channels, (offset / filter_size + index) % channels);
+            for i in 0..filter_h {
+                for j in 0..filter_w {
+                    let mut sum = T::zero();
+                    for n in 0..batch {
+                        let image = &input[((n * groups + k / group_out_channels) * channels + c) * height * width..];
+                        let diff = &output_diff[(n * out_channels + k) * out_h * out_w..];
+                        for oh in 0..out_h {
+                            if let Some(h) = input_index(oh, i * config.dilation[0], config.stride[0], config.padding[0], height) {
+                                for ow in 0..out_w {
+                                    if let Some(w) = input_index(ow, j * config.dilation[1], config.stride[1], config.padding[1], width) {
+                                        sum = sum + diff[oh * out_w + ow] * image[h * width + w];
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    filter[i * filter_w + j] = sum;
+                }
+            }
+        }
+    });
+}
+
+fn backward_data_implicit<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, weights: &[T], output_diff: &[T], input_diff: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels, filter_h, filter_w) = (config.filter_shape[0], config.filter_shape[1], config.filter_shape[2], config.filter_shape[3]);
+    let plane = height * width;
+    // returns the output position that reads input position `position` with dilated filter offset `offset`.
+    let output_index = |position: usize, offset: usize, stride: usize, padding: usize, size: usize| {
+        let padded = position + padding;
+        if padded < offset || (padded - offset) % stride != 0 || (padded - offset) / stride >= size {
+            None
+        } else {
+            Some((padded - offset) / stride)
+        }
+    };
+    let (in_channels, group_out_channels) = (channels * config.groups, out_channels / config.groups);
+    parallel::for_each_chunk_mut(pool, &mut input_diff[..batch * in_channels * plane], plane, |offset, chunk| {
+        for (index, image) in chunk.chunks_mut(plane).enumerate() {
+            let (n, c) = ((offset / plane + index) / in_channels, (offset / plane + index) % in_channels);
+            // the group of input channel `c` and the position of `c` within it.
+            let (g, c) = (c / channels, c % channels);
+            for h in 0..height {
+                for w in 0..width {
+                    let mut sum = T::zero();
+                    for k in g * group_out_channels..(g + 1) * group_out_channels {
+                        let filter = &weights[(k * channels + c) * filter_h * filter_w..];
+                        let diff = &output_diff[(n * out_channels + k) * out_h * out_w..];
+                        for i in 0..filter_h {
+                            if let Some(oh) = output_index(h, i * config.dilation[0], config.stride[0], config.padding[0], out_h) {
+                                for j in 0..filter_w {
+                                    if let Some(ow) = output_index(w, j * config.dilation[1], config.stride[1], config.padding[1], out_w) {
+                                        sum = sum + filter[i * filter_w + j] * diff[oh * out_w + ow];
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    image[h * width + w] = sum;
+                }
+            }
+        }
+    });
+}
+
+/// Computes the in-place radix-2 FFT of the interleaved complex `data`, whose length is a power of two.
+///
+/// The inverse transform is not normalized.
+fn fft<T: Float>(data: &mut [T], inverse: bool) {
+    let len = data.len() / 2;
+    let mut j = 0;
+    for i in 1..len {
+        let mut bit = len >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(2 * i, 2 * j);
+            data.swap(2 * i + 1, 2 * j + 1);
+        }
+    }
+    let sign = if inverse { T::one() } else { -T::one() };
+    let two_pi = T::from(2f64 * ::std::f64::consts::PI).unwrap();
+    let mut size = 2;
+    while size <= len {
+        let half = size / 2;
+        for k in 0..half {
+            let angle = sign * two_pi * T::from(k).unwrap() / T::from(size).unwrap();
+            let (w_re, w_im) = (angle.cos(), angle.sin());
+            let mut start = 0;
+            while start < len {
+                let (a, b) = (2 * (start + k), 2 * (start + k + half));
+                let re = data[b] * w_re - data[b + 1] * w_im;
+                let im = data[b] * w_im + data[b + 1] * w_re;
+                data[b] = data[a] - re;
+                data[b + 1] = data[a + 1] - im;
+                data[a] = data[a] + re;
+                data[a + 1] = data[a + 1] + im;
+                start += size;
+            }
+        }
+        size *= 2;
+    }
+}
+
+/// Computes the in-place 2D FFT of the interleaved complex `rows` x `cols` matrix `data`.
+///
+/// The inverse transform is normalized.
+fn fft2<T: Float>(data: &mut [T], rows: usize, cols: usize, inverse: bool) {
+    for row in data.chunks_mut(2 * cols) {
+        fft(row, inverse);
+    }
+    let mut column = vec![T::zero(); 2 * rows];
+    for col in 0..cols {
+        for row in 0..rows {
+            column[2 * row] = data[2 * (row * cols + col)];
+            column[2 * row + 1] = data[2 * (row * cols + col) + 1];
+        }
+        fft(&mut column, inverse);
+        for row in 0..rows {
+            data[2 * (row * cols + col)] = column[2 * row];
+            data[2 * (row * cols + col) + 1] = column[2 * row + 1];
+        }
+    }
+    if inverse {
+        let scale = T::one() / T::from(rows * cols).unwrap();
+        for value in data.iter_mut() {
+            *value = *value * scale;
+        }
+    }
+}
+
+/// Writes the real `rows` x `cols` matrix `src` at `(row, col)` with `(row step, col step)` spacing
+/// into the zeroed interleaved complex transform `dest` of `fft_cols` columns and transforms it.
+fn load_fft<T: Float>(src: &[T], rows: usize, cols: usize, at: (usize, usize), step: (usize, usize),
+                      dest: &mut [T], fft_rows: usize, fft_cols: usize) {
+    for value in dest.iter_mut() {
+        *value = T::zero();
+    }
+    for r in 0..rows {
+        for c in 0..cols {
+            dest[2 * ((at.0 + r * step.0) * fft_cols + at.1 + c * step.1)] = src[r * cols + c];
+        }
+    }
+    fft2(dest, fft_rows, fft_cols, false);
+}
+
+/// Adds the product of the complex transforms `a` and `b`, or of `a` and the conjugate of `b`, to `acc`.
+fn multiply_add<T: Float>(a: &[T], b: &[T], conjugate: bool, acc: &mut [T]) {
+    let sign = if conjugate { -T::one() } else { T::one() };
+    for ((a, b), acc) in a.chunks(2).zip(b.chunks(2)).zip(acc.chunks_mut(2)) {
+        let b_im = sign * b[1];
+        acc[0] = acc[0] + a[0] * b[0] - a[1] * b_im;
+        acc[1] = acc[1] + a[0] * b_im + a[1] * b[0];
+    }
+}
+
+/// Transforms every `filter_h` x `filter_w` filter of `weights`, spread out by the dilation,
+/// into the consecutive interleaved complex transforms of `spectra`.
+fn load_filter_spectra<T: Float>(config: &ConvolutionConfig, weights: &[T], spectra: &mut [T], fft_h: usize, fft_w: usize) {
+    let filter_len = config.filter_shape[2] * config.filter_shape[3];
+    let size = 2 * fft_h * fft_w;
+    for (filter, spectrum) in weights.chunks(filter_len).zip(spectra.chunks_mut(size)) {
+        load_fft(filter, config.filter_shape[2], config.filter_shape[3], (0, 0),
+                 (config.dilation[0], config.dilation[1]), spectrum, fft_h, fft_w);
+    }
+}
+
+fn forward_fft<T: Float>(config: &ConvolutionConfig, dims: ConvDims, weights: &[T], input: &[T], output: &mut [T], workspace: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels, filter_h, filter_w) = (config.filter_shape[0], config.filter_shape[1], config.filter_shape[2], config.filter_shape[3]);
+    let (in_channels, group_out_channels) = (channels * config.groups, out_channels / config.groups);
+    let (fft_h, fft_w) = config.fft_dims(height, width);
+    let size = 2 * fft_h * fft_w;
+    // the filters are transformed once for the whole batch.
+    let (filters, rest) = workspace.split_at_mut(out_channels * channels * size);
+    let (spectra, rest) = rest.split_at_mut(in_channels * size);
+    let acc = &mut rest[..size];
+    load_filter_spectra(config, &weights[..out_channels * channels * filter_h * filter_w], filters, fft_h, fft_w);
+    for n in 0..batch {
+        for c in 0..in_channels {
+            load_fft(&input[(n * in_channels + c) * height * width..(n * in_channels + c + 1) * height * width], height, width,
+                     (config.padding[0], config.padding[1]), (1, 1), &mut spectra[c * size..(c + 1) * size], fft_h, fft_w);
+        }
+        for k in 0..out_channels {
+            for value in acc.iter_mut() {
+                *value = T::zero();
+            }
+            let first_channel = k / group_out_channels * channels;
+            for c in 0..channels {
+                let filter = &filters[(k * channels + c) * size..(k * channels + c + 1) * size];
+                multiply_add(&spectra[(first_channel + c) * size..(first_channel + c + 1) * size], filter, true, acc);
+            }
+            fft2(acc, fft_h, fft_w, true);
+            let out = &mut output[(n * out_channels + k) * out_h * out_w..];
+            for oh in 0..out_h {
+                for ow in 0..out_w {
+                    out[oh * out_w + ow] = acc[2 * (oh * config.stride[0] * fft_w + ow * config.stride[1])];
+                }
+            }
+        }
+    }
+}
+
+fn backward_filter_fft<T: Float>(config: &ConvolutionConfig, dims: ConvDims, input: &[T], output_diff: &[T], weights_diff: &mut [T], workspace: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels, filter_h, filter_w) = (config.filter_shape[0], config.filter_shape[1], config.filter_shape[2], config.filter_shape[3]);
+    let (in_channels, group_out_channels) = (channels * config.groups, out_channels / config.groups);
+    let (fft_h, fft_w) = config.fft_dims(height, width);
+    let size = 2 * fft_h * fft_w;
+    let (spectra, rest) = workspace.split_at_mut(in_channels * size);
+    let (diff, rest) = rest.split_at_mut(size);
+    let acc = &mut rest[..size];
+    for value in weights_diff[..out_channels * channels * filter_h * filter_w].iter_mut() {
+        *value = T::zero();
+    }
+    for n in 0..batch {
+        for c in 0..in_channels {
+            load_fft(&input[(n * in_channels + c) * height * width..(n * in_channels + c + 1) * height * width], height, width,
+                     (config.padding[0], config.padding[1]), (1, 1), &mut spectra[c * size..(c + 1) * size], fft_h, fft_w);
+        }
+        for k in 0..out_channels {
+            // the output gradient is spread out by the stride.
+            load_fft(&output_diff[(n * out_channels + k) * out_h * out_w..(n * out_channels + k + 1) * out_h * out_w], out_h, out_w,
+                     (0, 0), (config.stride[0], config.stride[1]), diff, fft_h, fft_w);
+            let first_channel = k / group_out_channels * channels;
+            for c in 0..channels {
+                for value in acc.iter_mut() {
+                    *value = T::zero();
+                }
+                multiply_add(&spectra[(first_channel + c) * size..(first_channel + c + 1) * size], diff, true, acc);
+                fft2(acc, fft_h, fft_w, true);
+                let filter = &mut weights_diff[(k * channels + c) * filter_h * filter_w..];
+                for i in 0..filter_h {
+                    for j in 0..filter_w {
+                        let position = i * config.dilation[0] * fft_w + j * config.dilation[1];
+                        filter[i * filter_w + j] = filter[i * filter_w + j] + acc[2 * position];
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn backward_data_fft<T: Float>(config: &ConvolutionConfig, dims: ConvDims, weights: &[T], output_diff: &[T], input_diff: &mut [T], workspace: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels, filter_h, filter_w) = (config.filter_shape[0], config.filter_shape[1], config.filter_shape[2], config.filter_shape[3]);
+    let (in_channels, group_out_channels) = (channels * config.groups, out_channels / config.groups);
+    let (fft_h, fft_w) = config.fft_dims(height, width);
+    let size = 2 * fft_h * fft_w;
+    // the filters are transformed once for the whole batch.
+    let (filters, rest) = workspace.split_at_mut(out_channels * channels * size);
+    let (spectra, rest) = rest.split_at_mut(out_channels * size);
+    let acc = &mut rest[..size];
+    load_filter_spectra(config, &weights[..out_channels * channels * filter_h * filter_w], filters, fft_h, fft_w);
+    for n in 0..batch {
+        for k in 0..out_channels {
+            load_fft(&output_diff[(n * out_channels + k) * out_h * out_w..(n * out_channels + k + 1) * out_h * out_w], out_h, out_w,
+                     (0, 0), (config.stride[0], config.stride[1]), &mut spectra[k * size..(k + 1) * size], fft_h, fft_w);
+        }
+        for c in 0..in_channels {
+            for value in acc.iter_mut() {
+                *value = T::zero();
+            }
+            let g = c / channels;
+            for k in g * group_out_channels..(g + 1) * group_out_channels {
+                let filter = k * channels + c % channels;
+                multiply_add(&spectra[k * size..(k + 1) * size], &filters[filter * size..(filter + 1) * size], false, acc);
+            }
+            fft2(acc, fft_h, fft_w, true);
+            let image = &mut input_diff[(n * in_channels + c) * height * width..];
+            for h in 0..height {
+                for w in 0..width {
+                    image[h * width + w] = acc[2 * ((h + config.padding[0]) * fft_w + w + config.padding[1])];
+                }
+            }
+        }
+    }
+}
+
+/// Computes the Winograd transform `G g G^T` of the 3x3 filter `g` into the 16 elements of `u`.
+fn winograd_filter<T: Float>(g: &[T], u: &mut [T; 16]) {
+    let half = T::from(0.5f64).unwrap();
+    // t = G g, a 4x3 matrix.
+    let mut t = [T::zero(); 12];
+    for j in 0..3 {
+        let (g0, g1, g2) = (g[j], g[3 + j], g[6 + j]);
+        t[j] = g0;
+        t[3 + j] = (g0 + g1 + g2) * half;
+        t[6 + j] = (g0 - g1 + g2) * half;
+        t[9 + j] = g2;
+    }
+    // u = t G^T, a 4x4 matrix.
+    for i in 0..4 {
+        let (t0, t1, t2) = (t[3 * i], t[3 * i + 1], t[3 * i + 2]);
+        u[4 * i] = t0;
+        u[4 * i + 1] = (t0 + t1 + t2) * half;
+        u[4 * i + 2] = (t0 - t1 + t2) * half;
+        u[4 * i + 3] = t2;
+    }
+}
+
+/// Computes the Winograd transform `B^T d B` of the 4x4 input tile `d` into `v`.
+fn winograd_input<T: Float>(d: &[T; 16], v: &mut [T; 16]) {
+    // t = B^T d.
+    let mut t = [T::zero(); 16];
+    for j in 0..4 {
+        t[j] = d[j] - d[8 + j];
+        t[4 + j] = d[4 + j] + d[8 + j];
+        t[8 + j] = d[8 + j] - d[4 + j];
+        t[12 + j] = d[4 + j] - d[12 + j];
+    }
+    // v = t B.
+    for i in 0..4 {
+        let row = &t[4 * i..4 * i + 4];
+        v[4 * i] = row[0] - row[2];
+        v[4 * i + 1] = row[1] + row[2];
+        v[4 * i + 2] = row[2] - row[1];
+        v[4 * i + 3] = row[1] - row[3];
+    }
+}
+
+/// Computes the inverse Winograd transform `A^T m A` of the 4x4 tile `m` into the 2x2 output tile `y`.
+fn winograd_output<T: Float>(m: &[T; 16], y: &mut [T; 4]) {
+    // t = A^T m, a 2x4 matrix.
+    let mut t = [T::zero(); 8];
+    for j in 0..4 {
+        t[j] = m[j] + m[4 + j] + m[8 + j];
+        t[4 + j] = m[4 + j] - m[8 + j] - m[12 + j];
+    }
+    for i in 0..2 {
+        let row = &t[4 * i..4 * i + 4];
+        y[2 * i] = row[0] + row[1] + row[2];
+        y[2 * i + 1] = row[1] - row[2] - row[3];
+    }
+}
+
+fn forward_winograd<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, weights: &[T], input: &[T], output: &mut [T], workspace: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels) = (config.filter_shape[0], config.filter_shape[1]);
+    let (in_channels, group_out_channels) = (channels * config.groups, out_channels / config.groups);
+    let (tiles_h, tiles_w) = ((out_h + 1) / 2, (out_w + 1) / 2);
+    let tiles = tiles_h * tiles_w;
+    // every transformed position is stored as matrix: filters as [16][K][C / G], inputs as [16][C][P], outputs as [16][K][P].
+    let (filters, rest) = workspace.split_at_mut(16 * out_channels * channels);
+    let (inputs, rest) = rest.split_at_mut(16 * in_channels * tiles);
+    let outputs = &mut rest[..16 * out_channels * tiles];
+    let mut u = [T::zero(); 16];
+    for k in 0..out_channels {
+        for c in 0..channels {
+            winograd_filter(&weights[(k * channels + c) * 9..(k * channels + c + 1) * 9], &mut u);
+            for xi in 0..16 {
+                filters[(xi * out_channels + k) * channels + c] = u[xi];
+            }
+        }
+    }
+    let (mut d, mut v, mut m, mut y) = ([T::zero(); 16], [T::zero(); 16], [T::zero(); 16], [T::zero(); 4]);
+    for n in 0..batch {
+        for c in 0..in_channels {
+            let image = &input[(n * in_channels + c) * height * width..(n * in_channels + c + 1) * height * width];
+            for th in 0..tiles_h {
+                for tw in 0..tiles_w {
+                    for i in 0..4 {
+                        for j in 0..4 {
+                            d[4 * i + j] = match (input_index(th, i, 2, config.padding[0], height), input_index(tw, j, 2, config.padding[1], width)) {
+                                (Some(h), Some(w)) => image[h * width + w],
+                                _ => T::zero(),
+                            };
+                        }
+                    }
+                    winograd_input(&d, &mut v);
+                    for xi in 0..16 {
+                        inputs[(xi * in_channels + c) * tiles + th * tiles_w + tw] = v[xi];
+                    }
+                }
+            }
+        }
+        for xi in 0..16 {
+            for g in 0..config.groups {
+                let (k, c) = (xi * out_channels + g * group_out_channels, xi * in_channels + g * channels);
+                helper::gemm(pool, false, false, group_out_channels, tiles, channels, T::one(),
+                             &filters[k * channels..(k + group_out_channels) * channels],
+                             &inputs[c * tiles..(c + channels) * tiles], T::zero(),
+                             &mut outputs[k * tiles..(k + group_out_channels) * tiles]);
+            }
+        }
+        for k in 0..out_channels {
+            let out = &mut output[(n * out_channels + k) * out_h * out_w..(n * out_channels + k + 1) * out_h * out_w];
+            for tile in 0..tiles {
+                for xi in 0..16 {
+                    m[xi] = outputs[(xi * out_channels + k) * tiles + tile];
+                }
+                winograd_output(&m, &mut y);
+                let (oh, ow) = (2 * (tile / tiles_w), 2 * (tile % tiles_w));
+                for i in 0..2 {
+                    for j in 0..2 {
+                        if oh + i < out_h && ow + j < out_w {
+                            out[(oh + i) * out_w + ow + j] = y[2 * i + j];
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the range of output positions whose three dilated filter taps all lie inside the input of `size`.
+fn interior(out: usize, stride: usize, padding: usize, dilation: usize, size: usize) -> (usize, usize) {
+    let start = ::std::cmp::min((padding + stride - 1) / stride, out);
+    let end = if size + padding > 2 * dilation {
+        ::std::cmp::min((size + padding - 2 * dilation - 1) / stride + 1, out)
+    } else {
+        0
+    };
+    (start, ::std::cmp::max(start, end))
+}
+
+/// Returns the input positions of the three filter taps at output position `(oh, ow)`.
+///
+/// Taps in the zero padding are `None`.
+#[inline]
+fn depthwise_taps(config: &ConvolutionConfig, oh: usize, ow: usize, height: usize, width: usize) -> ([Option<usize>; 3], [Option<usize>; 3]) {
+    let (mut rows, mut cols) = ([None; 3], [None; 3]);
+    for i in 0..3 {
+        rows[i] = input_index(oh, i * config.dilation[0], config.stride[0], config.padding[0], height);
+        cols[i] = input_index(ow, i * config.dilation[1], config.stride[1], config.padding[1], width);
+    }
+    (rows, cols)
+}
+
+fn forward_depthwise<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, weights: &[T], input: &[T], output: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels) = (config.filter_shape[0], config.groups);
+    let multiplier = out_channels / channels;
+    let (stride, padding, dilation) = (&config.stride, &config.padding, &config.dilation);
+    let (rows, cols) = (interior(out_h, stride[0], padding[0], dilation[0], height), interior(out_w, stride[1], padding[1], dilation[1], width));
+    let plane = out_h * out_w;
+    parallel::for_each_chunk_mut(pool, &mut output[..batch * out_channels * plane], plane, |offset, chunk| {
+        for (index, out) in chunk.chunks_mut(plane).enumerate() {
+            let (n, k) = ((offset / plane + index) / out_channels, (offset / plane + index) % out_channels);
+            let image = &input[(n * channels + k / multiplier) * height * width..(n * channels + k / multiplier + 1) * height * width];
+            let filter = &weights[k * 9..(k + 1) * 9];
+            for oh in 0..out_h {
+                for ow in 0..out_w {
+                    let mut sum = T::zero();
+                    if oh >= rows.0 && oh < rows.1 && ow >= cols.0 && ow < cols.1 {
+                        let (h, w) = (oh * stride[0] - padding[0], ow * stride[1] - padding[1]);
+                        for i in 0..3 {
+                            let row = &image[(h + i * dilation[0]) * width + w..];
+                            sum = sum + filter[3 * i] * row[0] + filter[3 * i + 1] * row[dilation[1]] + filter[3 * i + 2] * row[2 * dilation[1]];
+                        }
+                    } else {
+                        let (taps_h, taps_w) = depthwise_taps(config, oh, ow, height, width);
+                        for i in 0..3 {
+                            for j in 0..3 {
+                                if let (Some(h), Some(w)) = (taps_h[i], taps_w[j]) {
+                                    sum = sum + filter[3 * i + j] * image[h * width + w];
+                                }
+                            }
+                        }
+                    }
+                    out[oh * out_w + ow] = sum;
+                }
+            }
+        }
+    });
+}
+
+fn backward_filter_depthwise<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, input: &[T], output_diff: &[T], weights_diff: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels) = (config.filter_shape[0], config.groups);
+    let multiplier = out_channels / channels;
+    let (stride, padding, dilation) = (&config.stride, &config.padding, &config.dilation);
+    let (rows, cols) = (interior(out_h, stride[0], padding[0], dilation[0], height), interior(out_w, stride[1], padding[1], dilation[1], width));
+    parallel::for_each_chunk_mut(pool, &mut weights_diff[..out_channels * 9], 9, |offset, chunk| {
+        for (index, filter) in chunk.chunks_mut(9).enumerate() {
+            let k = offset / 9 + index;
+            let mut sums = [T::zero(); 9];
+            for n in 0..batch {
+                let image = &input[(n * channels + k / multiplier) * height * width..(n * channels + k / multiplier + 1) * height * width];
+                let diff = &output_diff[(n * out_channels + k) * out_h * out_w..(n * out_channels + k + 1) * out_h * out_w];
+                for oh in 0..out_h {
+                    for ow in 0..out_w {
+                        let value = diff[oh * out_w + ow];
+                        if oh >= rows.0 && oh < rows.1 && ow >= cols.0 && ow < cols.1 {
+                            let (h, w) = (oh * stride[0] - padding[0], ow * stride[1] - padding[1]);
+                            for i in 0..3 {
+                                let row = &image[(h + i * dilation[0]) * width + w..];
+                                sums[3 * i] = sums[3 * i] + value * row[0];
+                                sums[3 * i + 1] = sums[3 * i + 1] + value * row[dilation[1]];
+                                sums[3 * i + 2] = sums[3 * i + 2] + value * row[2 * dilation[1]];
+                            }
+                        } else {
+                            let (taps_h, taps_w) = depthwise_taps(config, oh, ow, height, width);
+                            for i in 0..3 {
+                                for j in 0..3 {
+                                    if let (Some(h), Some(w)) = (taps_h[i], taps_w[j]) {
+                                        sums[3 * i + j] = sums[3 * i + j] + value * image[h * width + w];
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            filter.copy_from_slice(&sums);
+        }
+    });
+}
+
+fn backward_data_depthwise<T: Float + Send + Sync>(pool: &ThreadPool, config: &ConvolutionConfig, dims: ConvDims, weights: &[T], output_diff: &[T], input_diff: &mut [T]) {
+    let (batch, height, width, out_h, out_w) = dims;
+    let (out_channels, channels) = (config.filter_shape[0], config.groups);
+    let multiplier = out_channels / channels;
+    let (stride, padding, dilation) = (&config.stride, &config.padding, &config.dilation);
+    let (rows, cols) = (interior(out_h, stride[0], padding[0], dilation[0], height), interior(out_w, stride[1], padding[1], dilation[1], width));
+    let plane = height * width;
+    parallel::for_each_chunk_mut(pool, &mut input_diff[..batch * channels * plane], plane, |offset, chunk| {
+        for (index, image) in chunk.chunks_mut(plane).enumerate() {
+            let (n, c) = ((offset / plane + index) / channels, (offset / plane + index) % channels);
+            for value in image.iter_mut() {
+                *value = T::zero();
+            }
+            // every output position scatters its gradient back onto the taps it read.
+            for k in c * multiplier..(c + 1) * multiplier {
+                let filter = &weights[k * 9..(k + 1) * 9];
+                let diff = &output_diff[(n * out_channels + k) * out_h * out_w..(n * out_channels + k + 1) * out_h * out_w];
+                for oh in 0..out_h {
+                    for ow in 0..out_w {
+                        let value = diff[oh * out_w + ow];
+                        if oh >= rows.0 && oh < rows.1 && ow >= cols.0 && ow < cols.1 {
+                            let (h, w) = (oh * stride[0] - padding[0], ow * stride[1] - padding[1]);
+                            for i in 0..3 {
+                                let row = &mut image[(h + i * dilation[0]) * width + w..];
+                                row[0] = row[0] + filter[3 * i] * value;
+                                row[dilation[1]] = row[dilation[1]] + filter[3 * i + 1] * value;
+                                row[2 * dilation[1]] = row[2 * dilation[1]] + filter[3 * i + 2] * value;
+                            }
+                        } else {
+                            let (taps_h, taps_w) = depthwise_taps(config, oh, ow, height, width);
+                            for i in 0..3 {
+                                for j in 0..3 {
+                                    if let (Some(h), Some(w)) = (taps_h[i], taps_w[j]) {
+                                        image[h * width + w] = image[h * width + w] + filter[3 * i + j] * value;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
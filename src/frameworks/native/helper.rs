@@ -1,17 +1,550 @@
 //! Provides useful macros for easier NN implementation for native.
 
 use co::plugin::numeric_helpers::Float;
+use co::plugin::Error as PluginError;
 use co::memory::MemoryType;
+use super::parallel::{self, ThreadPool};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Describes how the native backend computes a convolution pass.
+pub enum ConvAlgo {
+    /// Compute the pass as explicit matrix product over a column buffer of the input.
+    ///
+    /// Needs a workspace of one column buffer.
+    GEMM,
+    /// Compute the pass as matrix product directly on the input without forming the column buffer.
+    ///
+    /// Does not need any memory workspace.
+    ImplicitGEMM,
+    /// Compute the pass as product of 2D Fast-Fourier Transforms.
+    ///
+    /// Needs a workspace of one transform per channel.
+    FFT,
+    /// Compute the pass with the Winograd F(2x2, 3x3) transform of 4x4 input tiles.
+    ///
+    /// Only available for the forward pass of 3x3 filters with a stride and dilation of 1.
+    /// Needs a workspace of the transformed filter, input tiles and output tiles.
+    Winograd,
+    /// Compute the pass directly with an unrolled 3x3 filter per channel.
+    ///
+    /// Only available for depthwise convolutions of 3x3 filters.
+    /// Does not need any memory workspace.
+    Depthwise,
+}
+
+#[derive(Debug, Clone)]
+/// Provides the configuration of a 2D convolution on the native backend.
+pub struct ConvolutionConfig {
+    /// The shape of the input as `[batch, channels, height, width]`.
+    pub input_shape: Vec<usize>,
+    /// The shape of the filter as `[output channels, input channels / groups, height, width]`.
+    pub filter_shape: Vec<usize>,
+    /// The stride in `[height, width]`.
+    pub stride: Vec<usize>,
+    /// The zero padding in `[height, width]`.
+    pub padding: Vec<usize>,
+    /// The spacing between the filter elements in `[height, width]`.
+    pub dilation: Vec<usize>,
+    /// The number of groups the input and output channels are split into.
+    pub groups: usize,
+    /// The algorithm of the forward pass.
+    pub forward_algo: ConvAlgo,
+    /// The algorithm of the gradient with respect to the filter.
+    pub backward_filter_algo: ConvAlgo,
+    /// The algorithm of the gradient with respect to the data.
+    pub backward_data_algo: ConvAlgo,
+}
+
+impl ConvolutionConfig {
+    /// Creates a new ConvolutionConfig after validating the input shape, filter shape, stride, padding, dilation and groups.
+    ///
+    /// All passes use the `GEMM` algorithm.
+    pub fn new(input_shape: &[usize], filter_shape: &[usize], stride: &[i32], padding: &[i32], dilation: &[i32], groups: usize) -> Result<ConvolutionConfig, PluginError> {
+        if input_shape.len() != 4 || filter_shape.len() != 4 {
+            return Err(PluginError::Plugin("Native convolution requires a 4D input and a 4D filter."));
+        }
+        if stride.len() != 2 || padding.len() != 2 || dilation.len() != 2 {
+            return Err(PluginError::Plugin("Native convolution requires a stride, zero padding and dilation for two spatial dimensions."));
+        }
+        if stride.iter().any(|&s| s < 1) || padding.iter().any(|&p| p < 0) {
+            return Err(PluginError::Plugin("Native convolution requires a positive stride and a non-negative zero padding."));
+        }
+        if dilation.iter().any(|&d| d < 1) {
+            return Err(PluginError::Plugin("Native convolution requires a positive dilation."));
+        }
+        if groups == 0 || filter_shape[0] % groups != 0 {
+            return Err(PluginError::Plugin("Native convolution requires a positive number of groups that divides the output channels."));
+        }
+        Ok(ConvolutionConfig {
+            input_shape: input_shape.to_vec(),
+            filter_shape: filter_shape.to_vec(),
+            stride: stride.iter().map(|&s| s as usize).collect(),
+            padding: padding.iter().map(|&p| p as usize).collect(),
+            dilation: dilation.iter().map(|&d| d as usize).collect(),
+            groups: groups,
+            forward_algo: ConvAlgo::GEMM,
+            backward_filter_algo: ConvAlgo::GEMM,
+            backward_data_algo: ConvAlgo::GEMM,
+        })
+    }
+
+    /// Returns the `(height, width)` of the input window the dilated filter covers.
+    pub fn filter_extent(&self) -> (usize, usize) {
+        ((self.filter_shape[2] - 1) * self.dilation[0] + 1, (self.filter_shape[3] - 1) * self.dilation[1] + 1)
+    }
+
+    /// Computes the spatial output dimensions `(height, width)` for an input of `height` x `width`.
+    ///
+    /// Returns `None` if the padded input is smaller than the dilated filter.
+    pub fn output_dims(&self, height: usize, width: usize) -> Option<(usize, usize)> {
+        let padded_h = height + 2 * self.padding[0];
+        let padded_w = width + 2 * self.padding[1];
+        if self.filter_shape[2] == 0 || self.filter_shape[3] == 0 {
+            return None;
+        }
+        let (extent_h, extent_w) = self.filter_extent();
+        if padded_h < extent_h || padded_w < extent_w {
+            return None;
+        }
+        Some(((padded_h - extent_h) / self.stride[0] + 1,
+              (padded_w - extent_w) / self.stride[1] + 1))
+    }
+
+    /// Checks that `src`, `dest` and the filter of shape `filter` describe a valid convolution
+    /// and returns `(batch, height, width, output height, output width)`.
+    pub fn check_shapes(&self, src: &[usize], dest: &[usize], filter: &[usize]) -> Result<(usize, usize, usize, usize, usize), PluginError> {
+        if src.len() != 4 || dest.len() != 4 {
+            return Err(PluginError::Operation("Native convolution requires 4D input and output tensors."));
+        }
+        if filter != &self.filter_shape[..] {
+            return Err(PluginError::Operation("Native convolution filter shape does not match the configured filter."));
+        }
+        if src[1] != self.filter_shape[1] * self.groups || dest[1] != self.filter_shape[0] || src[0] != dest[0] {
+            return Err(PluginError::Operation("Native convolution tensor shapes do not match the filter."));
+        }
+        match self.output_dims(src[2], src[3]) {
+            Some((out_h, out_w)) if out_h == dest[2] && out_w == dest[3] => Ok((src[0], src[2], src[3], out_h, out_w)),
+            _ => Err(PluginError::Operation("Native convolution output shape does not match the input shape.")),
+        }
+    }
+
+    /// Returns the number of elements of the column buffer for one group of one image of `height` x `width`.
+    pub fn col_len(&self, height: usize, width: usize) -> usize {
+        match self.output_dims(height, width) {
+            Some((out_h, out_w)) => self.filter_shape[1] * self.filter_shape[2] * self.filter_shape[3] * out_h * out_w,
+            None => 0,
+        }
+    }
+
+    /// Returns the `(height, width)` of the Fourier transforms for an input of `height` x `width`.
+    ///
+    /// The transforms cover the zero padded input, so the circular correlation never wraps around.
+    pub fn fft_dims(&self, height: usize, width: usize) -> (usize, usize) {
+        ((height + 2 * self.padding[0]).next_power_of_two(), (width + 2 * self.padding[1]).next_power_of_two())
+    }
+
+    /// Returns the number of workspace elements `algo` needs for an input of `height` x `width`.
+    pub fn algo_workspace_len(&self, algo: ConvAlgo, height: usize, width: usize) -> usize {
+        match algo {
+            ConvAlgo::GEMM => self.col_len(height, width),
+            ConvAlgo::ImplicitGEMM => 0,
+            ConvAlgo::FFT => {
+                // one complex transform per filter, per input or output channel and the accumulator,
+                // or the output gradient and the accumulator for the filter gradient.
+                let (fft_h, fft_w) = self.fft_dims(height, width);
+                let (out_channels, group_channels) = (self.filter_shape[0], self.filter_shape[1]);
+                2 * (out_channels * group_channels + ::std::cmp::max(out_channels, group_channels * self.groups) + 2) * fft_h * fft_w
+            },
+            ConvAlgo::Winograd => {
+                // the 16 transformed positions of the filter, the input tiles and the output tiles of one image.
+                let tiles = self.winograd_tiles(height, width);
+                let (out_channels, group_channels) = (self.filter_shape[0], self.filter_shape[1]);
+                16 * (out_channels * group_channels + group_channels * self.groups * tiles + out_channels * tiles)
+            },
+            ConvAlgo::Depthwise => 0,
+        }
+    }
+
+    /// Returns if the Winograd F(2x2, 3x3) algorithm can compute the forward pass.
+    pub fn supports_winograd(&self) -> bool {
+        self.filter_shape[2] == 3 && self.filter_shape[3] == 3 && self.stride[0] == 1 && self.stride[1] == 1
+            && self.dilation[0] == 1 && self.dilation[1] == 1
+    }
+
+    /// Returns if the depthwise 3x3 algorithm can compute the passes.
+    ///
+    /// Every input channel forms its own group, which may produce several output channels.
+    pub fn supports_depthwise(&self) -> bool {
+        self.filter_shape[1] == 1 && self.groups == self.input_shape[1] && self.filter_shape[2] == 3 && self.filter_shape[3] == 3
+    }
+
+    /// Returns the number of 2x2 output tiles of one image of `height` x `width`.
+    pub fn winograd_tiles(&self, height: usize, width: usize) -> usize {
+        match self.output_dims(height, width) {
+            Some((out_h, out_w)) => ((out_h + 1) / 2) * ((out_w + 1) / 2),
+            None => 0,
+        }
+    }
+
+    /// Returns the number of workspace elements the native convolution passes need for the configured input.
+    ///
+    /// Forward, filter gradient and data gradient share one workspace, which fits the largest of their algorithms.
+    pub fn workspace_len(&self) -> usize {
+        let (height, width) = (self.input_shape[2], self.input_shape[3]);
+        [self.forward_algo, self.backward_filter_algo, self.backward_data_algo].iter()
+            .map(|&algo| self.algo_workspace_len(algo, height, width))
+            .fold(0, ::std::cmp::max)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Provides the configuration of a 2D transposed convolution on the native backend.
+pub struct TransposedConvolutionConfig {
+    /// The convolution from the output of the transposed convolution to its input.
+    ///
+    /// The forward pass of the transposed convolution is the data gradient of this convolution.
+    pub convolution: ConvolutionConfig,
+    /// The additional size of the output in `[height, width]`.
+    pub output_padding: Vec<usize>,
+}
+
+impl TransposedConvolutionConfig {
+    /// Creates a new TransposedConvolutionConfig for an input of `src_shape` after validating
+    /// the filter shape, stride, padding and output padding.
+    ///
+    /// All passes use the `GEMM` algorithm.
+    pub fn new(src_shape: &[usize], filter_shape: &[usize], stride: &[i32], padding: &[i32],
+               output_padding: &[i32]) -> Result<TransposedConvolutionConfig, PluginError> {
+        let dest_shape = try!(TransposedConvolutionConfig::output_shape(src_shape, filter_shape, stride, padding, output_padding));
+        Ok(TransposedConvolutionConfig {
+            convolution: try!(ConvolutionConfig::new(&dest_shape, filter_shape, stride, padding, &[1, 1], 1)),
+            output_padding: output_padding.iter().map(|&p| p as usize).collect(),
+        })
+    }
+
+    /// Computes the shape of the output for an input of `src_shape`.
+    ///
+    /// Every spatial dimension is `(input - 1) * stride - 2 * padding + filter + output padding`.
+    pub fn output_shape(src_shape: &[usize], filter_shape: &[usize], stride: &[i32], padding: &[i32],
+                        output_padding: &[i32]) -> Result<Vec<usize>, PluginError> {
+        if src_shape.len() != 4 || filter_shape.len() != 4 {
+            return Err(PluginError::Plugin("Native transposed convolution requires a 4D input and a 4D filter."));
+        }
+        if stride.len() != 2 || padding.len() != 2 || output_padding.len() != 2 {
+            return Err(PluginError::Plugin("Native transposed convolution requires a stride, zero padding and output padding for two spatial dimensions."));
+        }
+        if stride.iter().any(|&s| s < 1) || padding.iter().any(|&p| p < 0) {
+            return Err(PluginError::Plugin("Native transposed convolution requires a positive stride and a non-negative zero padding."));
+        }
+        if output_padding.iter().zip(stride.iter()).any(|(&o, &s)| o < 0 || o >= s) {
+            return Err(PluginError::Plugin("Native transposed convolution requires a non-negative output padding smaller than the stride."));
+        }
+        if src_shape[1] != filter_shape[0] || src_shape.iter().any(|&d| d == 0) {
+            return Err(PluginError::Plugin("Native transposed convolution requires a non-empty input with the input channels of the filter."));
+        }
+        let mut shape = vec![src_shape[0], filter_shape[1]];
+        for i in 0..2 {
+            let full = (src_shape[2 + i] - 1) * stride[i] as usize + filter_shape[2 + i] + output_padding[i] as usize;
+            if full <= 2 * padding[i] as usize {
+                return Err(PluginError::Plugin("Native transposed convolution requires a zero padding smaller than the output."));
+            }
+            shape.push(full - 2 * padding[i] as usize);
+        }
+        Ok(shape)
+    }
+
+    /// Checks that `src`, `dest` and the filter of shape `filter` describe the configured transposed convolution
+    /// and returns the `(batch, height, width, output height, output width)` of its convolution.
+    pub fn check_shapes(&self, src: &[usize], dest: &[usize], filter: &[usize]) -> Result<(usize, usize, usize, usize, usize), PluginError> {
+        if dest.len() != 4 || dest[2..] != self.convolution.input_shape[2..] {
+            return Err(PluginError::Operation("Native transposed convolution output shape does not match the input shape."));
+        }
+        self.convolution.check_shapes(dest, src, filter)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Provides the configuration of a cross-channel LRN on the native backend.
+pub struct NormalizationConfig {
+    /// The number of channels in the normalization window.
+    pub n: usize,
+    /// The scaling parameter.
+    pub alpha: f64,
+    /// The exponent.
+    pub beta: f64,
+    /// The additive constant.
+    pub k: f64,
+}
+
+impl NormalizationConfig {
+    /// Creates a new NormalizationConfig after validating the window size.
+    pub fn new(n: u32, alpha: f64, beta: f64, k: f64) -> Result<NormalizationConfig, PluginError> {
+        if n < 1 {
+            return Err(PluginError::Plugin("Native LRN requires a window of at least one channel."));
+        }
+        Ok(NormalizationConfig { n: n as usize, alpha: alpha, beta: beta, k: k })
+    }
+
+    /// Returns the range of channels in the window centered at `channel`,
+    /// clipped to `channels`.
+    pub fn window(&self, channel: usize, channels: usize) -> ::std::ops::Range<usize> {
+        let pre = (self.n - 1) / 2;
+        let start = if channel > pre { channel - pre } else { 0 };
+        let end = ::std::cmp::min(channel + self.n - pre, channels);
+        start..end
+    }
+
+    /// Returns the range of channels whose window contains `channel`, clipped to `channels`.
+    pub fn inverse_window(&self, channel: usize, channels: usize) -> ::std::ops::Range<usize> {
+        let pre = (self.n - 1) / 2;
+        let start = if channel + pre + 1 > self.n { channel + pre + 1 - self.n } else { 0 };
+        let end = ::std::cmp::min(channel + pre + 1, channels);
+        start..end
+    }
+
+    /// Splits `dims` into `(batch, channels, spatial size)`.
+    pub fn layout(dims: &[usize]) -> (usize, usize, usize) {
+        match dims.len() {
+            0 => (1, 1, 1),
+            1 => (dims[0], 1, 1),
+            _ => (dims[0], dims[1], dims[2..].iter().fold(1, |acc, d| acc * d)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Provides the configuration of a group normalization on the native backend.
+pub struct GroupNormalizationConfig {
+    /// The number of groups the channels are split into.
+    pub groups: usize,
+    /// The constant added to the variance.
+    pub epsilon: f64,
+}
+
+impl GroupNormalizationConfig {
+    /// Creates a new GroupNormalizationConfig after validating the group count and epsilon.
+    pub fn new(groups: usize, epsilon: f64) -> Result<GroupNormalizationConfig, PluginError> {
+        if groups == 0 {
+            return Err(PluginError::Plugin("Native group normalization requires at least one group."));
+        }
+        if !(epsilon > 0f64) {
+            return Err(PluginError::Plugin("Native group normalization requires a positive epsilon."));
+        }
+        Ok(GroupNormalizationConfig { groups: groups, epsilon: epsilon })
+    }
+
+    /// Checks that an NCHW input of `dims` is not empty and splits its channels evenly into the groups,
+    /// that every Tensor in `params` has one element per channel and every Tensor in `stats` one element
+    /// per group of every sample.
+    pub fn check_params(&self, dims: &[usize], params: &[&[usize]], stats: &[&[usize]]) -> Result<(), PluginError> {
+        let (batch, channels, spatial) = NormalizationConfig::layout(dims);
+        if dims.len() < 2 || batch == 0 || channels == 0 || spatial == 0 {
+            return Err(PluginError::Operation("Native group normalization requires a non-empty input with a channel dimension."));
+        }
+        if channels % self.groups != 0 {
+            return Err(PluginError::Operation("Native group normalization requires a number of channels divisible by the number of groups."));
+        }
+        if params.iter().any(|desc| desc.iter().fold(1, |acc, d| acc * d) != channels) {
+            return Err(PluginError::Operation("Native group normalization requires one scale and bias per channel."));
+        }
+        if stats.iter().any(|desc| desc.iter().fold(1, |acc, d| acc * d) != batch * self.groups) {
+            return Err(PluginError::Operation("Native group normalization requires one saved statistic per group of every sample."));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Provides the configuration of a 2D pooling on the native backend.
+pub struct PoolingConfig {
+    /// The pooling window in `[height, width]`.
+    pub window: Vec<usize>,
+    /// The zero padding in `[height, width]`.
+    pub padding: Vec<usize>,
+    /// The stride in `[height, width]`.
+    pub stride: Vec<usize>,
+    /// The treatment of the zero padding in average pooling.
+    pub avg_mode: ::plugin::PoolingAvgMode,
+}
+
+impl PoolingConfig {
+    /// Creates a new PoolingConfig after validating the window, padding and stride.
+    pub fn new(window: &[i32], padding: &[i32], stride: &[i32], avg_mode: ::plugin::PoolingAvgMode) -> Result<PoolingConfig, PluginError> {
+        if window.len() != 2 || padding.len() != 2 || stride.len() != 2 {
+            return Err(PluginError::Plugin("Native pooling requires a window, padding and stride for two spatial dimensions."));
+        }
+        if window.iter().any(|&w| w < 1) || stride.iter().any(|&s| s < 1) || padding.iter().any(|&p| p < 0) {
+            return Err(PluginError::Plugin("Native pooling requires a positive window and stride and a non-negative padding."));
+        }
+        Ok(PoolingConfig {
+            window: window.iter().map(|&w| w as usize).collect(),
+            padding: padding.iter().map(|&p| p as usize).collect(),
+            stride: stride.iter().map(|&s| s as usize).collect(),
+            avg_mode: avg_mode,
+        })
+    }
+
+    /// Returns the number of elements the average over a window of `rows` x `cols` input elements divides by.
+    pub fn avg_count(&self, rows: usize, cols: usize) -> usize {
+        match self.avg_mode {
+            ::plugin::PoolingAvgMode::IncludePadding => self.window[0] * self.window[1],
+            ::plugin::PoolingAvgMode::ExcludePadding => rows * cols,
+        }
+    }
+
+    /// Returns the rows and columns of the input covered by the window of output position
+    /// `(oh, ow)`, clipped to an input of `height` x `width`.
+    pub fn window_range(&self, oh: usize, ow: usize, height: usize, width: usize) -> (::std::ops::Range<usize>, ::std::ops::Range<usize>) {
+        let clip = |start: isize, len: usize, size: usize| {
+            let end = ::std::cmp::min(start + len as isize, size as isize);
+            let start = ::std::cmp::max(start, 0);
+            if start >= end { 0..0 } else { start as usize..end as usize }
+        };
+        (clip((oh * self.stride[0]) as isize - self.padding[0] as isize, self.window[0], height),
+         clip((ow * self.stride[1]) as isize - self.padding[1] as isize, self.window[1], width))
+    }
+
+    /// Computes the spatial output dimensions `(height, width)` for an input of `height` x `width`.
+    ///
+    /// Returns `None` if the padded input is smaller than the window.
+    pub fn output_dims(&self, height: usize, width: usize) -> Option<(usize, usize)> {
+        let padded_h = height + 2 * self.padding[0];
+        let padded_w = width + 2 * self.padding[1];
+        if padded_h < self.window[0] || padded_w < self.window[1] {
+            return None;
+        }
+        Some(((padded_h - self.window[0]) / self.stride[0] + 1,
+              (padded_w - self.window[1]) / self.stride[1] + 1))
+    }
+
+    /// Checks that `src` and `dest` are 4D tensors with matching batch and channel dimensions
+    /// and that the spatial dimensions of `dest` match the window, stride and padding.
+    pub fn check_shapes(&self, src: &[usize], dest: &[usize]) -> Result<(), PluginError> {
+        if src.len() != 4 || dest.len() != 4 {
+            return Err(PluginError::Operation("Native pooling requires 4D input and output tensors."));
+        }
+        if src[0] != dest[0] || src[1] != dest[1] {
+            return Err(PluginError::Operation("Native pooling requires matching batch and channel dimensions."));
+        }
+        match self.output_dims(src[2], src[3]) {
+            Some((out_h, out_w)) if out_h == dest[2] && out_w == dest[3] => Ok(()),
+            _ => Err(PluginError::Operation("Native pooling output shape does not match the input shape.")),
+        }
+    }
+}
 
 #[derive(Debug, Copy, Clone)]
-#[allow(missing_docs)]
-pub struct ConvolutionConfig;
+/// Provides the configuration of a batch normalization on the native backend.
+pub struct BatchNormalizationConfig {
+    /// The set of elements the statistics are computed over.
+    pub mode: ::plugin::BatchNormMode,
+    /// The constant added to the variance.
+    pub epsilon: f64,
+    /// The weight of the batch statistics in the update of the running statistics.
+    pub momentum: f64,
+}
+
+impl BatchNormalizationConfig {
+    /// Creates a new BatchNormalizationConfig after validating epsilon and momentum.
+    pub fn new(mode: ::plugin::BatchNormMode, epsilon: f64, momentum: f64) -> Result<BatchNormalizationConfig, PluginError> {
+        if !(epsilon > 0f64) {
+            return Err(PluginError::Plugin("Native batch normalization requires a positive epsilon."));
+        }
+        if !(momentum >= 0f64 && momentum <= 1f64) {
+            return Err(PluginError::Plugin("Native batch normalization requires a momentum between 0 and 1."));
+        }
+        Ok(BatchNormalizationConfig { mode: mode, epsilon: epsilon, momentum: momentum })
+    }
+
+    /// Splits `dims` into `(batch, features, spatial size)`, where every feature is normalized
+    /// over the batch and the spatial size.
+    pub fn layout(&self, dims: &[usize]) -> (usize, usize, usize) {
+        let (batch, channels, spatial) = NormalizationConfig::layout(dims);
+        match self.mode {
+            ::plugin::BatchNormMode::PerActivation => (batch, channels * spatial, 1),
+            ::plugin::BatchNormMode::Spatial => (batch, channels, spatial),
+        }
+    }
+
+    /// Checks that every Tensor in `params` has one element per feature of an input of `dims`.
+    pub fn check_params(&self, dims: &[usize], params: &[&[usize]]) -> Result<(), PluginError> {
+        let (batch, features, _) = self.layout(dims);
+        if batch == 0 || features == 0 {
+            return Err(PluginError::Operation("Native batch normalization requires a non-empty input."));
+        }
+        if params.iter().any(|desc| desc.iter().fold(1, |acc, d| acc * d) != features) {
+            return Err(PluginError::Operation("Native batch normalization requires one scale, bias and statistic per feature."));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
-#[allow(missing_docs)]
-pub struct NormalizationConfig;
+/// Provides the configuration of a layer normalization on the native backend.
+pub struct LayerNormalizationConfig {
+    /// The number of trailing dimensions every group of normalized elements spans.
+    pub normalized_dims: usize,
+    /// The constant added to the variance.
+    pub epsilon: f64,
+}
+
+impl LayerNormalizationConfig {
+    /// Creates a new LayerNormalizationConfig after validating the normalized dimensions and epsilon.
+    pub fn new(normalized_dims: usize, epsilon: f64) -> Result<LayerNormalizationConfig, PluginError> {
+        if normalized_dims == 0 {
+            return Err(PluginError::Plugin("Native layer normalization requires at least one normalized dimension."));
+        }
+        if !(epsilon > 0f64) {
+            return Err(PluginError::Plugin("Native layer normalization requires a positive epsilon."));
+        }
+        Ok(LayerNormalizationConfig { normalized_dims: normalized_dims, epsilon: epsilon })
+    }
+
+    /// Splits `dims` into `(groups, features)`, where the `features` elements of the trailing
+    /// dimensions are normalized together for each of the `groups` leading indices.
+    pub fn layout(&self, dims: &[usize]) -> Result<(usize, usize), PluginError> {
+        if self.normalized_dims > dims.len() {
+            return Err(PluginError::Operation("Native layer normalization requires an input with at least the normalized dimensions."));
+        }
+        let (leading, trailing) = dims.split_at(dims.len() - self.normalized_dims);
+        Ok((leading.iter().fold(1, |acc, d| acc * d), trailing.iter().fold(1, |acc, d| acc * d)))
+    }
+
+    /// Checks that an input of `dims` is not empty, that every Tensor in `params` has one element
+    /// per feature and every Tensor in `stats` one element per group, and returns the layout.
+    pub fn check_params(&self, dims: &[usize], params: &[&[usize]], stats: &[&[usize]]) -> Result<(usize, usize), PluginError> {
+        let (groups, features) = try!(self.layout(dims));
+        if groups == 0 || features == 0 {
+            return Err(PluginError::Operation("Native layer normalization requires a non-empty input."));
+        }
+        if params.iter().any(|desc| desc.iter().fold(1, |acc, d| acc * d) != features) {
+            return Err(PluginError::Operation("Native layer normalization requires one scale and bias per normalized element."));
+        }
+        if stats.iter().any(|desc| desc.iter().fold(1, |acc, d| acc * d) != groups) {
+            return Err(PluginError::Operation("Native layer normalization requires one saved statistic per normalized group."));
+        }
+        Ok((groups, features))
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
-#[allow(missing_docs)]
-pub struct PoolingConfig;
+/// Provides the configuration of a dropout on the native backend.
+pub struct DropoutConfig {
+    /// The probability of dropping an element.
+    pub probability: f64,
+    /// The seed of the masks.
+    pub seed: u64,
+}
+
+impl DropoutConfig {
+    /// Creates a new DropoutConfig after validating the probability.
+    pub fn new(probability: f64, seed: u64) -> Result<DropoutConfig, PluginError> {
+        if !(probability >= 0f64 && probability < 1f64) {
+            return Err(PluginError::Plugin("Native dropout requires a probability of at least 0 and below 1."));
+        }
+        Ok(DropoutConfig { probability: probability, seed: seed })
+    }
+}
 
 /// Just a helper function until SharedTensor has a nice interface for writing data
 pub fn write_to_memory<T: Iterator>(mem: &mut MemoryType, data: T)
@@ -69,181 +602,2638 @@ pub fn tanh_grad<T: Float>(x: &T, dx: &T) -> T {
     (T::one() - x.powi(2)) * *dx
 }
 
-macro_rules! impl_oconf_for_cc(($($t: ident), +) => (
-    $(
-        impl<'a> NNOperationConfig<$t> for ::frameworks::native::helper::ConvolutionConfig { }
-        impl<'a> ConvolutionConfig<$t> for ::frameworks::native::helper::ConvolutionConfig { }
-    )+
-));
+/// Unrolls the filter-sized patches of one `[channels / groups, height, width]` image group into the columns of `col`.
+///
+/// `col` is laid out as `[channels * filter height * filter width, output height * output width]`,
+/// zero padded positions are filled with zero.
+pub fn im2col<T: Float>(src: &[T], height: usize, width: usize, config: &ConvolutionConfig, col: &mut [T]) {
+    let (out_h, out_w) = config.output_dims(height, width).unwrap();
+    let (kernel_h, kernel_w) = (config.filter_shape[2], config.filter_shape[3]);
+    let out_size = out_h * out_w;
+    for c in 0..config.filter_shape[1] {
+        for kh in 0..kernel_h {
+            for kw in 0..kernel_w {
+                let row = (c * kernel_h + kh) * kernel_w + kw;
+                for oh in 0..out_h {
+                    let h = (oh * config.stride[0] + kh * config.dilation[0]) as isize - config.padding[0] as isize;
+                    for ow in 0..out_w {
+                        let w = (ow * config.stride[1] + kw * config.dilation[1]) as isize - config.padding[1] as isize;
+                        col[row * out_size + oh * out_w + ow] =
+                            if h >= 0 && w >= 0 && (h as usize) < height && (w as usize) < width {
+                                src[(c * height + h as usize) * width + w as usize]
+                            } else {
+                                T::zero()
+                            };
+                    }
+                }
+            }
+        }
+    }
+}
 
-macro_rules! impl_oconf_for_clrn(($($t: ident), +) => (
-    $(
-        impl NNOperationConfig<$t> for ::frameworks::native::helper::NormalizationConfig { }
-    )+
-));
+/// Accumulates the columns of `col` back into one `[channels / groups, height, width]` image group.
+///
+/// This is the adjoint of `im2col`; `dest` is overwritten and positions
+/// covered by several patches receive the sum of their column entries.
+pub fn col2im<T: Float>(col: &[T], height: usize, width: usize, config: &ConvolutionConfig, dest: &mut [T]) {
+    let (out_h, out_w) = config.output_dims(height, width).unwrap();
+    let (kernel_h, kernel_w) = (config.filter_shape[2], config.filter_shape[3]);
+    let out_size = out_h * out_w;
+    for value in dest[..config.filter_shape[1] * height * width].iter_mut() {
+        *value = T::zero();
+    }
+    for c in 0..config.filter_shape[1] {
+        for kh in 0..kernel_h {
+            for kw in 0..kernel_w {
+                let row = (c * kernel_h + kh) * kernel_w + kw;
+                for oh in 0..out_h {
+                    let h = (oh * config.stride[0] + kh * config.dilation[0]) as isize - config.padding[0] as isize;
+                    if h < 0 || h as usize >= height {
+                        continue;
+                    }
+                    for ow in 0..out_w {
+                        let w = (ow * config.stride[1] + kw * config.dilation[1]) as isize - config.padding[1] as isize;
+                        if w >= 0 && (w as usize) < width {
+                            let index = (c * height + h as usize) * width + w as usize;
+                            dest[index] = dest[index] + col[row * out_size + oh * out_w + ow];
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
 
-macro_rules! impl_oconf_for_pooling(($($t: ident), +) => (
-    $(
-        impl NNOperationConfig<$t> for ::frameworks::native::helper::PoolingConfig { }
-    )+
-));
+/// Returns the first `len` elements of a native workspace reinterpreted as `T`.
+///
+/// Fails if the workspace is too small or not suitably aligned for `T`.
+pub fn workspace_as_mut_slice<T>(mem: &mut MemoryType, len: usize) -> Result<&mut [T], PluginError> {
+    match mem {
+        &mut MemoryType::Native(ref mut mem) => {
+            if len == 0 {
+                return Ok(&mut []);
+            }
+            if (mem.as_mut_slice::<u8>().as_ptr() as usize) % ::std::mem::align_of::<T>() != 0 {
+                return Err(PluginError::Operation("Native workspace is not aligned for the operation."));
+            }
+            let slice = mem.as_mut_slice::<T>();
+            if slice.len() < len {
+                return Err(PluginError::Operation("Native workspace is too small for the operation."));
+            }
+            Ok(&mut slice[..len])
+        },
+        #[cfg(any(feature = "opencl", feature = "cuda"))]
+        _ => Err(PluginError::MissingMemoryForDevice("Unable to receive native workspace memory."))
+    }
+}
 
-#[macro_export]
-macro_rules! impl_ops_sigmoid_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::Sigmoid<$t> for $b {
-            fn sigmoid(
-                &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>
-            ) -> Result<(), ::co::error::Error> {
-                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
-                match result.add_device(self.device()) { _ => () }
-                self.sigmoid_plain(x, result)
+/// Computes the max pooling of `[batch * channels, height, width]` images in `src`
+/// into the `[batch * channels, out height, out width]` images of `dest`.
+pub fn pooling_max<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], src_dims: &[usize], dest: &mut [T], dest_dims: &[usize], config: &PoolingConfig) {
+    let (height, width) = (src_dims[2], src_dims[3]);
+    let (out_h, out_w) = (dest_dims[2], dest_dims[3]);
+    let images = src_dims[0] * src_dims[1];
+    parallel::for_each_chunk_mut(pool, &mut dest[..images * out_h * out_w], out_h * out_w, |offset, chunk| {
+        let first = offset / (out_h * out_w);
+        for image in 0..chunk.len() / (out_h * out_w) {
+            let input = &src[(first + image) * height * width..(first + image + 1) * height * width];
+            for oh in 0..out_h {
+                for ow in 0..out_w {
+                    let (rows, cols) = config.window_range(oh, ow, height, width);
+                    let mut max = None;
+                    for h in rows {
+                        for w in cols.clone() {
+                            let value = input[h * width + w];
+                            max = match max {
+                                Some(current) if current >= value => Some(current),
+                                _ => Some(value),
+                            };
+                        }
+                    }
+                    chunk[(image * out_h + oh) * out_w + ow] = max.unwrap_or(T::zero());
+                }
             }
+        }
+    });
+}
 
-            fn sigmoid_plain(
-                &self,
-                x: &::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>
-            ) -> Result<(), ::co::error::Error> {
-                if let Some(input) = x.get(self.device()).unwrap().as_native() {
-                    let res = input.as_slice::<$t>().iter().map(::frameworks::native::helper::sigmoid);
-                    ::frameworks::native::helper::write_to_memory(result.get_mut(self.device()).unwrap(), res);
-                    return Ok(());
+/// Computes the gradient of max pooling.
+///
+/// Routes each output gradient of `dest_diff` to the first maximum of its window in `src`
+/// and accumulates the result in `src_diff`, which is overwritten.
+pub fn pooling_max_grad<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], src_dims: &[usize], src_diff: &mut [T],
+                                                dest_diff: &[T], dest_dims: &[usize], config: &PoolingConfig) {
+    let (height, width) = (src_dims[2], src_dims[3]);
+    let (out_h, out_w) = (dest_dims[2], dest_dims[3]);
+    let images = src_dims[0] * src_dims[1];
+    parallel::for_each_chunk_mut(pool, &mut src_diff[..images * height * width], height * width, |offset, chunk| {
+        let first = offset / (height * width);
+        for value in chunk.iter_mut() {
+            *value = T::zero();
+        }
+        for image in 0..chunk.len() / (height * width) {
+            let input = &src[(first + image) * height * width..(first + image + 1) * height * width];
+            for oh in 0..out_h {
+                for ow in 0..out_w {
+                    let (rows, cols) = config.window_range(oh, ow, height, width);
+                    let mut argmax = None;
+                    for h in rows {
+                        for w in cols.clone() {
+                            let index = h * width + w;
+                            argmax = match argmax {
+                                Some(current) if input[current] >= input[index] => Some(current),
+                                _ => Some(index),
+                            };
+                        }
+                    }
+                    if let Some(index) = argmax {
+                        let index = image * height * width + index;
+                        chunk[index] = chunk[index] + dest_diff[((first + image) * out_h + oh) * out_w + ow];
+                    }
                 }
-                Err(Error::Plugin(PluginError::Operation("Unable to execute Native sigmoid Forward.")))
             }
+        }
+    });
+}
 
-            fn sigmoid_grad(
-                &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
-                x_diff: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>
-            ) -> Result<(), ::co::error::Error> {
-                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
-                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
-                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
-                match result_diff.add_device(self.device()) { _ => () }
-                self.sigmoid_grad_plain(x, x_diff, result, result_diff)
+/// Computes the average pooling of `[batch * channels, height, width]` images in `src`
+/// into the `[batch * channels, out height, out width]` images of `dest`.
+///
+/// Windows without any input element average to zero.
+pub fn pooling_avg<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], src_dims: &[usize], dest: &mut [T], dest_dims: &[usize], config: &PoolingConfig) {
+    let (height, width) = (src_dims[2], src_dims[3]);
+    let (out_h, out_w) = (dest_dims[2], dest_dims[3]);
+    let images = src_dims[0] * src_dims[1];
+    parallel::for_each_chunk_mut(pool, &mut dest[..images * out_h * out_w], out_h * out_w, |offset, chunk| {
+        let first = offset / (out_h * out_w);
+        for image in 0..chunk.len() / (out_h * out_w) {
+            let input = &src[(first + image) * height * width..(first + image + 1) * height * width];
+            for oh in 0..out_h {
+                for ow in 0..out_w {
+                    let (rows, cols) = config.window_range(oh, ow, height, width);
+                    let count = config.avg_count(rows.len(), cols.len());
+                    let mut sum = T::zero();
+                    for h in rows {
+                        for w in cols.clone() {
+                            sum = sum + input[h * width + w];
+                        }
+                    }
+                    chunk[(image * out_h + oh) * out_w + ow] = if count == 0 { T::zero() } else { sum / T::from(count).unwrap() };
+                }
             }
+        }
+    });
+}
 
-            fn sigmoid_grad_plain(
-                &self,
-                x: &::co::tensor::SharedTensor<$t>,
-                x_diff: &::co::tensor::SharedTensor<$t>,
-                result: &::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>
-            ) -> Result<(), ::co::error::Error> {
-                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
-                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
-                        let res = sig_data.as_slice::<$t>().iter()
-                        .zip(sig_dx.as_slice::<$t>().iter())
-                        .map(|(t, dt)| ::frameworks::native::helper::sigmoid_grad(t, dt));
-                        ::frameworks::native::helper::write_to_memory(result_diff.get_mut(self.device()).unwrap(), res);
-                        return Ok(());
+/// Computes the gradient of average pooling.
+///
+/// Spreads each output gradient of `dest_diff` evenly over the input elements of its window
+/// and accumulates the result in `src_diff`, which is overwritten.
+pub fn pooling_avg_grad<T: Float + Send + Sync>(pool: &ThreadPool, src_dims: &[usize], src_diff: &mut [T], dest_diff: &[T], dest_dims: &[usize], config: &PoolingConfig) {
+    let (height, width) = (src_dims[2], src_dims[3]);
+    let (out_h, out_w) = (dest_dims[2], dest_dims[3]);
+    let images = src_dims[0] * src_dims[1];
+    parallel::for_each_chunk_mut(pool, &mut src_diff[..images * height * width], height * width, |offset, chunk| {
+        let first = offset / (height * width);
+        for value in chunk.iter_mut() {
+            *value = T::zero();
+        }
+        for image in 0..chunk.len() / (height * width) {
+            let input_diff = &mut chunk[image * height * width..(image + 1) * height * width];
+            for oh in 0..out_h {
+                for ow in 0..out_w {
+                    let (rows, cols) = config.window_range(oh, ow, height, width);
+                    let count = config.avg_count(rows.len(), cols.len());
+                    if count == 0 {
+                        continue;
+                    }
+                    let diff = dest_diff[((first + image) * out_h + oh) * out_w + ow] / T::from(count).unwrap();
+                    for h in rows {
+                        for w in cols.clone() {
+                            input_diff[h * width + w] = input_diff[h * width + w] + diff;
+                        }
                     }
                 }
-                Err(Error::Plugin(PluginError::Operation("Unable to execute Native sigmoid grad Forward.")))
             }
         }
-    );
+    });
 }
 
-#[macro_export]
-macro_rules! impl_ops_relu_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::Relu<$t> for $b {
-            fn relu(
-                &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>
-            ) -> Result<(), ::co::error::Error> {
-                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
-                match result.add_device(self.device()) { _ => () }
-                self.relu_plain(x, result)
+/// Computes the batch normalization of `src` with the statistics of the batch.
+///
+/// Moves `running_mean` and `running_variance` towards the batch mean and unbiased batch variance
+/// and saves the batch mean and inverse standard deviation to `saved_mean` and `saved_inv_variance`.
+pub fn batch_norm_training<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], dest: &mut [T], gamma: &[T], beta: &[T],
+                                                   running_mean: &mut [T], running_variance: &mut [T],
+                                                   saved_mean: &mut [T], saved_inv_variance: &mut [T], config: &BatchNormalizationConfig) {
+    let (batch, features, spatial) = config.layout(dims);
+    let count = batch * spatial;
+    let epsilon: T = ::co::plugin::numeric_helpers::cast(config.epsilon).unwrap();
+    let momentum: T = ::co::plugin::numeric_helpers::cast(config.momentum).unwrap();
+    let m = T::from(count).unwrap();
+    let bessel = if count > 1 { m / T::from(count - 1).unwrap() } else { T::one() };
+    let mut stats = vec![(T::zero(), T::zero()); features];
+    parallel::map_indices(pool, &mut stats, count, |f| {
+        let indices = || (0..batch).flat_map(move |n| (0..spatial).map(move |s| (n * features + f) * spatial + s));
+        let mean = indices().fold(T::zero(), |acc, i| acc + src[i]) / m;
+        let variance = indices().fold(T::zero(), |acc, i| acc + (src[i] - mean) * (src[i] - mean)) / m;
+        (mean, variance)
+    });
+    if spatial > 0 {
+        let stats = &stats;
+        parallel::for_each_chunk_mut(pool, &mut dest[..batch * features * spatial], spatial, |offset, chunk| {
+            for (index, plane) in chunk.chunks_mut(spatial).enumerate() {
+                let f = (offset / spatial + index) % features;
+                let (mean, variance) = stats[f];
+                let inv_std = T::one() / (variance + epsilon).sqrt();
+                for (value, x) in plane.iter_mut().zip(src[offset + index * spatial..].iter()) {
+                    *value = gamma[f] * (*x - mean) * inv_std + beta[f];
+                }
             }
+        });
+    }
+    for (f, &(mean, variance)) in stats.iter().enumerate() {
+        running_mean[f] = (T::one() - momentum) * running_mean[f] + momentum * mean;
+        running_variance[f] = (T::one() - momentum) * running_variance[f] + momentum * variance * bessel;
+        saved_mean[f] = mean;
+        saved_inv_variance[f] = T::one() / (variance + epsilon).sqrt();
+    }
+}
 
-            fn relu_plain(
-                &self,
-                x: &::co::tensor::SharedTensor<$t>,
+/// Computes the batch normalization of `src` with the running statistics.
+pub fn batch_norm_inference<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], dest: &mut [T], gamma: &[T], beta: &[T],
+                                                    running_mean: &[T], running_variance: &[T], config: &BatchNormalizationConfig) {
+    let (batch, features, spatial) = config.layout(dims);
+    let epsilon: T = ::co::plugin::numeric_helpers::cast(config.epsilon).unwrap();
+    if spatial == 0 {
+        return;
+    }
+    parallel::for_each_chunk_mut(pool, &mut dest[..batch * features * spatial], spatial, |offset, chunk| {
+        for (index, plane) in chunk.chunks_mut(spatial).enumerate() {
+            let f = (offset / spatial + index) % features;
+            let scale = gamma[f] / (running_variance[f] + epsilon).sqrt();
+            for (value, x) in plane.iter_mut().zip(src[offset + index * spatial..].iter()) {
+                *value = (*x - running_mean[f]) * scale + beta[f];
+            }
+        }
+    });
+}
+
+/// Computes the gradient of batch normalization in training mode.
+///
+/// Takes the gradient of the output `dest_diff` and the statistics saved by the forward pass and
+/// saves the gradients of the input, the scale and the bias to `src_diff`, `gamma_diff` and `beta_diff`.
+pub fn batch_norm_grad<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], dest_diff: &[T], gamma: &[T], saved_mean: &[T], saved_inv_variance: &[T],
+                                               src_diff: &mut [T], gamma_diff: &mut [T], beta_diff: &mut [T], config: &BatchNormalizationConfig) {
+    let (batch, features, spatial) = config.layout(dims);
+    let m = T::from(batch * spatial).unwrap();
+    let mut sums = vec![(T::zero(), T::zero()); features];
+    parallel::map_indices(pool, &mut sums, batch * spatial, |f| {
+        let indices = || (0..batch).flat_map(move |n| (0..spatial).map(move |s| (n * features + f) * spatial + s));
+        let (mean, inv_std) = (saved_mean[f], saved_inv_variance[f]);
+        let sum_diff = indices().fold(T::zero(), |acc, i| acc + dest_diff[i]);
+        let sum_diff_normalized = indices().fold(T::zero(), |acc, i| acc + dest_diff[i] * (src[i] - mean) * inv_std);
+        (sum_diff_normalized, sum_diff)
+    });
+    if spatial > 0 {
+        let sums = &sums;
+        parallel::for_each_chunk_mut(pool, &mut src_diff[..batch * features * spatial], spatial, |offset, chunk| {
+            for (index, plane) in chunk.chunks_mut(spatial).enumerate() {
+                let f = (offset / spatial + index) % features;
+                let (mean, inv_std) = (saved_mean[f], saved_inv_variance[f]);
+                let (sum_diff_normalized, sum_diff) = sums[f];
+                let scale = gamma[f] * inv_std / m;
+                for (value, i) in plane.iter_mut().zip(offset + index * spatial..) {
+                    let normalized = (src[i] - mean) * inv_std;
+                    *value = scale * (m * dest_diff[i] - sum_diff - normalized * sum_diff_normalized);
+                }
+            }
+        });
+    }
+    for (f, &(sum_diff_normalized, sum_diff)) in sums.iter().enumerate() {
+        gamma_diff[f] = sum_diff_normalized;
+        beta_diff[f] = sum_diff;
+    }
+}
+
+/// Normalizes the `groups` consecutive groups of `len` elements of `src` with their own statistics
+/// and scales and shifts every element by the `gamma` and `beta` of its parameter.
+///
+/// The input is laid out as `[outer, params, inner]`, so element `i` has the parameter `i / inner % params`.
+/// Saves the mean and inverse standard deviation of every group to `saved_mean` and `saved_inv_variance`.
+fn normalize_groups<T>(pool: &ThreadPool, src: &[T], dest: &mut [T], gamma: &[T], beta: &[T], saved_mean: &mut [T], saved_inv_variance: &mut [T],
+                       groups: usize, len: usize, params: usize, inner: usize, epsilon: f64)
+    where T: Float + Send + Sync
+{
+    let epsilon: T = ::co::plugin::numeric_helpers::cast(epsilon).unwrap();
+    let m = T::from(len).unwrap();
+    let mut stats = vec![(T::zero(), T::zero()); groups];
+    parallel::map_indices(pool, &mut stats, len, |g| {
+        let group = &src[g * len..(g + 1) * len];
+        let mean = group.iter().fold(T::zero(), |acc, x| acc + *x) / m;
+        let variance = group.iter().fold(T::zero(), |acc, x| acc + (*x - mean) * (*x - mean)) / m;
+        (mean, T::one() / (variance + epsilon).sqrt())
+    });
+    for (g, &(mean, inv_std)) in stats.iter().enumerate() {
+        saved_mean[g] = mean;
+        saved_inv_variance[g] = inv_std;
+    }
+    let stats = &stats;
+    parallel::for_each_chunk_mut(pool, &mut dest[..groups * len], len, |offset, chunk| {
+        for (index, value) in chunk.iter_mut().enumerate() {
+            let i = offset + index;
+            let (mean, inv_std) = stats[i / len];
+            let p = i / inner % params;
+            *value = gamma[p] * (src[i] - mean) * inv_std + beta[p];
+        }
+    });
+}
+
+/// Computes the gradient of `normalize_groups`.
+///
+/// Takes the gradient of the output `dest_diff` and the statistics saved by the forward pass and
+/// saves the gradients of the input, the scale and the bias to `src_diff`, `gamma_diff` and `beta_diff`.
+/// Every parameter gradient sums its elements in index order, so it doesn't depend on the number of threads.
+fn normalize_groups_grad<T>(pool: &ThreadPool, src: &[T], dest_diff: &[T], gamma: &[T], saved_mean: &[T], saved_inv_variance: &[T],
+                            src_diff: &mut [T], gamma_diff: &mut [T], beta_diff: &mut [T], groups: usize, len: usize, params: usize, inner: usize)
+    where T: Float + Send + Sync
+{
+    let m = T::from(len).unwrap();
+    parallel::for_each_chunk_mut(pool, &mut src_diff[..groups * len], len, |offset, chunk| {
+        for (index, diff) in chunk.chunks_mut(len).enumerate() {
+            let first = offset + index * len;
+            let g = first / len;
+            let (mean, inv_std) = (saved_mean[g], saved_inv_variance[g]);
+            let normalized = |k: usize| (src[first + k] - mean) * inv_std;
+            let scaled_diff = |k: usize| dest_diff[first + k] * gamma[(first + k) / inner % params];
+            let sum_diff = (0..len).fold(T::zero(), |acc, k| acc + scaled_diff(k));
+            let sum_diff_normalized = (0..len).fold(T::zero(), |acc, k| acc + scaled_diff(k) * normalized(k));
+            for (k, value) in diff.iter_mut().enumerate() {
+                *value = inv_std / m * (m * scaled_diff(k) - sum_diff - normalized(k) * sum_diff_normalized);
+            }
+        }
+    });
+    let outer = groups * len / ::std::cmp::max(params * inner, 1);
+    let mut sums = vec![(T::zero(), T::zero()); params];
+    parallel::map_indices(pool, &mut sums, outer * inner, |p| {
+        let indices = || (0..outer).flat_map(move |n| (0..inner).map(move |s| (n * params + p) * inner + s));
+        let sum_diff_normalized = indices().fold(T::zero(), |acc, i| acc + dest_diff[i] * (src[i] - saved_mean[i / len]) * saved_inv_variance[i / len]);
+        let sum_diff = indices().fold(T::zero(), |acc, i| acc + dest_diff[i]);
+        (sum_diff_normalized, sum_diff)
+    });
+    for (p, &(sum_diff_normalized, sum_diff)) in sums.iter().enumerate() {
+        gamma_diff[p] = sum_diff_normalized;
+        beta_diff[p] = sum_diff;
+    }
+}
+
+/// Computes the layer normalization of the `[groups, features]` input `src`.
+///
+/// Saves the mean and inverse standard deviation of every group to `saved_mean` and `saved_inv_variance`.
+pub fn layer_norm<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dest: &mut [T], gamma: &[T], beta: &[T], saved_mean: &mut [T], saved_inv_variance: &mut [T],
+                                          groups: usize, features: usize, config: &LayerNormalizationConfig) {
+    normalize_groups(pool, src, dest, gamma, beta, saved_mean, saved_inv_variance, groups, features, features, 1, config.epsilon);
+}
+
+/// Computes the gradient of layer normalization.
+///
+/// Takes the gradient of the output `dest_diff` and the statistics saved by the forward pass and
+/// saves the gradients of the input, the scale and the bias to `src_diff`, `gamma_diff` and `beta_diff`.
+pub fn layer_norm_grad<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dest_diff: &[T], gamma: &[T], saved_mean: &[T], saved_inv_variance: &[T],
+                                               src_diff: &mut [T], gamma_diff: &mut [T], beta_diff: &mut [T], groups: usize, features: usize) {
+    normalize_groups_grad(pool, src, dest_diff, gamma, saved_mean, saved_inv_variance, src_diff, gamma_diff, beta_diff, groups, features, features, 1);
+}
+
+/// Computes the group normalization of the NCHW input `src` of `dims`.
+///
+/// Saves the mean and inverse standard deviation of every group of every sample to `saved_mean` and `saved_inv_variance`.
+pub fn group_norm<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], dest: &mut [T], gamma: &[T], beta: &[T],
+                                          saved_mean: &mut [T], saved_inv_variance: &mut [T], config: &GroupNormalizationConfig) {
+    let (batch, channels, spatial) = NormalizationConfig::layout(dims);
+    let groups = config.groups;
+    normalize_groups(pool, src, dest, gamma, beta, saved_mean, saved_inv_variance, batch * groups, channels / groups * spatial, channels, spatial, config.epsilon);
+}
+
+/// Computes the gradient of group normalization.
+///
+/// Takes the gradient of the output `dest_diff` and the statistics saved by the forward pass and
+/// saves the gradients of the input, the scale and the bias to `src_diff`, `gamma_diff` and `beta_diff`.
+pub fn group_norm_grad<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], dest_diff: &[T], gamma: &[T], saved_mean: &[T], saved_inv_variance: &[T],
+                                               src_diff: &mut [T], gamma_diff: &mut [T], beta_diff: &mut [T], config: &GroupNormalizationConfig) {
+    let (batch, channels, spatial) = NormalizationConfig::layout(dims);
+    let groups = config.groups;
+    normalize_groups_grad(pool, src, dest_diff, gamma, saved_mean, saved_inv_variance, src_diff, gamma_diff, beta_diff,
+                          batch * groups, channels / groups * spatial, channels, spatial);
+}
+
+/// Mixes `value` with the SplitMix64 finalizer.
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Draws the dropout mask number `number` of `config` into `mask`.
+///
+/// Every element only depends on the seed, the mask number and its index,
+/// so the mask is the same for any number of threads.
+pub fn dropout_mask<T: Float + Send + Sync>(pool: &ThreadPool, mask: &mut [T], number: u64, config: &DropoutConfig) {
+    let stream = split_mix(config.seed ^ split_mix(number));
+    let scale = T::one() / ::co::plugin::numeric_helpers::cast::<f64, T>(1f64 - config.probability).unwrap();
+    let probability = config.probability;
+    parallel::for_each_chunk_mut(pool, mask, 1, |offset, chunk| {
+        for (index, value) in chunk.iter_mut().enumerate() {
+            // the upper 53 bits give a uniform sample of [0, 1).
+            let sample = (split_mix(stream.wrapping_add((offset + index) as u64)) >> 11) as f64 / (1u64 << 53) as f64;
+            *value = if sample < probability { T::zero() } else { scale };
+        }
+    });
+}
+
+/// Computes the scale `k + alpha / n * sum(x_j^2)` of every element in `src`,
+/// summing over the channels of its LRN window.
+fn lrn_scale<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], config: &NormalizationConfig) -> Vec<T> {
+    let (batch, channels, size) = NormalizationConfig::layout(dims);
+    let alpha_over_n: T = ::co::plugin::numeric_helpers::cast(config.alpha / config.n as f64).unwrap();
+    let k: T = ::co::plugin::numeric_helpers::cast(config.k).unwrap();
+    let mut scale = vec![k; batch * channels * size];
+    parallel::for_each_chunk_mut(pool, &mut scale, channels * size, |offset, chunk| {
+        let first = offset / (channels * size);
+        for b in 0..chunk.len() / (channels * size) {
+            for c in 0..channels {
+                for i in 0..size {
+                    let sum = config.window(c, channels)
+                        .map(|j| src[((first + b) * channels + j) * size + i])
+                        .fold(T::zero(), |acc, x| acc + x * x);
+                    chunk[(b * channels + c) * size + i] = k + alpha_over_n * sum;
+                }
+            }
+        }
+    });
+    scale
+}
+
+/// Computes the cross-channel local response normalization of `src` into `dest`.
+pub fn lrn<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], dest: &mut [T], config: &NormalizationConfig) {
+    let beta: T = ::co::plugin::numeric_helpers::cast(config.beta).unwrap();
+    let scale = lrn_scale(pool, src, dims, config);
+    parallel::zip_map(pool, src, &scale, dest, |x, s| *x * s.powf(-beta));
+}
+
+/// Computes the gradient of the cross-channel local response normalization.
+///
+/// Writes the gradient w.r.t. the input `src` into `src_diff`, given the output gradient `dest_diff`.
+pub fn lrn_grad<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dims: &[usize], src_diff: &mut [T], dest_diff: &[T], config: &NormalizationConfig) {
+    let (_, channels, size) = NormalizationConfig::layout(dims);
+    let beta: T = ::co::plugin::numeric_helpers::cast(config.beta).unwrap();
+    let factor: T = ::co::plugin::numeric_helpers::cast(2f64 * config.alpha * config.beta / config.n as f64).unwrap();
+    let scale = lrn_scale(pool, src, dims, config);
+    // dy_j * y_j / s_j of every element, with y_j = x_j * s_j^-beta.
+    let mut ratio = vec![T::zero(); scale.len()];
+    parallel::for_each_chunk_mut(pool, &mut ratio, 1, |offset, chunk| {
+        for (j, r) in chunk.iter_mut().enumerate() {
+            let i = offset + j;
+            *r = dest_diff[i] * src[i] * scale[i].powf(-beta - T::one());
+        }
+    });
+    parallel::for_each_chunk_mut(pool, &mut src_diff[..scale.len()], channels * size, |offset, chunk| {
+        for b in 0..chunk.len() / (channels * size) {
+            for c in 0..channels {
+                for i in 0..size {
+                    let local = (b * channels + c) * size + i;
+                    let index = offset + local;
+                    // channel `c` is part of the window of every channel `j` whose window contains it.
+                    let sum = config.inverse_window(c, channels)
+                        .map(|j| ratio[offset + (b * channels + j) * size + i])
+                        .fold(T::zero(), |acc, r| acc + r);
+                    chunk[local] = dest_diff[index] * scale[index].powf(-beta) - factor * src[index] * sum;
+                }
+            }
+        }
+    });
+}
+
+/// Returns the `(outer, length, inner)` layout of a softmax over a Tensor of `dims`.
+///
+/// Follows the cuDNN backend: a Tensor with two or at least four dimensions is a batch
+/// of samples along the first dimension, all other Tensors are a single sample.
+pub fn softmax_layout(dims: &[usize]) -> (usize, usize, usize) {
+    let size = dims.iter().fold(1, |acc, d| acc * d);
+    match dims.len() {
+        2 => (dims[0], dims[1], 1),
+        0 | 1 | 3 => (1, size, 1),
+        _ => (dims[0], size / dims[0], 1),
+    }
+}
+
+/// Returns the `(outer, length, inner)` layout of a softmax along `axis` of a Tensor of `dims`.
+pub fn softmax_axis_layout(dims: &[usize], axis: usize) -> Result<(usize, usize, usize), PluginError> {
+    if axis >= dims.len() {
+        return Err(PluginError::Operation("Native softmax axis is out of range for the Tensor."));
+    }
+    Ok((dims[..axis].iter().fold(1, |acc, d| acc * d),
+        dims[axis],
+        dims[axis + 1..].iter().fold(1, |acc, d| acc * d)))
+}
+
+/// Calls `func` with the input offset, the output chunk and the element index function
+/// of every `length` long slice of a `(outer, length, inner)` `layout` in parallel.
+fn for_each_softmax_slice<T, F>(pool: &ThreadPool, dest: &mut [T], layout: (usize, usize, usize), func: F)
+    where T: Send, F: Fn(usize, &mut [T], &Fn(usize) -> usize) + Sync
+{
+    let (outer, length, inner) = layout;
+    parallel::for_each_chunk_mut(pool, &mut dest[..outer * length * inner], length * inner, |offset, chunk| {
+        for o in 0..chunk.len() / (length * inner) {
+            for i in 0..inner {
+                func(offset, chunk, &|k: usize| (o * length + k) * inner + i);
+            }
+        }
+    });
+}
+
+/// Computes the softmax of every `length` long slice of `src` in the `(outer, length, inner)` `layout`.
+///
+/// The maximum of every slice is subtracted before exponentiating, so large logits don't overflow.
+/// A slice of only negative infinities is treated like a slice of equal values.
+pub fn softmax<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dest: &mut [T], layout: (usize, usize, usize)) {
+    let length = layout.1;
+    for_each_softmax_slice(pool, dest, layout, |offset, dest, index| {
+        let max = (0..length).fold(T::neg_infinity(), |acc, k| acc.max(src[offset + index(k)]));
+        if max == T::neg_infinity() {
+            let uniform = T::one() / T::from(length).unwrap();
+            for k in 0..length {
+                dest[index(k)] = uniform;
+            }
+            return;
+        }
+        let mut sum = T::zero();
+        for k in 0..length {
+            dest[index(k)] = (src[offset + index(k)] - max).exp();
+            sum = sum + dest[index(k)];
+        }
+        for k in 0..length {
+            dest[index(k)] = dest[index(k)] / sum;
+        }
+    });
+}
+
+/// Computes the softmax gradient from the softmax output `y` and its gradient `dy`
+/// in the `(outer, length, inner)` `layout`.
+pub fn softmax_grad<T: Float + Send + Sync>(pool: &ThreadPool, y: &[T], dy: &[T], dx: &mut [T], layout: (usize, usize, usize)) {
+    let length = layout.1;
+    for_each_softmax_slice(pool, dx, layout, |offset, dx, index| {
+        let dot = (0..length).fold(T::zero(), |acc, k| acc + y[offset + index(k)] * dy[offset + index(k)]);
+        for k in 0..length {
+            dx[index(k)] = y[offset + index(k)] * (dy[offset + index(k)] - dot);
+        }
+    });
+}
+
+/// Computes `ln(sum(e^value(k)))` over the `length` values returned by `value`.
+///
+/// The maximum is subtracted before exponentiating, so large values don't overflow.
+/// If every value is negative infinity, so is the result.
+pub fn log_sum_exp<T: Float, F: Fn(usize) -> T>(length: usize, value: F) -> T {
+    let max = (0..length).fold(T::neg_infinity(), |acc, k| acc.max(value(k)));
+    if max == T::neg_infinity() {
+        return max;
+    }
+    let sum = (0..length).fold(T::zero(), |acc, k| acc + (value(k) - max).exp());
+    max + sum.ln()
+}
+
+/// Computes the logarithmic softmax of every `length` long slice of `src` in the `(outer, length, inner)` `layout`.
+///
+/// A slice of only negative infinities is treated like a slice of equal values.
+pub fn log_softmax<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], dest: &mut [T], layout: (usize, usize, usize)) {
+    let length = layout.1;
+    for_each_softmax_slice(pool, dest, layout, |offset, dest, index| {
+        let logsum = log_sum_exp(length, |k| src[offset + index(k)]);
+        if logsum == T::neg_infinity() {
+            let uniform = -T::from(length).unwrap().ln();
+            for k in 0..length {
+                dest[index(k)] = uniform;
+            }
+            return;
+        }
+        for k in 0..length {
+            dest[index(k)] = src[offset + index(k)] - logsum;
+        }
+    });
+}
+
+/// Computes the logarithmic softmax gradient from the logarithmic softmax output `y` and its
+/// gradient `dy` in the `(outer, length, inner)` `layout`.
+pub fn log_softmax_grad<T: Float + Send + Sync>(pool: &ThreadPool, y: &[T], dy: &[T], dx: &mut [T], layout: (usize, usize, usize)) {
+    let length = layout.1;
+    for_each_softmax_slice(pool, dx, layout, |offset, dx, index| {
+        let sum = (0..length).fold(T::zero(), |acc, k| acc + dy[offset + index(k)]);
+        for k in 0..length {
+            dx[index(k)] = dy[offset + index(k)] - y[offset + index(k)].exp() * sum;
+        }
+    });
+}
+
+/// Checks that `bias_dims` hold one bias per channel of the `[batch, channels, spatial..]` `dims`
+/// and that none of the spatial dimensions is empty.
+pub fn conv_bias_check_shapes(bias_dims: &[usize], dims: &[usize]) -> Result<(), PluginError> {
+    if dims.len() < 2 || bias_dims.iter().fold(1, |acc, d| acc * d) != dims[1] {
+        return Err(PluginError::Operation("Native convolution bias requires one bias per channel of the output."));
+    }
+    if dims[2..].iter().any(|&d| d == 0) {
+        return Err(PluginError::Operation("Native convolution bias requires non-empty spatial dimensions."));
+    }
+    Ok(())
+}
+
+/// Adds the per-channel `bias` to every position of the `[batch, channels, spatial..]` tensor `dest`.
+pub fn conv_bias<T: Float + Send + Sync>(pool: &ThreadPool, bias: &[T], dest: &mut [T], dims: &[usize]) {
+    let (_, channels, spatial) = NormalizationConfig::layout(dims);
+    parallel::for_each_chunk_mut(pool, dest, spatial, |offset, chunk| {
+        for (plane, values) in chunk.chunks_mut(spatial).enumerate() {
+            let channel_bias = bias[(offset / spatial + plane) % channels];
+            for value in values.iter_mut() {
+                *value = *value + channel_bias;
+            }
+        }
+    });
+}
+
+/// Sums the `[batch, channels, spatial..]` gradients `dest_diff` of every channel into `bias_diff`.
+pub fn conv_bias_grad<T: Float>(dest_diff: &[T], dims: &[usize], bias_diff: &mut [T]) {
+    let (_, channels, spatial) = NormalizationConfig::layout(dims);
+    for value in bias_diff[..channels].iter_mut() {
+        *value = T::zero();
+    }
+    for (plane, values) in dest_diff.chunks(spatial).enumerate() {
+        let sum = values.iter().fold(T::zero(), |acc, v| acc + *v);
+        bias_diff[plane % channels] = bias_diff[plane % channels] + sum;
+    }
+}
+
+/// Returns the `(batch, inputs, outputs)` of a fully connected layer with an input of `src_dims` and weights of `weight_dims`.
+pub fn linear_dims(src_dims: &[usize], weight_dims: &[usize]) -> Result<(usize, usize, usize), PluginError> {
+    if weight_dims.len() != 2 {
+        return Err(PluginError::Operation("Native linear requires weights of [outputs, inputs]."));
+    }
+    let (batch, inputs) = match src_dims.split_first() {
+        Some((batch, rest)) => (*batch, rest.iter().fold(1, |acc, d| acc * d)),
+        None => return Err(PluginError::Operation("Native linear requires an input of [batch, inputs].")),
+    };
+    if inputs != weight_dims[1] {
+        return Err(PluginError::Operation("Native linear requires as many inputs per sample as the weights have columns."));
+    }
+    Ok((batch, inputs, weight_dims[0]))
+}
+
+/// Computes the fully connected layer `dest = src * weight^T + bias` of a `[batch, inputs]` input.
+pub fn linear<T: Float + Send + Sync>(pool: &ThreadPool, src: &[T], weight: &[T], bias: &[T], dest: &mut [T], batch: usize, inputs: usize, outputs: usize) {
+    for row in dest[..batch * outputs].chunks_mut(outputs) {
+        row.copy_from_slice(&bias[..outputs]);
+    }
+    gemm(pool, false, true, batch, outputs, inputs, T::one(), src, weight, T::one(), dest);
+}
+
+/// Sums the `[batch, outputs]` output gradients `dest_diff` over the batch into `bias_diff`.
+pub fn linear_grad_bias<T: Float>(dest_diff: &[T], bias_diff: &mut [T], batch: usize, outputs: usize) {
+    for value in bias_diff[..outputs].iter_mut() {
+        *value = T::zero();
+    }
+    for row in dest_diff[..batch * outputs].chunks(outputs) {
+        for (value, diff) in bias_diff.iter_mut().zip(row.iter()) {
+            *value = *value + *diff;
+        }
+    }
+}
+
+/// Computes the general matrix multiplication `c = alpha * op(a) * op(b) + beta * c` on row-major matrices.
+///
+/// `op(a)` is `m` x `k`, `op(b)` is `k` x `n` and `c` is `m` x `n`, where `op` transposes
+/// the stored matrix if the matching `trans_*` flag is set.
+pub fn gemm<T: Float + Send + Sync>(pool: &ThreadPool, trans_a: bool, trans_b: bool, m: usize, n: usize, k: usize,
+                                    alpha: T, a: &[T], b: &[T], beta: T, c: &mut [T]) {
+    parallel::for_each_chunk_mut(pool, &mut c[..m * n], n, |offset, rows| {
+        for value in rows.iter_mut() {
+            *value = if beta == T::zero() { T::zero() } else { *value * beta };
+        }
+        for row in 0..rows.len() / n {
+            let i = offset / n + row;
+            let c_row = &mut rows[row * n..(row + 1) * n];
+            for p in 0..k {
+                let a_ip = alpha * if trans_a { a[p * m + i] } else { a[i * k + p] };
+                if trans_b {
+                    for (j, c_ij) in c_row.iter_mut().enumerate() {
+                        *c_ij = *c_ij + a_ip * b[j * k + p];
+                    }
+                } else {
+                    for (c_ij, b_pj) in c_row.iter_mut().zip(b[p * n..(p + 1) * n].iter()) {
+                        *c_ij = *c_ij + a_ip * *b_pj;
+                    }
+                }
+            }
+        }
+    });
+}
+
+macro_rules! impl_oconf_for_cc(($($t: ident), +) => (
+    $(
+        impl<'a> NNOperationConfig<$t> for ::frameworks::native::helper::ConvolutionConfig { }
+        impl<'a> ConvolutionConfig<$t> for ::frameworks::native::helper::ConvolutionConfig {
+            fn workspace_size(&self) -> usize {
+                self.workspace_len() * ::std::mem::size_of::<$t>()
+            }
+        }
+    )+
+));
+
+macro_rules! impl_oconf_for_ctc(($($t: ident), +) => (
+    $(
+        impl NNOperationConfig<$t> for ::frameworks::native::helper::TransposedConvolutionConfig { }
+        impl ConvolutionConfig<$t> for ::frameworks::native::helper::TransposedConvolutionConfig {
+            fn workspace_size(&self) -> usize {
+                self.convolution.workspace_len() * ::std::mem::size_of::<$t>()
+            }
+        }
+    )+
+));
+
+macro_rules! impl_oconf_for_clrn(($($t: ident), +) => (
+    $(
+        impl NNOperationConfig<$t> for ::frameworks::native::helper::NormalizationConfig { }
+    )+
+));
+
+macro_rules! impl_oconf_for_batch_normalization(($($t: ident), +) => (
+    $(
+        impl NNOperationConfig<$t> for ::frameworks::native::helper::BatchNormalizationConfig { }
+    )+
+));
+
+macro_rules! impl_oconf_for_group_normalization(($($t: ident), +) => (
+    $(
+        impl NNOperationConfig<$t> for ::frameworks::native::helper::GroupNormalizationConfig { }
+    )+
+));
+
+macro_rules! impl_oconf_for_layer_normalization(($($t: ident), +) => (
+    $(
+        impl NNOperationConfig<$t> for ::frameworks::native::helper::LayerNormalizationConfig { }
+    )+
+));
+
+macro_rules! impl_oconf_for_dropout(($($t: ident), +) => (
+    $(
+        impl NNOperationConfig<$t> for ::frameworks::native::helper::DropoutConfig { }
+    )+
+));
+
+macro_rules! impl_oconf_for_pooling(($($t: ident), +) => (
+    $(
+        impl NNOperationConfig<$t> for ::frameworks::native::helper::PoolingConfig { }
+    )+
+));
+
+#[macro_export]
+macro_rules! impl_ops_sigmoid_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Sigmoid<$t> for B {
+            fn sigmoid(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.sigmoid_plain(x, result)
+            }
+
+            fn sigmoid_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        let (input, output) = (input.as_slice::<$t>(), output.as_mut_slice::<$t>());
+                        ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut output[..input.len()], 1, |offset, chunk| {
+                            ::frameworks::native::simd::forward(::frameworks::native::simd::Function::Sigmoid, &input[offset..], chunk)
+                        });
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native sigmoid Forward.")))
+            }
+
+            fn sigmoid_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.sigmoid_grad_plain(x, x_diff, result, result_diff)
+            }
+
+            fn sigmoid_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(output) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            let (sig_data, sig_dx, output) = (sig_data.as_slice::<$t>(), sig_dx.as_slice::<$t>(), output.as_mut_slice::<$t>());
+                            ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut output[..sig_data.len()], 1, |offset, chunk| {
+                                ::frameworks::native::simd::backward(::frameworks::native::simd::Function::Sigmoid, &sig_data[offset..], &sig_dx[offset..], chunk)
+                            });
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native sigmoid grad Forward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_sigmoid_pointwise_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::SigmoidPointwise<$t> for B {
+            fn sigmoid_pointwise(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                self.sigmoid_pointwise_plain(x)
+            }
+
+            fn sigmoid_pointwise_plain(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(input) = x.get_mut(self.device()).unwrap().as_mut_native() {
+                    ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), input.as_mut_slice::<$t>(), 1, |_, chunk| {
+                        ::frameworks::native::simd::forward_in_place(::frameworks::native::simd::Function::Sigmoid, chunk)
+                    });
+                    return Ok(());
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native Sigmoid Pointwise forward.")))
+            }
+
+            fn sigmoid_pointwise_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                self.sigmoid_pointwise_grad_plain(x, x_diff)
+            }
+
+            fn sigmoid_pointwise_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(sig_dx) = x_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                        let (sig_data, sig_dx) = (sig_data.as_slice::<$t>(), sig_dx.as_mut_slice::<$t>());
+                        ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut sig_dx[..sig_data.len()], 1, |offset, chunk| {
+                            ::frameworks::native::simd::backward_in_place(::frameworks::native::simd::Function::Sigmoid, &sig_data[offset..], chunk)
+                        });
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native Sigmoid Pointwise backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_relu_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Relu<$t> for B {
+            fn relu(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.relu_plain(x, result)
+            }
+
+            fn relu_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
                 if let Some(input) = x.get(self.device()).unwrap().as_native() {
-                    let res = input.as_slice::<$t>().iter().map(::frameworks::native::helper::relu);
-                    ::frameworks::native::helper::write_to_memory(result.get_mut(self.device()).unwrap(), res);
-                    return Ok(());
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        let (input, output) = (input.as_slice::<$t>(), output.as_mut_slice::<$t>());
+                        ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut output[..input.len()], 1, |offset, chunk| {
+                            ::frameworks::native::simd::forward(::frameworks::native::simd::Function::Relu, &input[offset..], chunk)
+                        });
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native ReLU Forward.")))
+            }
+
+            fn relu_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.relu_grad_plain(x, x_diff, result, result_diff)
+            }
+
+            fn relu_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(dx) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(output) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            let (input, dx, output) = (input.as_slice::<$t>(), dx.as_slice::<$t>(), output.as_mut_slice::<$t>());
+                            ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut output[..input.len()], 1, |offset, chunk| {
+                                ::frameworks::native::simd::backward(::frameworks::native::simd::Function::Relu, &input[offset..], &dx[offset..], chunk)
+                            });
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native ReLU grad Forward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_relu_pointwise_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::ReluPointwise<$t> for B {
+            fn relu_pointwise(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                self.relu_pointwise_plain(x)
+            }
+
+            fn relu_pointwise_plain(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(input) = x.get_mut(self.device()).unwrap().as_mut_native() {
+                    ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), input.as_mut_slice::<$t>(), 1, |_, chunk| {
+                        ::frameworks::native::simd::forward_in_place(::frameworks::native::simd::Function::Relu, chunk)
+                    });
+                    return Ok(());
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native ReLU Pointwise forward.")))
+            }
+
+            fn relu_pointwise_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                self.relu_pointwise_grad_plain(x, x_diff)
+            }
+
+            fn relu_pointwise_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(relu_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(relu_dx) = x_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                        let (relu_data, relu_dx) = (relu_data.as_slice::<$t>(), relu_dx.as_mut_slice::<$t>());
+                        ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut relu_dx[..relu_data.len()], 1, |offset, chunk| {
+                            ::frameworks::native::simd::backward_in_place(::frameworks::native::simd::Function::Relu, &relu_data[offset..], chunk)
+                        });
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native ReLU Pointwise backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_clipped_relu_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::ClippedRelu<$t> for B {
+            fn clipped_relu(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.clipped_relu_plain(x, result, ceiling)
+            }
+
+            fn clipped_relu_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                ::plugin::Activation::<$t>::activation_plain(self, ::plugin::ActivationMode::ClippedRelu(ceiling), x, result)
+            }
+
+            fn clipped_relu_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.clipped_relu_grad_plain(x, x_diff, result, result_diff, ceiling)
+            }
+
+            fn clipped_relu_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                ::plugin::Activation::<$t>::activation_grad_plain(self, ::plugin::ActivationMode::ClippedRelu(ceiling), x, x_diff, result, result_diff)
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_clipped_relu_pointwise_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::ClippedReluPointwise<$t> for B {
+            fn clipped_relu_pointwise(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                self.clipped_relu_pointwise_plain(x, ceiling)
+            }
+
+            fn clipped_relu_pointwise_plain(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                ::plugin::Activation::<$t>::activation_pointwise_plain(self, ::plugin::ActivationMode::ClippedRelu(ceiling), x)
+            }
+
+            fn clipped_relu_pointwise_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                self.clipped_relu_pointwise_grad_plain(x, x_diff, ceiling)
+            }
+
+            fn clipped_relu_pointwise_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                ceiling: f64
+            ) -> Result<(), ::co::error::Error> {
+                ::plugin::Activation::<$t>::activation_pointwise_grad_plain(self, ::plugin::ActivationMode::ClippedRelu(ceiling), x, x_diff)
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_tanh_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Tanh<$t> for B {
+            #[inline]
+            fn tanh(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.tanh_plain(x, result)
+            }
+
+            fn tanh_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        let (input, output) = (input.as_slice::<$t>(), output.as_mut_slice::<$t>());
+                        ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut output[..input.len()], 1, |offset, chunk| {
+                            ::frameworks::native::simd::forward(::frameworks::native::simd::Function::Tanh, &input[offset..], chunk)
+                        });
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native tanh Forward.")))
+            }
+
+            fn tanh_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.tanh_grad_plain(x, x_diff, result, result_diff)
+            }
+
+            fn tanh_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(dx) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(output) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            let (input, dx, output) = (input.as_slice::<$t>(), dx.as_slice::<$t>(), output.as_mut_slice::<$t>());
+                            ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut output[..input.len()], 1, |offset, chunk| {
+                                ::frameworks::native::simd::backward(::frameworks::native::simd::Function::Tanh, &input[offset..], &dx[offset..], chunk)
+                            });
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native tanh_grad Forward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_tanh_pointwise_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::TanhPointwise<$t> for B {
+            fn tanh_pointwise(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                self.tanh_pointwise_plain(x)
+            }
+
+            fn tanh_pointwise_plain(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(input) = x.get_mut(self.device()).unwrap().as_mut_native() {
+                    ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), input.as_mut_slice::<$t>(), 1, |_, chunk| {
+                        ::frameworks::native::simd::forward_in_place(::frameworks::native::simd::Function::Tanh, chunk)
+                    });
+                    return Ok(());
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native Tanh Pointwise forward.")))
+            }
+
+            fn tanh_pointwise_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                self.tanh_pointwise_grad_plain(x, x_diff)
+            }
+
+            fn tanh_pointwise_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                if let Some(tanh_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(tanh_dx) = x_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                        let (tanh_data, tanh_dx) = (tanh_data.as_slice::<$t>(), tanh_dx.as_mut_slice::<$t>());
+                        ::frameworks::native::parallel::for_each_chunk_mut(self.thread_pool(), &mut tanh_dx[..tanh_data.len()], 1, |offset, chunk| {
+                            ::frameworks::native::simd::backward_in_place(::frameworks::native::simd::Function::Tanh, &tanh_data[offset..], chunk)
+                        });
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native Tanh Pointwise backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_convolution_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Convolution<$t> for B {
+            fn new_convolution_config(
+                &self,
+                src: &::co::tensor::SharedTensor<$t>,
+                dest: &::co::tensor::SharedTensor<$t>,
+                filter: &mut ::co::tensor::SharedTensor<$t>,
+                algo_fwd: ::plugin::ConvForwardAlgo,
+                algo_bwd_filter: ::plugin::ConvBackwardFilterAlgo,
+                algo_bwd_data: ::plugin::ConvBackwardDataAlgo,
+                stride: &[i32],
+                zero_padding: &[i32],
+                dilation: &[i32],
+                groups: usize
+            ) -> Result<Self::CC, ::co::error::Error> {
+                let mut config = try!(::frameworks::native::helper::ConvolutionConfig::new(src.desc(), filter.desc(), stride, zero_padding, dilation, groups));
+                try!(config.check_shapes(src.desc(), dest.desc(), filter.desc()));
+                try!(::frameworks::native::convolution::select_algos::<$t>(self.thread_pool(), &mut config, dest.desc(), algo_fwd, algo_bwd_filter, algo_bwd_data));
+                Ok(config)
+            }
+
+            fn convolution(
+                &self,
+                filter: &mut ::co::tensor::SharedTensor<$t>,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CC
+            ) -> Result<(), ::co::error::Error> {
+                match filter.add_device(self.device()) { _ => try!(filter.sync(self.device())) }
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                match workspace.add_device(self.device()) { _ => () }
+                self.convolution_plain(filter, x, result, workspace, config)
+            }
+
+            fn convolution_plain(
+                &self,
+                filter: &::co::tensor::SharedTensor<$t>,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CC
+            ) -> Result<(), ::co::error::Error> {
+                let dims = try!(config.check_shapes(x.desc(), result.desc(), filter.desc()));
+                let workspace = try!(::frameworks::native::helper::workspace_as_mut_slice::<$t>(
+                    workspace.get_mut(self.device()).unwrap(), config.algo_workspace_len(config.forward_algo, dims.1, dims.2)));
+                if let Some(weights) = filter.get(self.device()).unwrap().as_native() {
+                    if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                        if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::convolution::forward(self.thread_pool(), config, dims, weights.as_slice::<$t>(), input.as_slice::<$t>(),
+                                                                        output.as_mut_slice::<$t>(), workspace);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native convolution Forward.")))
+            }
+
+            fn convolution_grad_filter(
+                &self,
+                src_data: &mut ::co::tensor::SharedTensor<$t>,
+                dest_diff: &mut ::co::tensor::SharedTensor<$t>,
+                filter_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CC
+            ) -> Result<(), ::co::error::Error> {
+                match src_data.add_device(self.device()) { _ => try!(src_data.sync(self.device())) }
+                match dest_diff.add_device(self.device()) { _ => try!(dest_diff.sync(self.device())) }
+                match filter_diff.add_device(self.device()) { _ => () }
+                match workspace.add_device(self.device()) { _ => () }
+                self.convolution_grad_filter_plain(src_data, dest_diff, filter_diff, workspace, config)
+            }
+
+            fn convolution_grad_filter_plain(
+                &self,
+                src_data: &::co::tensor::SharedTensor<$t>,
+                dest_diff: &::co::tensor::SharedTensor<$t>,
+                filter_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CC
+            ) -> Result<(), ::co::error::Error> {
+                let dims = try!(config.check_shapes(src_data.desc(), dest_diff.desc(), filter_diff.desc()));
+                let workspace = try!(::frameworks::native::helper::workspace_as_mut_slice::<$t>(
+                    workspace.get_mut(self.device()).unwrap(), config.algo_workspace_len(config.backward_filter_algo, dims.1, dims.2)));
+                if let Some(input) = src_data.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = dest_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(weights_diff) = filter_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::convolution::backward_filter(self.thread_pool(), config, dims, input.as_slice::<$t>(), output_diff.as_slice::<$t>(),
+                                                                                weights_diff.as_mut_slice::<$t>(), workspace);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native convolution grad filter Backward.")))
+            }
+
+            fn convolution_grad_data(
+                &self,
+                filter: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CC
+            ) -> Result<(), ::co::error::Error> {
+                match filter.add_device(self.device()) { _ => try!(filter.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                match workspace.add_device(self.device()) { _ => () }
+                self.convolution_grad_data_plain(filter, x_diff, result_diff, workspace, config)
+            }
+
+            fn convolution_grad_data_plain(
+                &self,
+                filter: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CC
+            ) -> Result<(), ::co::error::Error> {
+                let dims = try!(config.check_shapes(result_diff.desc(), x_diff.desc(), filter.desc()));
+                let workspace = try!(::frameworks::native::helper::workspace_as_mut_slice::<$t>(
+                    workspace.get_mut(self.device()).unwrap(), config.algo_workspace_len(config.backward_data_algo, dims.1, dims.2)));
+                if let Some(weights) = filter.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::convolution::backward_data(self.thread_pool(), config, dims, weights.as_slice::<$t>(), output_diff.as_slice::<$t>(),
+                                                                              input_diff.as_mut_slice::<$t>(), workspace);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native convolution grad data Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_transposed_convolution_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::TransposedConvolution<$t> for B {
+            type CTC = ::frameworks::native::helper::TransposedConvolutionConfig;
+
+            fn transposed_convolution_output_shape(
+                &self,
+                src_shape: &[usize],
+                filter_shape: &[usize],
+                stride: &[i32],
+                zero_padding: &[i32],
+                output_padding: &[i32]
+            ) -> Result<Vec<usize>, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::TransposedConvolutionConfig::output_shape(src_shape, filter_shape, stride, zero_padding, output_padding)))
+            }
+
+            fn new_transposed_convolution_config(
+                &self,
+                src: &::co::tensor::SharedTensor<$t>,
+                dest: &::co::tensor::SharedTensor<$t>,
+                filter: &mut ::co::tensor::SharedTensor<$t>,
+                algo_fwd: ::plugin::ConvBackwardDataAlgo,
+                algo_bwd_filter: ::plugin::ConvBackwardFilterAlgo,
+                algo_bwd_data: ::plugin::ConvForwardAlgo,
+                stride: &[i32],
+                zero_padding: &[i32],
+                output_padding: &[i32]
+            ) -> Result<Self::CTC, ::co::error::Error> {
+                let mut config = try!(::frameworks::native::helper::TransposedConvolutionConfig::new(src.desc(), filter.desc(), stride, zero_padding, output_padding));
+                try!(config.check_shapes(src.desc(), dest.desc(), filter.desc()));
+                try!(::frameworks::native::convolution::select_algos::<$t>(self.thread_pool(), &mut config.convolution, src.desc(), algo_bwd_data, algo_bwd_filter, algo_fwd));
+                Ok(config)
+            }
+
+            fn transposed_convolution(
+                &self,
+                filter: &mut ::co::tensor::SharedTensor<$t>,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CTC
+            ) -> Result<(), ::co::error::Error> {
+                match filter.add_device(self.device()) { _ => try!(filter.sync(self.device())) }
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                match workspace.add_device(self.device()) { _ => () }
+                self.transposed_convolution_plain(filter, x, result, workspace, config)
+            }
+
+            fn transposed_convolution_plain(
+                &self,
+                filter: &::co::tensor::SharedTensor<$t>,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CTC
+            ) -> Result<(), ::co::error::Error> {
+                let dims = try!(config.check_shapes(x.desc(), result.desc(), filter.desc()));
+                let conv = &config.convolution;
+                let workspace = try!(::frameworks::native::helper::workspace_as_mut_slice::<$t>(
+                    workspace.get_mut(self.device()).unwrap(), conv.algo_workspace_len(conv.backward_data_algo, dims.1, dims.2)));
+                if let Some(weights) = filter.get(self.device()).unwrap().as_native() {
+                    if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                        if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::convolution::backward_data(self.thread_pool(), conv, dims, weights.as_slice::<$t>(), input.as_slice::<$t>(),
+                                                                              output.as_mut_slice::<$t>(), workspace);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native transposed convolution Forward.")))
+            }
+
+            fn transposed_convolution_grad_filter(
+                &self,
+                src_data: &mut ::co::tensor::SharedTensor<$t>,
+                dest_diff: &mut ::co::tensor::SharedTensor<$t>,
+                filter_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CTC
+            ) -> Result<(), ::co::error::Error> {
+                match src_data.add_device(self.device()) { _ => try!(src_data.sync(self.device())) }
+                match dest_diff.add_device(self.device()) { _ => try!(dest_diff.sync(self.device())) }
+                match filter_diff.add_device(self.device()) { _ => () }
+                match workspace.add_device(self.device()) { _ => () }
+                self.transposed_convolution_grad_filter_plain(src_data, dest_diff, filter_diff, workspace, config)
+            }
+
+            fn transposed_convolution_grad_filter_plain(
+                &self,
+                src_data: &::co::tensor::SharedTensor<$t>,
+                dest_diff: &::co::tensor::SharedTensor<$t>,
+                filter_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CTC
+            ) -> Result<(), ::co::error::Error> {
+                let dims = try!(config.check_shapes(src_data.desc(), dest_diff.desc(), filter_diff.desc()));
+                let conv = &config.convolution;
+                let workspace = try!(::frameworks::native::helper::workspace_as_mut_slice::<$t>(
+                    workspace.get_mut(self.device()).unwrap(), conv.algo_workspace_len(conv.backward_filter_algo, dims.1, dims.2)));
+                if let Some(input) = src_data.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = dest_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(weights_diff) = filter_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            // the output gradient is the input and the input the output gradient of the convolution.
+                            ::frameworks::native::convolution::backward_filter(self.thread_pool(), conv, dims, output_diff.as_slice::<$t>(), input.as_slice::<$t>(),
+                                                                                weights_diff.as_mut_slice::<$t>(), workspace);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native transposed convolution grad filter Backward.")))
+            }
+
+            fn transposed_convolution_grad_data(
+                &self,
+                filter: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CTC
+            ) -> Result<(), ::co::error::Error> {
+                match filter.add_device(self.device()) { _ => try!(filter.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                match workspace.add_device(self.device()) { _ => () }
+                self.transposed_convolution_grad_data_plain(filter, x_diff, result_diff, workspace, config)
+            }
+
+            fn transposed_convolution_grad_data_plain(
+                &self,
+                filter: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                workspace: &mut ::co::tensor::SharedTensor<u8>,
+                config: &Self::CTC
+            ) -> Result<(), ::co::error::Error> {
+                let dims = try!(config.check_shapes(result_diff.desc(), x_diff.desc(), filter.desc()));
+                let conv = &config.convolution;
+                let workspace = try!(::frameworks::native::helper::workspace_as_mut_slice::<$t>(
+                    workspace.get_mut(self.device()).unwrap(), conv.algo_workspace_len(conv.forward_algo, dims.1, dims.2)));
+                if let Some(weights) = filter.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::convolution::forward(self.thread_pool(), conv, dims, weights.as_slice::<$t>(), output_diff.as_slice::<$t>(),
+                                                                        input_diff.as_mut_slice::<$t>(), workspace);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native transposed convolution grad data Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_softmax_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Softmax<$t> for B {
+            fn softmax(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.softmax_plain(x, result)
+            }
+            fn softmax_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                let layout = ::frameworks::native::helper::softmax_layout(x.desc());
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native softmax requires input and output of the same size.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::softmax(self.thread_pool(), input.as_slice::<$t>(), output.as_mut_slice::<$t>(), layout);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(
+                    PluginError::Operation("Unable to execute Native softmax Forward.")))
+            }
+            fn softmax_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.softmax_grad_plain(x, x_diff, result_diff)
+            }
+            fn softmax_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                let layout = ::frameworks::native::helper::softmax_layout(x.desc());
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native softmax requires inputs and output of the same size.")));
+                }
+                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(output_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::softmax_grad(self.thread_pool(), sig_data.as_slice::<$t>(), sig_dx.as_slice::<$t>(),
+                                                                        output_diff.as_mut_slice::<$t>(), layout);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(
+                        PluginError::Operation("Unable to execute Native softmax Backward.")))
+            }
+            fn softmax_axis(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.softmax_axis_plain(x, result, axis)
+            }
+            fn softmax_axis_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let layout = try!(::frameworks::native::helper::softmax_axis_layout(x.desc(), axis));
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native softmax requires input and output of the same size.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::softmax(self.thread_pool(), input.as_slice::<$t>(), output.as_mut_slice::<$t>(), layout);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(
+                    PluginError::Operation("Unable to execute Native softmax Forward.")))
+            }
+            fn softmax_axis_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.softmax_axis_grad_plain(x, x_diff, result_diff, axis)
+            }
+            fn softmax_axis_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let layout = try!(::frameworks::native::helper::softmax_axis_layout(x.desc(), axis));
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native softmax requires inputs and output of the same size.")));
+                }
+                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(output_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::softmax_grad(self.thread_pool(), sig_data.as_slice::<$t>(), sig_dx.as_slice::<$t>(),
+                                                                        output_diff.as_mut_slice::<$t>(), layout);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(
+                        PluginError::Operation("Unable to execute Native softmax Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_log_softmax_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::LogSoftmax<$t> for B {
+            fn log_softmax(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.log_softmax_plain(x, result)
+            }
+            fn log_softmax_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                let layout = ::frameworks::native::helper::softmax_layout(x.desc());
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native logarithmic softmax requires input and output of the same size.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::log_softmax(self.thread_pool(), input.as_slice::<$t>(), output.as_mut_slice::<$t>(), layout);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(
+                    PluginError::Operation("Unable to execute Native logarithmic softmax Forward.")))
+            }
+            fn log_softmax_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.log_softmax_grad_plain(x, x_diff, result_diff)
+            }
+            fn log_softmax_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                let layout = ::frameworks::native::helper::softmax_layout(x.desc());
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native logarithmic softmax requires inputs and output of the same size.")));
+                }
+                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(output_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::log_softmax_grad(self.thread_pool(), sig_data.as_slice::<$t>(), sig_dx.as_slice::<$t>(),
+                                                                        output_diff.as_mut_slice::<$t>(), layout);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(
+                        PluginError::Operation("Unable to execute Native logarithmic softmax Backward.")))
+            }
+            fn log_softmax_axis(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.log_softmax_axis_plain(x, result, axis)
+            }
+            fn log_softmax_axis_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let layout = try!(::frameworks::native::helper::softmax_axis_layout(x.desc(), axis));
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native logarithmic softmax requires input and output of the same size.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::log_softmax(self.thread_pool(), input.as_slice::<$t>(), output.as_mut_slice::<$t>(), layout);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(
+                    PluginError::Operation("Unable to execute Native logarithmic softmax Forward.")))
+            }
+            fn log_softmax_axis_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.log_softmax_axis_grad_plain(x, x_diff, result_diff, axis)
+            }
+            fn log_softmax_axis_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                axis: usize
+            ) -> Result<(), ::co::error::Error> {
+                let layout = try!(::frameworks::native::helper::softmax_axis_layout(x.desc(), axis));
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native logarithmic softmax requires inputs and output of the same size.")));
+                }
+                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(output_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::log_softmax_grad(self.thread_pool(), sig_data.as_slice::<$t>(), sig_dx.as_slice::<$t>(),
+                                                                        output_diff.as_mut_slice::<$t>(), layout);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(
+                        PluginError::Operation("Unable to execute Native logarithmic softmax Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_lrn_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::LRN<$t> for B {
+            fn new_lrn_config(
+                &self,
+                n: u32,
+                alpha: f64,
+                beta: f64,
+                k: f64
+            ) -> Result<Self::CLRN, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::NormalizationConfig::new(n, alpha, beta, k)))
+            }
+
+            fn lrn(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLRN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.lrn_plain(x, result, config)
+            }
+
+            fn lrn_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLRN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native LRN requires input and output of the same size.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::lrn(self.thread_pool(), input.as_slice::<$t>(), x.desc(), output.as_mut_slice::<$t>(), config);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native LRN Forward.")))
+            }
+
+            fn lrn_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLRN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.lrn_grad_plain(x, x_diff, result, result_diff, config)
+            }
+
+            fn lrn_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLRN
+            ) -> Result<(), ::co::error::Error> {
+                // `x` and `x_diff` belong to the LRN output, `result` and `result_diff` to the LRN input.
+                // The output is recomputed from `result`, so `x` only has to match in size.
+                let size = result.desc().size();
+                if x.desc().size() != size || x_diff.desc().size() != size || result_diff.desc().size() != size {
+                    return Err(Error::Plugin(PluginError::Operation("Native LRN requires inputs and outputs of the same size.")));
+                }
+                if let Some(input) = result.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::lrn_grad(self.thread_pool(), input.as_slice::<$t>(), result.desc(), input_diff.as_mut_slice::<$t>(),
+                                                                   output_diff.as_slice::<$t>(), config);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native LRN Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_pooling_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Pooling<$t> for B {
+            fn new_pooling_config(
+                &self,
+                window: &[i32],
+                padding: &[i32],
+                stride: &[i32]
+            ) -> Result<Self::CPOOL, ::co::error::Error> {
+                ::plugin::Pooling::<$t>::new_pooling_avg_config(self, window, padding, stride, ::plugin::PoolingAvgMode::ExcludePadding)
+            }
+
+            fn new_pooling_avg_config(
+                &self,
+                window: &[i32],
+                padding: &[i32],
+                stride: &[i32],
+                avg_mode: ::plugin::PoolingAvgMode
+            ) -> Result<Self::CPOOL, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::PoolingConfig::new(window, padding, stride, avg_mode)))
+            }
+
+            fn pooling_max(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.pooling_max_plain(x, result, config)
+            }
+
+            fn pooling_max_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                try!(config.check_shapes(x.desc(), result.desc()));
+                let result_dims = result.desc().clone();
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::pooling_max(self.thread_pool(), input.as_slice::<$t>(), x.desc(),
+                                                                  output.as_mut_slice::<$t>(), &result_dims, config);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native pooling Forward.")))
+            }
+
+            fn pooling_max_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.pooling_max_grad_plain(x, x_diff, result, result_diff, config)
+            }
+
+            fn pooling_max_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                // `x` and `x_diff` belong to the pooled output, `result` and `result_diff` to the pooled input.
+                try!(config.check_shapes(result.desc(), x_diff.desc()));
+                if let Some(input) = result.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::pooling_max_grad(self.thread_pool(), input.as_slice::<$t>(), result.desc(), input_diff.as_mut_slice::<$t>(),
+                                                                           output_diff.as_slice::<$t>(), x_diff.desc(), config);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native pooling Backward.")))
+            }
+
+            fn pooling_avg(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.pooling_avg_plain(x, result, config)
+            }
+
+            fn pooling_avg_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                try!(config.check_shapes(x.desc(), result.desc()));
+                let result_dims = result.desc().clone();
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::pooling_avg(self.thread_pool(), input.as_slice::<$t>(), x.desc(),
+                                                                  output.as_mut_slice::<$t>(), &result_dims, config);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native average pooling Forward.")))
+            }
+
+            fn pooling_avg_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.pooling_avg_grad_plain(x, x_diff, result, result_diff, config)
+            }
+
+            fn pooling_avg_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                result: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CPOOL
+            ) -> Result<(), ::co::error::Error> {
+                // `x` and `x_diff` belong to the pooled output, `result` and `result_diff` to the pooled input.
+                try!(config.check_shapes(result.desc(), x_diff.desc()));
+                let input_dims = result.desc().clone();
+                if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                    if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::pooling_avg_grad(self.thread_pool(), &input_dims, input_diff.as_mut_slice::<$t>(),
+                                                                       output_diff.as_slice::<$t>(), x_diff.desc(), config);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native average pooling Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_batch_normalization_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::BatchNormalization<$t> for B {
+            type CBN = ::frameworks::native::helper::BatchNormalizationConfig;
+
+            fn new_batch_normalization_config(
+                &self,
+                mode: ::plugin::BatchNormMode,
+                epsilon: f64,
+                momentum: f64
+            ) -> Result<Self::CBN, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::BatchNormalizationConfig::new(mode, epsilon, momentum)))
+            }
+
+            fn batch_normalization_training(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &mut ::co::tensor::SharedTensor<$t>,
+                beta: &mut ::co::tensor::SharedTensor<$t>,
+                running_mean: &mut ::co::tensor::SharedTensor<$t>,
+                running_variance: &mut ::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CBN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match gamma.add_device(self.device()) { _ => try!(gamma.sync(self.device())) }
+                match beta.add_device(self.device()) { _ => try!(beta.sync(self.device())) }
+                match running_mean.add_device(self.device()) { _ => try!(running_mean.sync(self.device())) }
+                match running_variance.add_device(self.device()) { _ => try!(running_variance.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                match saved_mean.add_device(self.device()) { _ => () }
+                match saved_inv_variance.add_device(self.device()) { _ => () }
+                self.batch_normalization_training_plain(x, result, gamma, beta, running_mean, running_variance, saved_mean, saved_inv_variance, config)
+            }
+
+            fn batch_normalization_training_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &::co::tensor::SharedTensor<$t>,
+                beta: &::co::tensor::SharedTensor<$t>,
+                running_mean: &mut ::co::tensor::SharedTensor<$t>,
+                running_variance: &mut ::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CBN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native batch normalization requires input and output of the same size.")));
+                }
+                try!(config.check_params(x.desc(), &[gamma.desc(), beta.desc(), running_mean.desc(), running_variance.desc(),
+                                                     saved_mean.desc(), saved_inv_variance.desc()]));
+                if let (Some(input), Some(gamma), Some(beta)) = (x.get(self.device()).unwrap().as_native(),
+                                                                  gamma.get(self.device()).unwrap().as_native(),
+                                                                  beta.get(self.device()).unwrap().as_native()) {
+                    if let (Some(output), Some(running_mean), Some(running_variance)) = (result.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                         running_mean.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                         running_variance.get_mut(self.device()).unwrap().as_mut_native()) {
+                        if let (Some(saved_mean), Some(saved_inv_variance)) = (saved_mean.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                               saved_inv_variance.get_mut(self.device()).unwrap().as_mut_native()) {
+                            ::frameworks::native::helper::batch_norm_training(self.thread_pool(), input.as_slice::<$t>(), x.desc(), output.as_mut_slice::<$t>(),
+                                                                              gamma.as_slice::<$t>(), beta.as_slice::<$t>(),
+                                                                              running_mean.as_mut_slice::<$t>(), running_variance.as_mut_slice::<$t>(),
+                                                                              saved_mean.as_mut_slice::<$t>(), saved_inv_variance.as_mut_slice::<$t>(), config);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native batch normalization training Forward.")))
+            }
+
+            fn batch_normalization_inference(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &mut ::co::tensor::SharedTensor<$t>,
+                beta: &mut ::co::tensor::SharedTensor<$t>,
+                running_mean: &mut ::co::tensor::SharedTensor<$t>,
+                running_variance: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CBN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match gamma.add_device(self.device()) { _ => try!(gamma.sync(self.device())) }
+                match beta.add_device(self.device()) { _ => try!(beta.sync(self.device())) }
+                match running_mean.add_device(self.device()) { _ => try!(running_mean.sync(self.device())) }
+                match running_variance.add_device(self.device()) { _ => try!(running_variance.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.batch_normalization_inference_plain(x, result, gamma, beta, running_mean, running_variance, config)
+            }
+
+            fn batch_normalization_inference_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &::co::tensor::SharedTensor<$t>,
+                beta: &::co::tensor::SharedTensor<$t>,
+                running_mean: &::co::tensor::SharedTensor<$t>,
+                running_variance: &::co::tensor::SharedTensor<$t>,
+                config: &Self::CBN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native batch normalization requires input and output of the same size.")));
+                }
+                try!(config.check_params(x.desc(), &[gamma.desc(), beta.desc(), running_mean.desc(), running_variance.desc()]));
+                if let (Some(input), Some(gamma), Some(beta)) = (x.get(self.device()).unwrap().as_native(),
+                                                                  gamma.get(self.device()).unwrap().as_native(),
+                                                                  beta.get(self.device()).unwrap().as_native()) {
+                    if let (Some(running_mean), Some(running_variance)) = (running_mean.get(self.device()).unwrap().as_native(),
+                                                                           running_variance.get(self.device()).unwrap().as_native()) {
+                        if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::batch_norm_inference(self.thread_pool(), input.as_slice::<$t>(), x.desc(), output.as_mut_slice::<$t>(),
+                                                                               gamma.as_slice::<$t>(), beta.as_slice::<$t>(),
+                                                                               running_mean.as_slice::<$t>(), running_variance.as_slice::<$t>(), config);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native batch normalization inference Forward.")))
+            }
+
+            fn batch_normalization_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &mut ::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma_diff: &mut ::co::tensor::SharedTensor<$t>,
+                beta_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CBN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match gamma.add_device(self.device()) { _ => try!(gamma.sync(self.device())) }
+                match saved_mean.add_device(self.device()) { _ => try!(saved_mean.sync(self.device())) }
+                match saved_inv_variance.add_device(self.device()) { _ => try!(saved_inv_variance.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                match gamma_diff.add_device(self.device()) { _ => () }
+                match beta_diff.add_device(self.device()) { _ => () }
+                self.batch_normalization_grad_plain(x, x_diff, gamma, saved_mean, saved_inv_variance, result_diff, gamma_diff, beta_diff, config)
+            }
+
+            fn batch_normalization_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                gamma: &::co::tensor::SharedTensor<$t>,
+                saved_mean: &::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma_diff: &mut ::co::tensor::SharedTensor<$t>,
+                beta_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CBN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native batch normalization requires input, output and gradients of the same size.")));
+                }
+                try!(config.check_params(x.desc(), &[gamma.desc(), saved_mean.desc(), saved_inv_variance.desc(), gamma_diff.desc(), beta_diff.desc()]));
+                if let (Some(input), Some(output_diff), Some(gamma)) = (x.get(self.device()).unwrap().as_native(),
+                                                                         x_diff.get(self.device()).unwrap().as_native(),
+                                                                         gamma.get(self.device()).unwrap().as_native()) {
+                    if let (Some(saved_mean), Some(saved_inv_variance)) = (saved_mean.get(self.device()).unwrap().as_native(),
+                                                                           saved_inv_variance.get(self.device()).unwrap().as_native()) {
+                        if let (Some(input_diff), Some(gamma_diff), Some(beta_diff)) = (result_diff.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                        gamma_diff.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                        beta_diff.get_mut(self.device()).unwrap().as_mut_native()) {
+                            ::frameworks::native::helper::batch_norm_grad(self.thread_pool(), input.as_slice::<$t>(), x.desc(), output_diff.as_slice::<$t>(), gamma.as_slice::<$t>(),
+                                                                          saved_mean.as_slice::<$t>(), saved_inv_variance.as_slice::<$t>(),
+                                                                          input_diff.as_mut_slice::<$t>(), gamma_diff.as_mut_slice::<$t>(),
+                                                                          beta_diff.as_mut_slice::<$t>(), config);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native batch normalization Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_layer_normalization_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::LayerNormalization<$t> for B {
+            type CLN = ::frameworks::native::helper::LayerNormalizationConfig;
+
+            fn new_layer_normalization_config(
+                &self,
+                normalized_dims: usize,
+                epsilon: f64
+            ) -> Result<Self::CLN, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::LayerNormalizationConfig::new(normalized_dims, epsilon)))
+            }
+
+            fn layer_normalization(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &mut ::co::tensor::SharedTensor<$t>,
+                beta: &mut ::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match gamma.add_device(self.device()) { _ => try!(gamma.sync(self.device())) }
+                match beta.add_device(self.device()) { _ => try!(beta.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                match saved_mean.add_device(self.device()) { _ => () }
+                match saved_inv_variance.add_device(self.device()) { _ => () }
+                self.layer_normalization_plain(x, result, gamma, beta, saved_mean, saved_inv_variance, config)
+            }
+
+            fn layer_normalization_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &::co::tensor::SharedTensor<$t>,
+                beta: &::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native layer normalization requires input and output of the same size.")));
+                }
+                let (groups, features) = try!(config.check_params(x.desc(), &[gamma.desc(), beta.desc()], &[saved_mean.desc(), saved_inv_variance.desc()]));
+                if let (Some(input), Some(gamma), Some(beta)) = (x.get(self.device()).unwrap().as_native(),
+                                                                  gamma.get(self.device()).unwrap().as_native(),
+                                                                  beta.get(self.device()).unwrap().as_native()) {
+                    if let (Some(output), Some(saved_mean), Some(saved_inv_variance)) = (result.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                         saved_mean.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                         saved_inv_variance.get_mut(self.device()).unwrap().as_mut_native()) {
+                        ::frameworks::native::helper::layer_norm(self.thread_pool(), input.as_slice::<$t>(), output.as_mut_slice::<$t>(), gamma.as_slice::<$t>(),
+                                                                 beta.as_slice::<$t>(), saved_mean.as_mut_slice::<$t>(),
+                                                                 saved_inv_variance.as_mut_slice::<$t>(), groups, features, config);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native layer normalization Forward.")))
+            }
+
+            fn layer_normalization_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &mut ::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma_diff: &mut ::co::tensor::SharedTensor<$t>,
+                beta_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match gamma.add_device(self.device()) { _ => try!(gamma.sync(self.device())) }
+                match saved_mean.add_device(self.device()) { _ => try!(saved_mean.sync(self.device())) }
+                match saved_inv_variance.add_device(self.device()) { _ => try!(saved_inv_variance.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                match gamma_diff.add_device(self.device()) { _ => () }
+                match beta_diff.add_device(self.device()) { _ => () }
+                self.layer_normalization_grad_plain(x, x_diff, gamma, saved_mean, saved_inv_variance, result_diff, gamma_diff, beta_diff, config)
+            }
+
+            fn layer_normalization_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                gamma: &::co::tensor::SharedTensor<$t>,
+                saved_mean: &::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma_diff: &mut ::co::tensor::SharedTensor<$t>,
+                beta_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CLN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native layer normalization requires input, output and gradients of the same size.")));
+                }
+                let (groups, features) = try!(config.check_params(x.desc(), &[gamma.desc(), gamma_diff.desc(), beta_diff.desc()],
+                                                                  &[saved_mean.desc(), saved_inv_variance.desc()]));
+                if let (Some(input), Some(output_diff), Some(gamma)) = (x.get(self.device()).unwrap().as_native(),
+                                                                         x_diff.get(self.device()).unwrap().as_native(),
+                                                                         gamma.get(self.device()).unwrap().as_native()) {
+                    if let (Some(saved_mean), Some(saved_inv_variance)) = (saved_mean.get(self.device()).unwrap().as_native(),
+                                                                           saved_inv_variance.get(self.device()).unwrap().as_native()) {
+                        if let (Some(input_diff), Some(gamma_diff), Some(beta_diff)) = (result_diff.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                        gamma_diff.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                        beta_diff.get_mut(self.device()).unwrap().as_mut_native()) {
+                            ::frameworks::native::helper::layer_norm_grad(self.thread_pool(), input.as_slice::<$t>(), output_diff.as_slice::<$t>(), gamma.as_slice::<$t>(),
+                                                                          saved_mean.as_slice::<$t>(), saved_inv_variance.as_slice::<$t>(),
+                                                                          input_diff.as_mut_slice::<$t>(), gamma_diff.as_mut_slice::<$t>(),
+                                                                          beta_diff.as_mut_slice::<$t>(), groups, features);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native layer normalization Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_group_normalization_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::GroupNormalization<$t> for B {
+            type CGN = ::frameworks::native::helper::GroupNormalizationConfig;
+
+            fn new_group_normalization_config(
+                &self,
+                groups: usize,
+                epsilon: f64
+            ) -> Result<Self::CGN, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::GroupNormalizationConfig::new(groups, epsilon)))
+            }
+
+            fn new_instance_normalization_config(
+                &self,
+                channels: usize,
+                epsilon: f64
+            ) -> Result<Self::CGN, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::GroupNormalizationConfig::new(channels, epsilon)))
+            }
+
+            fn group_normalization(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &mut ::co::tensor::SharedTensor<$t>,
+                beta: &mut ::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CGN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match gamma.add_device(self.device()) { _ => try!(gamma.sync(self.device())) }
+                match beta.add_device(self.device()) { _ => try!(beta.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                match saved_mean.add_device(self.device()) { _ => () }
+                match saved_inv_variance.add_device(self.device()) { _ => () }
+                self.group_normalization_plain(x, result, gamma, beta, saved_mean, saved_inv_variance, config)
+            }
+
+            fn group_normalization_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &::co::tensor::SharedTensor<$t>,
+                beta: &::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CGN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native group normalization requires input and output of the same size.")));
+                }
+                try!(config.check_params(x.desc(), &[gamma.desc(), beta.desc()], &[saved_mean.desc(), saved_inv_variance.desc()]));
+                if let (Some(input), Some(gamma), Some(beta)) = (x.get(self.device()).unwrap().as_native(),
+                                                                  gamma.get(self.device()).unwrap().as_native(),
+                                                                  beta.get(self.device()).unwrap().as_native()) {
+                    if let (Some(output), Some(saved_mean), Some(saved_inv_variance)) = (result.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                         saved_mean.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                         saved_inv_variance.get_mut(self.device()).unwrap().as_mut_native()) {
+                        ::frameworks::native::helper::group_norm(self.thread_pool(), input.as_slice::<$t>(), x.desc(), output.as_mut_slice::<$t>(), gamma.as_slice::<$t>(),
+                                                                 beta.as_slice::<$t>(), saved_mean.as_mut_slice::<$t>(),
+                                                                 saved_inv_variance.as_mut_slice::<$t>(), config);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native group normalization Forward.")))
+            }
+
+            fn group_normalization_grad(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                x_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma: &mut ::co::tensor::SharedTensor<$t>,
+                saved_mean: &mut ::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma_diff: &mut ::co::tensor::SharedTensor<$t>,
+                beta_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CGN
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                match gamma.add_device(self.device()) { _ => try!(gamma.sync(self.device())) }
+                match saved_mean.add_device(self.device()) { _ => try!(saved_mean.sync(self.device())) }
+                match saved_inv_variance.add_device(self.device()) { _ => try!(saved_inv_variance.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                match gamma_diff.add_device(self.device()) { _ => () }
+                match beta_diff.add_device(self.device()) { _ => () }
+                self.group_normalization_grad_plain(x, x_diff, gamma, saved_mean, saved_inv_variance, result_diff, gamma_diff, beta_diff, config)
+            }
+
+            fn group_normalization_grad_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                x_diff: &::co::tensor::SharedTensor<$t>,
+                gamma: &::co::tensor::SharedTensor<$t>,
+                saved_mean: &::co::tensor::SharedTensor<$t>,
+                saved_inv_variance: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                gamma_diff: &mut ::co::tensor::SharedTensor<$t>,
+                beta_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CGN
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != x_diff.desc().size() || x.desc().size() != result_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native group normalization requires input, output and gradients of the same size.")));
+                }
+                try!(config.check_params(x.desc(), &[gamma.desc(), gamma_diff.desc(), beta_diff.desc()],
+                                         &[saved_mean.desc(), saved_inv_variance.desc()]));
+                if let (Some(input), Some(output_diff), Some(gamma)) = (x.get(self.device()).unwrap().as_native(),
+                                                                         x_diff.get(self.device()).unwrap().as_native(),
+                                                                         gamma.get(self.device()).unwrap().as_native()) {
+                    if let (Some(saved_mean), Some(saved_inv_variance)) = (saved_mean.get(self.device()).unwrap().as_native(),
+                                                                           saved_inv_variance.get(self.device()).unwrap().as_native()) {
+                        if let (Some(input_diff), Some(gamma_diff), Some(beta_diff)) = (result_diff.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                        gamma_diff.get_mut(self.device()).unwrap().as_mut_native(),
+                                                                                        beta_diff.get_mut(self.device()).unwrap().as_mut_native()) {
+                            ::frameworks::native::helper::group_norm_grad(self.thread_pool(), input.as_slice::<$t>(), x.desc(), output_diff.as_slice::<$t>(), gamma.as_slice::<$t>(),
+                                                                          saved_mean.as_slice::<$t>(), saved_inv_variance.as_slice::<$t>(),
+                                                                          input_diff.as_mut_slice::<$t>(), gamma_diff.as_mut_slice::<$t>(),
+                                                                          beta_diff.as_mut_slice::<$t>(), config);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native group normalization Backward.")))
+            }
+        }
+    )+);
+}
+
+#[macro_export]
+macro_rules! impl_ops_dropout_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Dropout<$t> for B {
+            type CDROP = ::frameworks::native::helper::DropoutConfig;
+
+            fn new_dropout_config(
+                &self,
+                probability: f64,
+                seed: u64
+            ) -> Result<Self::CDROP, ::co::error::Error> {
+                Ok(try!(::frameworks::native::helper::DropoutConfig::new(probability, seed)))
+            }
+
+            fn dropout(
+                &self,
+                x: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                mask: &mut ::co::tensor::SharedTensor<$t>,
+                mask_index: u64,
+                config: &Self::CDROP
+            ) -> Result<(), ::co::error::Error> {
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                match mask.add_device(self.device()) { _ => () }
+                self.dropout_plain(x, result, mask, mask_index, config)
+            }
+
+            fn dropout_plain(
+                &self,
+                x: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>,
+                mask: &mut ::co::tensor::SharedTensor<$t>,
+                mask_index: u64,
+                config: &Self::CDROP
+            ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != result.desc().size() || x.desc().size() != mask.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native dropout requires input, output and mask of the same size.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(mask) = mask.get_mut(self.device()).unwrap().as_mut_native() {
+                        if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                            let mask = mask.as_mut_slice::<$t>();
+                            ::frameworks::native::helper::dropout_mask(self.thread_pool(), mask, mask_index, config);
+                            ::frameworks::native::parallel::zip_map(self.thread_pool(), input.as_slice::<$t>(), mask, output.as_mut_slice::<$t>(), |x, m| *x * *m);
+                            return Ok(());
+                        }
+                    }
                 }
-                Err(Error::Plugin(PluginError::Operation("Unable to execute Native ReLU Forward.")))
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native dropout Forward.")))
             }
 
-            fn relu_grad(
+            fn dropout_grad(
                 &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
                 x_diff: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>
+                mask: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CDROP
             ) -> Result<(), ::co::error::Error> {
-                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
                 match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
-                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                match mask.add_device(self.device()) { _ => try!(mask.sync(self.device())) }
                 match result_diff.add_device(self.device()) { _ => () }
-                self.relu_grad_plain(x, x_diff, result, result_diff)
+                self.dropout_grad_plain(x_diff, mask, result_diff, config)
             }
 
-            fn relu_grad_plain(
+            fn dropout_grad_plain(
                 &self,
-                x: &::co::tensor::SharedTensor<$t>,
                 x_diff: &::co::tensor::SharedTensor<$t>,
-                result: &::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>
+                mask: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>,
+                config: &Self::CDROP
             ) -> Result<(), ::co::error::Error> {
-                if let Some(input) = x.get(self.device()).unwrap().as_native() {
-                    if let Some(dx) = x_diff.get(self.device()).unwrap().as_native() {
-                        let res = input.as_slice::<$t>().iter()
-                        .zip(dx.as_slice::<$t>().iter())
-                        .map(|(x, dx)| ::frameworks::native::helper::relu_grad(x, dx));
-                        ::frameworks::native::helper::write_to_memory(result_diff.get_mut(self.device()).unwrap(), res);
-                        return Ok(());
+                if x_diff.desc().size() != result_diff.desc().size() || x_diff.desc().size() != mask.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native dropout requires gradients and mask of the same size.")));
+                }
+                if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                    if let Some(mask) = mask.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::parallel::zip_map(self.thread_pool(), output_diff.as_slice::<$t>(), mask.as_slice::<$t>(), input_diff.as_mut_slice::<$t>(),
+                                                                    |dy, m| *dy * *m);
+                            return Ok(());
+                        }
                     }
                 }
-                Err(Error::Plugin(PluginError::Operation("Unable to execute Native ReLU grad Forward.")))
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native dropout Backward.")))
             }
         }
-    );
+    )+);
 }
 
 #[macro_export]
-macro_rules! impl_ops_tanh_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::Tanh<$t> for $b {
-            #[inline]
-            fn tanh(
+macro_rules! impl_ops_activation_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Activation<$t> for B {
+            fn activation(
                 &self,
+                mode: ::plugin::ActivationMode,
                 x: &mut ::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
                 match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
                 match result.add_device(self.device()) { _ => () }
-                self.tanh_plain(x, result)
+                self.activation_plain(mode, x, result)
             }
 
-            fn tanh_plain(
+            fn activation_plain(
                 &self,
+                mode: ::plugin::ActivationMode,
                 x: &::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
+                if x.desc().size() != result.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native activation requires input and output of the same size.")));
+                }
                 if let Some(input) = x.get(self.device()).unwrap().as_native() {
-                    let res = input.as_slice::<$t>().iter().map(::frameworks::native::helper::tanh);
-                    ::frameworks::native::helper::write_to_memory(result.get_mut(self.device()).unwrap(), res);
-                    return Ok(());
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        try!(::frameworks::native::activation::forward(self.thread_pool(), mode, input.as_slice::<$t>(), output.as_mut_slice::<$t>()));
+                        return Ok(());
+                    }
                 }
-                Err(Error::Plugin(PluginError::Operation("Unable to execute Native tanh Forward.")))
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native activation Forward.")))
             }
 
-            fn tanh_grad(
+            fn activation_grad(
                 &self,
+                mode: ::plugin::ActivationMode,
                 x: &mut ::co::tensor::SharedTensor<$t>,
                 x_diff: &mut ::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>,
@@ -253,363 +3243,442 @@ macro_rules! impl_ops_tanh_for {
                 match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
                 match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
                 match result_diff.add_device(self.device()) { _ => () }
-                self.tanh_grad_plain(x, x_diff, result, result_diff)
+                self.activation_grad_plain(mode, x, x_diff, result, result_diff)
             }
 
-            fn tanh_grad_plain(
+            fn activation_grad_plain(
                 &self,
+                mode: ::plugin::ActivationMode,
                 x: &::co::tensor::SharedTensor<$t>,
                 x_diff: &::co::tensor::SharedTensor<$t>,
                 result: &::co::tensor::SharedTensor<$t>,
                 result_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                if let Some(input) = x.get(self.device()).unwrap().as_native() {
-                    if let Some(dx) = x_diff.get(self.device()).unwrap().as_native() {
-                        let res = input.as_slice::<$t>().iter()
-                        .zip(dx.as_slice::<$t>().iter())
-                        .map(|(x, dx)| ::frameworks::native::helper::tanh_grad(x, dx));
-                        ::frameworks::native::helper::write_to_memory(result_diff.get_mut(self.device()).unwrap(), res);
-                        return Ok(());
+                let size = x.desc().size();
+                if x_diff.desc().size() != size || result.desc().size() != size || result_diff.desc().size() != size {
+                    return Err(Error::Plugin(PluginError::Operation("Native activation gradient requires tensors of the same size.")));
+                }
+                if let Some(output) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(input) = result.get(self.device()).unwrap().as_native() {
+                            if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                                try!(::frameworks::native::activation::backward(self.thread_pool(), mode, input.as_slice::<$t>(), output.as_slice::<$t>(),
+                                                                                output_diff.as_slice::<$t>(), input_diff.as_mut_slice::<$t>()));
+                                return Ok(());
+                            }
+                        }
                     }
                 }
-                Err(Error::Plugin(PluginError::Operation("Unable to execute Native tanh_grad Forward.")))
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native activation Backward.")))
             }
-        }
-    );
-}
 
-#[macro_export]
-macro_rules! impl_ops_convolution_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::Convolution<$t> for $b {
-            fn new_convolution_config(
-                &self,
-                src: &::co::tensor::SharedTensor<$t>,
-                dest: &::co::tensor::SharedTensor<$t>,
-                filter: &mut ::co::tensor::SharedTensor<$t>,
-                stride: &[i32],
-                zero_padding: &[i32]
-            ) -> Result<Self::CC, ::co::error::Error> {
-                unimplemented!();
-                Ok(helper::ConvolutionConfig)
-            }
-            fn convolution(
+            fn activation_pointwise(
                 &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CC
+                mode: ::plugin::ActivationMode,
+                x: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                self.activation_pointwise_plain(mode, x)
             }
 
-            fn convolution_plain(
+            fn activation_pointwise_plain(
                 &self,
-                x: &::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CC
+                mode: ::plugin::ActivationMode,
+                x: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                if let Some(input) = x.get_mut(self.device()).unwrap().as_mut_native() {
+                    try!(::frameworks::native::activation::forward_in_place(self.thread_pool(), mode, input.as_mut_slice::<$t>()));
+                    return Ok(());
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native activation Pointwise forward.")))
             }
 
-            fn convolution_grad(
+            fn activation_pointwise_grad(
                 &self,
+                mode: ::plugin::ActivationMode,
                 x: &mut ::co::tensor::SharedTensor<$t>,
-                x_diff: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CC
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
+                self.activation_pointwise_grad_plain(mode, x, x_diff)
             }
 
-            fn convolution_grad_plain(
+            fn activation_pointwise_grad_plain(
                 &self,
+                mode: ::plugin::ActivationMode,
                 x: &::co::tensor::SharedTensor<$t>,
-                x_diff: &::co::tensor::SharedTensor<$t>,
-                result: &::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CC
+                x_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                if x.desc().size() != x_diff.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native activation gradient requires tensors of the same size.")));
+                }
+                if let Some(output) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = x_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                        try!(::frameworks::native::activation::backward_in_place(self.thread_pool(), mode, output.as_slice::<$t>(), output_diff.as_mut_slice::<$t>()));
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native activation Pointwise backward.")))
             }
         }
-    );
+    )+);
 }
 
 #[macro_export]
-macro_rules! impl_ops_softmax_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::Softmax<$t> for $b {
-            fn softmax(
+macro_rules! impl_ops_linear_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Linear<$t> for B {
+            fn linear(
                 &self,
+                weight: &mut ::co::tensor::SharedTensor<$t>,
+                bias: &mut ::co::tensor::SharedTensor<$t>,
                 x: &mut ::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
+                match weight.add_device(self.device()) { _ => try!(weight.sync(self.device())) }
+                match bias.add_device(self.device()) { _ => try!(bias.sync(self.device())) }
                 match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
                 match result.add_device(self.device()) { _ => () }
-                self.softmax_plain(x, result)
+                self.linear_plain(weight, bias, x, result)
             }
-            fn softmax_plain(
+
+            fn linear_plain(
                 &self,
+                weight: &::co::tensor::SharedTensor<$t>,
+                bias: &::co::tensor::SharedTensor<$t>,
                 x: &::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
+                let (batch, inputs, outputs) = try!(::frameworks::native::helper::linear_dims(x.desc(), weight.desc()));
+                if bias.desc().size() != outputs || result.desc().size() != batch * outputs {
+                    return Err(Error::Plugin(PluginError::Operation("Native linear requires a bias of [outputs] and an output of [batch, outputs].")));
+                }
                 if let Some(input) = x.get(self.device()).unwrap().as_native() {
-                    let mut exps = Vec::with_capacity(x.capacity());
-                    let mut sum : $t = 0 as $t;
-                    for exp in input.as_slice::<$t>().iter().map(|t|t.exp()) {
-                        exps.push(exp);
-                        sum += exp;
-                    }
-                    let res = exps.iter().map(|t| t / sum);
-                    ::frameworks::native::helper::write_to_memory(result.get_mut(self.device()).unwrap(), res);
-                    return Ok(());
+                    if let Some(weights) = weight.get(self.device()).unwrap().as_native() {
+                        if let Some(bias) = bias.get(self.device()).unwrap().as_native() {
+                            if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                                ::frameworks::native::helper::linear(self.thread_pool(), input.as_slice::<$t>(), weights.as_slice::<$t>(), bias.as_slice::<$t>(),
+                                                                     output.as_mut_slice::<$t>(), batch, inputs, outputs);
+                                return Ok(());
+                            }
+                        }
+                    }
                 }
-                Err(Error::Plugin(
-                    PluginError::Operation("Unable to execute Native softmax Forward.")))
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native linear Forward.")))
             }
-            fn softmax_grad(
+
+            fn linear_grad_input(
                 &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
+                weight: &mut ::co::tensor::SharedTensor<$t>,
                 x_diff: &mut ::co::tensor::SharedTensor<$t>,
                 result_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match weight.add_device(self.device()) { _ => try!(weight.sync(self.device())) }
                 match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
                 match result_diff.add_device(self.device()) { _ => () }
-                self.softmax_grad_plain(x, x_diff, result_diff)
+                self.linear_grad_input_plain(weight, x_diff, result_diff)
             }
-            fn softmax_grad_plain(
+
+            fn linear_grad_input_plain(
                 &self,
-                x: &::co::tensor::SharedTensor<$t>,
+                weight: &::co::tensor::SharedTensor<$t>,
                 x_diff: &::co::tensor::SharedTensor<$t>,
                 result_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
-                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
-                        let mut dot : $t = 0 as $t;
-                        let sig_data_slice = sig_data.as_slice::<$t>();
-                        let sig_dx_slice = sig_dx.as_slice::<$t>();
-                        for (t, dt) in sig_data_slice.iter().zip(sig_dx_slice.iter()) {
-                            dot += t * dt;
-                        }
-                        let res = sig_data_slice.iter()
-                            .zip(sig_dx_slice.iter())
-                            .map(|(t, dt)| t * (dt - dot));
-                        ::frameworks::native::helper::write_to_memory(result_diff.get_mut(self.device()).unwrap(), res);
-                        return Ok(());
+                let (batch, inputs, outputs) = try!(::frameworks::native::helper::linear_dims(result_diff.desc(), weight.desc()));
+                if x_diff.desc().size() != batch * outputs {
+                    return Err(Error::Plugin(PluginError::Operation("Native linear requires an output gradient of [batch, outputs].")));
+                }
+                if let Some(weights) = weight.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = x_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::gemm(self.thread_pool(), false, false, batch, inputs, outputs, 1 as $t, output_diff.as_slice::<$t>(),
+                                                               weights.as_slice::<$t>(), 0 as $t, input_diff.as_mut_slice::<$t>());
+                            return Ok(());
+                        }
                     }
                 }
-                Err(Error::Plugin(
-                        PluginError::Operation("Unable to execute Native softmax Backward.")))
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native linear input Backward.")))
+            }
+
+            fn linear_grad_weight(
+                &self,
+                src_data: &mut ::co::tensor::SharedTensor<$t>,
+                dest_diff: &mut ::co::tensor::SharedTensor<$t>,
+                weight_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match src_data.add_device(self.device()) { _ => try!(src_data.sync(self.device())) }
+                match dest_diff.add_device(self.device()) { _ => try!(dest_diff.sync(self.device())) }
+                match weight_diff.add_device(self.device()) { _ => () }
+                self.linear_grad_weight_plain(src_data, dest_diff, weight_diff)
+            }
 
+            fn linear_grad_weight_plain(
+                &self,
+                src_data: &::co::tensor::SharedTensor<$t>,
+                dest_diff: &::co::tensor::SharedTensor<$t>,
+                weight_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                let (batch, inputs, outputs) = try!(::frameworks::native::helper::linear_dims(src_data.desc(), weight_diff.desc()));
+                if dest_diff.desc().size() != batch * outputs {
+                    return Err(Error::Plugin(PluginError::Operation("Native linear requires an output gradient of [batch, outputs].")));
+                }
+                if let Some(input) = src_data.get(self.device()).unwrap().as_native() {
+                    if let Some(output_diff) = dest_diff.get(self.device()).unwrap().as_native() {
+                        if let Some(weights_diff) = weight_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            ::frameworks::native::helper::gemm(self.thread_pool(), true, false, outputs, inputs, batch, 1 as $t, output_diff.as_slice::<$t>(),
+                                                               input.as_slice::<$t>(), 0 as $t, weights_diff.as_mut_slice::<$t>());
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native linear weight Backward.")))
+            }
+
+            fn linear_grad_bias(
+                &self,
+                dest_diff: &mut ::co::tensor::SharedTensor<$t>,
+                bias_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                match dest_diff.add_device(self.device()) { _ => try!(dest_diff.sync(self.device())) }
+                match bias_diff.add_device(self.device()) { _ => () }
+                self.linear_grad_bias_plain(dest_diff, bias_diff)
+            }
+
+            fn linear_grad_bias_plain(
+                &self,
+                dest_diff: &::co::tensor::SharedTensor<$t>,
+                bias_diff: &mut ::co::tensor::SharedTensor<$t>
+            ) -> Result<(), ::co::error::Error> {
+                let outputs = bias_diff.desc().size();
+                if outputs == 0 || dest_diff.desc().size() % outputs != 0 {
+                    return Err(Error::Plugin(PluginError::Operation("Native linear requires an output gradient of [batch, outputs].")));
+                }
+                let batch = dest_diff.desc().size() / outputs;
+                if let Some(output_diff) = dest_diff.get(self.device()).unwrap().as_native() {
+                    if let Some(bias_diff) = bias_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::linear_grad_bias(output_diff.as_slice::<$t>(), bias_diff.as_mut_slice::<$t>(), batch, outputs);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native linear bias Backward.")))
             }
         }
-    );
+    )+);
 }
 
 #[macro_export]
-macro_rules! impl_ops_log_softmax_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::LogSoftmax<$t> for $b {
-            fn log_softmax(
+macro_rules! impl_ops_convolution_bias_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::ConvolutionBias<$t> for B {
+            fn convolution_bias(
                 &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
+                bias: &mut ::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
-                match result.add_device(self.device()) { _ => () }
-                self.log_softmax_plain(x, result)
+                match bias.add_device(self.device()) { _ => try!(bias.sync(self.device())) }
+                match result.add_device(self.device()) { _ => try!(result.sync(self.device())) }
+                self.convolution_bias_plain(bias, result)
             }
-            fn log_softmax_plain(
+
+            fn convolution_bias_plain(
                 &self,
-                x: &::co::tensor::SharedTensor<$t>,
+                bias: &::co::tensor::SharedTensor<$t>,
                 result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                if let Some(input) = x.get(self.device()).unwrap().as_native() {
-                    let mut max_input = ::std::$t::NEG_INFINITY;
-                    for &input_val in input.as_slice::<$t>() {
-                        max_input = max_input.max(input_val);
-                    }
-
-                    let mut logsum : $t = 0 as $t;
-                    for exp in input.as_slice::<$t>().iter().map(|t| (-(max_input - t)).exp()) {
-                        logsum += exp;
+                let dims = result.desc().clone();
+                try!(::frameworks::native::helper::conv_bias_check_shapes(bias.desc(), &dims));
+                if let Some(bias) = bias.get(self.device()).unwrap().as_native() {
+                    if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::conv_bias(self.thread_pool(), bias.as_slice::<$t>(), output.as_mut_slice::<$t>(), &dims);
+                        return Ok(());
                     }
-                    logsum = max_input + logsum.ln();
-
-                    let res = input.as_slice::<$t>().iter().map(|t| t - logsum);
-
-                    ::frameworks::native::helper::write_to_memory(result.get_mut(self.device()).unwrap(), res);
-                    return Ok(());
                 }
-                Err(Error::Plugin(
-                    PluginError::Operation("Unable to execute Native softmax Forward.")))
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native convolution bias Forward.")))
             }
-            fn log_softmax_grad(
+
+            fn convolution_grad_bias(
                 &self,
-                x: &mut ::co::tensor::SharedTensor<$t>,
-                x_diff: &mut ::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>
+                dest_diff: &mut ::co::tensor::SharedTensor<$t>,
+                bias_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
-                match x_diff.add_device(self.device()) { _ => try!(x_diff.sync(self.device())) }
-                match result_diff.add_device(self.device()) { _ => () }
-                self.log_softmax_grad_plain(x, x_diff, result_diff)
+                match dest_diff.add_device(self.device()) { _ => try!(dest_diff.sync(self.device())) }
+                match bias_diff.add_device(self.device()) { _ => () }
+                self.convolution_grad_bias_plain(dest_diff, bias_diff)
             }
-            fn log_softmax_grad_plain(
+
+            fn convolution_grad_bias_plain(
                 &self,
-                x: &::co::tensor::SharedTensor<$t>,
-                x_diff: &::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>
+                dest_diff: &::co::tensor::SharedTensor<$t>,
+                bias_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                if let Some(sig_data) = x.get(self.device()).unwrap().as_native() {
-                    if let Some(sig_dx) = x_diff.get(self.device()).unwrap().as_native() {
-                        let x_slice = sig_data.as_slice::<$t>();
-                        let x_diff_slice = sig_dx.as_slice::<$t>();
-                        let mut sum = 0 as $t;
-                        for &grad_val in x_diff_slice.iter() {
-                            sum += grad_val;
-                        }
-                        let res = x_slice.iter().zip(x_diff_slice.iter()).map(|(x_val, x_diff_val)| {
-                            x_diff_val - x_val.exp() * sum
-                        });
-
-                        ::frameworks::native::helper::write_to_memory(result_diff.get_mut(self.device()).unwrap(), res);
+                let dims = dest_diff.desc();
+                try!(::frameworks::native::helper::conv_bias_check_shapes(bias_diff.desc(), dims));
+                if let Some(output_diff) = dest_diff.get(self.device()).unwrap().as_native() {
+                    if let Some(bias_diff) = bias_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                        ::frameworks::native::helper::conv_bias_grad(output_diff.as_slice::<$t>(), dims, bias_diff.as_mut_slice::<$t>());
                         return Ok(());
                     }
                 }
-                Err(Error::Plugin(
-                        PluginError::Operation("Unable to execute Native softmax Backward.")))
-
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native convolution bias Backward.")))
             }
         }
-    );
+    )+);
 }
 
 #[macro_export]
-macro_rules! impl_ops_lrn_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::LRN<$t> for $b {
-            fn new_lrn_config(
-                &self,
-                n: u32,
-                alpha: f64,
-                beta: f64,
-                k: f64
-            ) -> Result<Self::CLRN, ::co::error::Error> {
-                unimplemented!();
-                Ok(::frameworks::native::helper::NormalizationConfig)
-            }
-
-            fn lrn(
+macro_rules! impl_ops_loss_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::Loss<$t> for B {
+            fn loss(
                 &self,
+                mode: ::plugin::LossMode,
+                reduction: ::plugin::LossReduction,
                 x: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CLRN
+                target: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match target.add_device(self.device()) { _ => try!(target.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.loss_plain(mode, reduction, x, target, result)
             }
 
-            fn lrn_plain(
+            fn loss_plain(
                 &self,
+                mode: ::plugin::LossMode,
+                reduction: ::plugin::LossReduction,
                 x: &::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CLRN
+                target: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(target) = target.get(self.device()).unwrap().as_native() {
+                        if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                            try!(::frameworks::native::loss::loss(mode, reduction, input.as_slice::<$t>(), x.desc(),
+                                                                  target.as_slice::<$t>(), output.as_mut_slice::<$t>()));
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native loss Forward.")))
             }
 
-            fn lrn_grad(
+            fn loss_grad(
                 &self,
+                mode: ::plugin::LossMode,
+                reduction: ::plugin::LossReduction,
                 x: &mut ::co::tensor::SharedTensor<$t>,
-                x_diff: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CLRN
+                target: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match target.add_device(self.device()) { _ => try!(target.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.loss_grad_plain(mode, reduction, x, target, result_diff)
             }
 
-            fn lrn_grad_plain(
+            fn loss_grad_plain(
                 &self,
+                mode: ::plugin::LossMode,
+                reduction: ::plugin::LossReduction,
                 x: &::co::tensor::SharedTensor<$t>,
-                x_diff: &::co::tensor::SharedTensor<$t>,
-                result: &::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CLRN
+                target: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                if result_diff.desc().size() != x.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native loss requires a gradient of the shape of the input.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(target) = target.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            try!(::frameworks::native::loss::loss_grad(self.thread_pool(), mode, reduction, input.as_slice::<$t>(), x.desc(),
+                                                                       target.as_slice::<$t>(), input_diff.as_mut_slice::<$t>()));
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native loss Backward.")))
             }
         }
-    );
+    )+);
 }
 
 #[macro_export]
-macro_rules! impl_ops_pooling_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::Pooling<$t> for $b {
-            fn new_pooling_config(
-                &self,
-                window: &[i32],
-                padding: &[i32],
-                stride: &[i32]
-            ) -> Result<Self::CPOOL, ::co::error::Error> {
-                unimplemented!();
-                Ok(::frameworks::native::helper::PoolingConfig)
-            }
-
-            fn pooling_max(
+macro_rules! impl_ops_softmax_cross_entropy_for {
+    ($($t:ident),+) => ($(
+        impl<B: ::frameworks::native::NativeThreads> ::plugin::SoftmaxCrossEntropy<$t> for B {
+            fn softmax_cross_entropy(
                 &self,
+                target_kind: ::plugin::CrossEntropyTarget,
+                reduction: ::plugin::LossReduction,
                 x: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CPOOL
+                target: &mut ::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match target.add_device(self.device()) { _ => try!(target.sync(self.device())) }
+                match result.add_device(self.device()) { _ => () }
+                self.softmax_cross_entropy_plain(target_kind, reduction, x, target, result)
             }
 
-            fn pooling_max_plain(
+            fn softmax_cross_entropy_plain(
                 &self,
+                target_kind: ::plugin::CrossEntropyTarget,
+                reduction: ::plugin::LossReduction,
                 x: &::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CPOOL
+                target: &::co::tensor::SharedTensor<$t>,
+                result: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(target) = target.get(self.device()).unwrap().as_native() {
+                        if let Some(output) = result.get_mut(self.device()).unwrap().as_mut_native() {
+                            try!(::frameworks::native::loss::softmax_cross_entropy(target_kind, reduction, input.as_slice::<$t>(), x.desc(),
+                                                                                   target.as_slice::<$t>(), output.as_mut_slice::<$t>()));
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native softmax cross-entropy Forward.")))
             }
-            #[allow(unused_variables)]
-            fn pooling_max_grad(
+
+            fn softmax_cross_entropy_grad(
                 &self,
+                target_kind: ::plugin::CrossEntropyTarget,
+                reduction: ::plugin::LossReduction,
                 x: &mut ::co::tensor::SharedTensor<$t>,
-                x_diff: &mut ::co::tensor::SharedTensor<$t>,
-                result: &mut ::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CPOOL
+                target: &mut ::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+                match target.add_device(self.device()) { _ => try!(target.sync(self.device())) }
+                match result_diff.add_device(self.device()) { _ => () }
+                self.softmax_cross_entropy_grad_plain(target_kind, reduction, x, target, result_diff)
             }
 
-            fn pooling_max_grad_plain(
+            fn softmax_cross_entropy_grad_plain(
                 &self,
+                target_kind: ::plugin::CrossEntropyTarget,
+                reduction: ::plugin::LossReduction,
                 x: &::co::tensor::SharedTensor<$t>,
-                x_diff: &::co::tensor::SharedTensor<$t>,
-                result: &::co::tensor::SharedTensor<$t>,
-                result_diff: &mut ::co::tensor::SharedTensor<$t>,
-                config: &Self::CPOOL
+                target: &::co::tensor::SharedTensor<$t>,
+                result_diff: &mut ::co::tensor::SharedTensor<$t>
             ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
+                if result_diff.desc().size() != x.desc().size() {
+                    return Err(Error::Plugin(PluginError::Operation("Native softmax cross-entropy requires a gradient of the shape of the logits.")));
+                }
+                if let Some(input) = x.get(self.device()).unwrap().as_native() {
+                    if let Some(target) = target.get(self.device()).unwrap().as_native() {
+                        if let Some(input_diff) = result_diff.get_mut(self.device()).unwrap().as_mut_native() {
+                            try!(::frameworks::native::loss::softmax_cross_entropy_grad(self.thread_pool(), target_kind, reduction, input.as_slice::<$t>(), x.desc(),
+                                                                                        target.as_slice::<$t>(), input_diff.as_mut_slice::<$t>()));
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(Error::Plugin(PluginError::Operation("Unable to execute Native softmax cross-entropy Backward.")))
             }
         }
-    );
+    )+);
 }
@@ -0,0 +1,229 @@
+//! Provides the native kernels of the loss functions of `LossMode` and of the fused softmax cross-entropy.
+//!
+//! The losses of the samples are summed in order, so the reduced loss does not depend on the
+//! number of native threads. The gradients are computed per sample across the native threads.
+
+use co::plugin::numeric_helpers::Float;
+use co::plugin::Error as PluginError;
+use plugin::{CrossEntropyTarget, LossMode, LossReduction};
+use super::helper;
+use super::parallel::{self, ThreadPool};
+
+/// The lower bound of the logarithms of the binary cross-entropy.
+const MIN_LOG: f64 = -100.0;
+/// The lower bound of the denominator of the binary cross-entropy gradient.
+const MIN_VARIANCE: f64 = 1e-12;
+
+/// Returns the `(batch, classes)` of an input of `dims`.
+fn layout(dims: &[usize]) -> (usize, usize) {
+    match dims.split_first() {
+        Some((batch, rest)) => (*batch, rest.iter().fold(1, |acc, d| acc * d)),
+        None => (1, 1),
+    }
+}
+
+/// Returns whether `mode` takes one class label per sample as target.
+fn takes_labels(mode: LossMode) -> bool {
+    match mode {
+        LossMode::NegativeLogLikelihood | LossMode::Hinge(_) => true,
+        LossMode::MeanSquaredError | LossMode::BinaryCrossEntropy => false,
+    }
+}
+
+/// Checks that an input of `dims` is not empty and returns its `(batch, classes)`.
+fn check_layout(dims: &[usize]) -> Result<(usize, usize), PluginError> {
+    let (batch, classes) = layout(dims);
+    if batch == 0 || classes == 0 {
+        return Err(PluginError::Operation("Native loss requires a non-empty input."));
+    }
+    Ok((batch, classes))
+}
+
+/// Checks that `target` holds one integral class label below `classes` per sample and returns the labels.
+fn class_labels<T: Float>(target: &[T], batch: usize, classes: usize) -> Result<Vec<usize>, PluginError> {
+    if target.len() != batch {
+        return Err(PluginError::Operation("Native loss requires one class label per sample."));
+    }
+    target.iter().map(|label| {
+        match label.to_usize() {
+            Some(class) if class < classes && T::from(class).unwrap() == *label => Ok(class),
+            _ => Err(PluginError::Operation("Native loss requires integral class labels below the number of classes.")),
+        }
+    }).collect()
+}
+
+/// Checks that `target` has one element per element of the input.
+fn check_values<T>(target: &[T], batch: usize, classes: usize) -> Result<(), PluginError> {
+    if target.len() != batch * classes {
+        return Err(PluginError::Operation("Native loss requires a target of the shape of the input."));
+    }
+    Ok(())
+}
+
+/// Checks that an output of `len` elements holds the `reduction` of `batch` sample losses.
+fn check_result(reduction: LossReduction, batch: usize, len: usize) -> Result<(), PluginError> {
+    match reduction {
+        LossReduction::None if len != batch => Err(PluginError::Operation("Native loss without reduction requires one output per sample.")),
+        LossReduction::Mean | LossReduction::Sum if len != 1 => Err(PluginError::Operation("Native reduced loss requires a single element output.")),
+        _ => Ok(()),
+    }
+}
+
+/// Checks `mode` and the target against an input of `dims` and returns the class labels, if `mode` takes them.
+fn check<T: Float>(mode: LossMode, dims: &[usize], target: &[T]) -> Result<Vec<usize>, PluginError> {
+    let (batch, classes) = try!(check_layout(dims));
+    if let LossMode::Hinge(margin) = mode {
+        if !margin.is_finite() {
+            return Err(PluginError::Operation("Native hinge loss requires a finite margin."));
+        }
+    }
+    if takes_labels(mode) {
+        class_labels(target, batch, classes)
+    } else {
+        check_values(target, batch, classes).map(|_| vec![])
+    }
+}
+
+/// Returns the factor that turns the sum of the losses of `elements` samples or elements into their `reduction`.
+fn scale<T: Float>(reduction: LossReduction, elements: usize) -> T {
+    match reduction {
+        LossReduction::Mean => T::one() / T::from(elements).unwrap(),
+        LossReduction::Sum | LossReduction::None => T::one(),
+    }
+}
+
+/// Writes the sample losses `sample_loss` of `batch` samples, reduced by `reduction`, into `dest`.
+fn reduce<T: Float, F: Fn(usize) -> T>(reduction: LossReduction, batch: usize, scale: T, dest: &mut [T], sample_loss: F) {
+    match reduction {
+        LossReduction::None => {
+            for (sample, value) in dest[..batch].iter_mut().enumerate() {
+                *value = sample_loss(sample);
+            }
+        },
+        LossReduction::Mean | LossReduction::Sum => {
+            dest[0] = (0..batch).fold(T::zero(), |acc, sample| acc + sample_loss(sample)) * scale;
+        },
+    }
+}
+
+/// Writes the `mode` loss of the `[batch, classes]` input `src` against `target`, reduced by `reduction`, into `dest`.
+pub fn loss<T: Float>(mode: LossMode, reduction: LossReduction, src: &[T], dims: &[usize], target: &[T], dest: &mut [T]) -> Result<(), PluginError> {
+    let labels = try!(check(mode, dims, target));
+    let (batch, classes) = layout(dims);
+    try!(check_result(reduction, batch, dest.len()));
+    let scale = scale(reduction, if takes_labels(mode) { batch } else { batch * classes });
+    let min_log = T::from(MIN_LOG).unwrap();
+    reduce(reduction, batch, scale, dest, |sample| {
+        let scores = &src[sample * classes..(sample + 1) * classes];
+        match mode {
+            LossMode::NegativeLogLikelihood => -scores[labels[sample]],
+            LossMode::MeanSquaredError => {
+                scores.iter().zip(target[sample * classes..].iter()).fold(T::zero(), |acc, (x, t)| acc + (*x - *t) * (*x - *t))
+            },
+            LossMode::BinaryCrossEntropy => {
+                scores.iter().zip(target[sample * classes..].iter()).fold(T::zero(), |acc, (x, t)| {
+                    acc - (*t * x.ln().max(min_log) + (T::one() - *t) * (T::one() - *x).ln().max(min_log))
+                })
+            },
+            LossMode::Hinge(margin) => {
+                let (margin, label) = (T::from(margin).unwrap(), labels[sample]);
+                scores.iter().enumerate().filter(|&(class, _)| class != label)
+                      .fold(T::zero(), |acc, (_, score)| acc + (margin - scores[label] + *score).max(T::zero()))
+            },
+        }
+    });
+    Ok(())
+}
+
+/// Writes the gradient of the `mode` loss of the `[batch, classes]` input `src` against `target`, reduced by `reduction`, into `src_diff`.
+pub fn loss_grad<T: Float + Send + Sync>(pool: &ThreadPool, mode: LossMode, reduction: LossReduction, src: &[T], dims: &[usize], target: &[T],
+                                         src_diff: &mut [T]) -> Result<(), PluginError> {
+    let labels = try!(check(mode, dims, target));
+    let (batch, classes) = layout(dims);
+    let scale = scale::<T>(reduction, if takes_labels(mode) { batch } else { batch * classes });
+    let labels = &labels;
+    parallel::for_each_chunk_mut(pool, &mut src_diff[..batch * classes], classes, |offset, chunk| {
+        for (index, diff) in chunk.iter_mut().enumerate() {
+            let i = offset + index;
+            let (x, class) = (src[i], i % classes);
+            *diff = match mode {
+                LossMode::NegativeLogLikelihood => if labels[i / classes] == class { -scale } else { T::zero() },
+                LossMode::MeanSquaredError => (x - target[i]) * T::from(2).unwrap() * scale,
+                LossMode::BinaryCrossEntropy => {
+                    (x - target[i]) / (x * (T::one() - x)).max(T::from(MIN_VARIANCE).unwrap()) * scale
+                },
+                LossMode::Hinge(_) => T::zero(),
+            };
+        }
+        if let LossMode::Hinge(margin) = mode {
+            let margin = T::from(margin).unwrap();
+            for (sample, diff) in chunk.chunks_mut(classes).enumerate() {
+                let first = offset + sample * classes;
+                let label = labels[first / classes];
+                let scores = &src[first..first + classes];
+                for class in (0..classes).filter(|&class| class != label) {
+                    if margin - scores[label] + scores[class] > T::zero() {
+                        diff[class] = diff[class] + scale;
+                        diff[label] = diff[label] - scale;
+                    }
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Checks the target of a softmax cross-entropy against logits of `dims` and returns the class labels, if the target holds them.
+fn check_cross_entropy<T: Float>(target_kind: CrossEntropyTarget, dims: &[usize], target: &[T]) -> Result<Vec<usize>, PluginError> {
+    let (batch, classes) = try!(check_layout(dims));
+    match target_kind {
+        CrossEntropyTarget::ClassIndices => class_labels(target, batch, classes),
+        CrossEntropyTarget::Probabilities => check_values(target, batch, classes).map(|_| vec![]),
+    }
+}
+
+/// Writes the cross-entropy of the softmax of the `[batch, classes]` logits `src` against `target`, reduced by `reduction`, into `dest`.
+///
+/// The loss of a sample is `log_sum_exp(x) - x[label]`, or `sum(target * (log_sum_exp(x) - x))` for probability targets.
+pub fn softmax_cross_entropy<T: Float>(target_kind: CrossEntropyTarget, reduction: LossReduction, src: &[T], dims: &[usize],
+                                       target: &[T], dest: &mut [T]) -> Result<(), PluginError> {
+    let labels = try!(check_cross_entropy(target_kind, dims, target));
+    let (batch, classes) = layout(dims);
+    try!(check_result(reduction, batch, dest.len()));
+    reduce(reduction, batch, scale(reduction, batch), dest, |sample| {
+        let logits = &src[sample * classes..(sample + 1) * classes];
+        let logsum = helper::log_sum_exp(classes, |k| logits[k]);
+        match target_kind {
+            CrossEntropyTarget::ClassIndices => logsum - logits[labels[sample]],
+            CrossEntropyTarget::Probabilities => {
+                logits.iter().zip(target[sample * classes..].iter()).fold(T::zero(), |acc, (x, t)| acc + *t * (logsum - *x))
+            },
+        }
+    });
+    Ok(())
+}
+
+/// Writes the gradient `softmax(x) - target` of the softmax cross-entropy of the `[batch, classes]` logits `src`,
+/// scaled by `reduction`, into `src_diff`.
+pub fn softmax_cross_entropy_grad<T: Float + Send + Sync>(pool: &ThreadPool, target_kind: CrossEntropyTarget, reduction: LossReduction, src: &[T], dims: &[usize],
+                                                          target: &[T], src_diff: &mut [T]) -> Result<(), PluginError> {
+    let labels = try!(check_cross_entropy(target_kind, dims, target));
+    let (batch, classes) = layout(dims);
+    let scale = scale::<T>(reduction, batch);
+    let labels = &labels;
+    parallel::for_each_chunk_mut(pool, &mut src_diff[..batch * classes], classes, |offset, chunk| {
+        for (sample, diff) in chunk.chunks_mut(classes).enumerate() {
+            let first = offset + sample * classes;
+            let logits = &src[first..first + classes];
+            let logsum = helper::log_sum_exp(classes, |k| logits[k]);
+            for (class, value) in diff.iter_mut().enumerate() {
+                let expected = match target_kind {
+                    CrossEntropyTarget::ClassIndices => if labels[first / classes] == class { T::one() } else { T::zero() },
+                    CrossEntropyTarget::Probabilities => target[first + class],
+                };
+                *value = ((logits[class] - logsum).exp() - expected) * scale;
+            }
+        }
+    });
+    Ok(())
+}
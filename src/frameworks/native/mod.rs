@@ -11,43 +11,61 @@ use co::plugin::Error as PluginError;
 
 #[macro_use]
 pub mod helper;
+pub mod activation;
+pub mod convolution;
+pub mod loss;
+pub mod parallel;
+pub mod simd;
+
+pub use self::parallel::{NativeThreads, ThreadedBackend};
 
 impl_oconf_for_cc!(f32, f64);
+impl_oconf_for_ctc!(f32, f64);
 impl_oconf_for_clrn!(f32, f64);
 impl_oconf_for_pooling!(f32, f64);
+impl_oconf_for_batch_normalization!(f32, f64);
+impl_oconf_for_layer_normalization!(f32, f64);
+impl_oconf_for_group_normalization!(f32, f64);
+impl_oconf_for_dropout!(f32, f64);
 
-impl NN<f32> for Backend<Native> {
+impl<B: NativeThreads> NN<f32> for B {
     type CC = helper::ConvolutionConfig;
     type CLRN = helper::NormalizationConfig;
     type CPOOL = helper::PoolingConfig;
 
     fn init_nn() { }
-    fn device(&self) -> &DeviceType { self.device() }
+    fn device(&self) -> &DeviceType { NativeThreads::device(self) }
 }
 
-impl_ops_sigmoid_for!(f32, Backend<Native>);
-impl_ops_relu_for!(f32, Backend<Native>);
-impl_ops_tanh_for!(f32, Backend<Native>);
-// impl_ops_convolution_for!(f32, Backend<Native>);
- impl_ops_softmax_for!(f32, Backend<Native>);
- impl_ops_log_softmax_for!(f32, Backend<Native>);
-// impl_ops_lrn_for!(f32, Backend<Native>);
-// impl_ops_pooling_for!(f32, Backend<Native>);
-
-impl NN<f64> for Backend<Native> {
+impl<B: NativeThreads> NN<f64> for B {
     type CC = helper::ConvolutionConfig;
     type CLRN = helper::NormalizationConfig;
     type CPOOL = helper::PoolingConfig;
 
     fn init_nn() { }
-    fn device(&self) -> &DeviceType { self.device() }
+    fn device(&self) -> &DeviceType { NativeThreads::device(self) }
 }
 
-impl_ops_sigmoid_for!(f64, Backend<Native>);
-impl_ops_relu_for!(f64, Backend<Native>);
-impl_ops_tanh_for!(f64, Backend<Native>);
-// impl_ops_convolution_for!(f64, Backend<Native>);
- impl_ops_softmax_for!(f64, Backend<Native>);
- impl_ops_log_softmax_for!(f64, Backend<Native>);
-// impl_ops_lrn_for!(f64, Backend<Native>);
-// impl_ops_pooling_for!(f64, Backend<Native>);
+impl_ops_sigmoid_for!(f32, f64);
+impl_ops_sigmoid_pointwise_for!(f32, f64);
+impl_ops_relu_for!(f32, f64);
+impl_ops_relu_pointwise_for!(f32, f64);
+impl_ops_clipped_relu_for!(f32, f64);
+impl_ops_clipped_relu_pointwise_for!(f32, f64);
+impl_ops_tanh_for!(f32, f64);
+impl_ops_tanh_pointwise_for!(f32, f64);
+impl_ops_activation_for!(f32, f64);
+impl_ops_convolution_for!(f32, f64);
+impl_ops_convolution_bias_for!(f32, f64);
+impl_ops_transposed_convolution_for!(f32, f64);
+impl_ops_linear_for!(f32, f64);
+impl_ops_softmax_for!(f32, f64);
+impl_ops_log_softmax_for!(f32, f64);
+impl_ops_loss_for!(f32, f64);
+impl_ops_softmax_cross_entropy_for!(f32, f64);
+impl_ops_lrn_for!(f32, f64);
+impl_ops_pooling_for!(f32, f64);
+impl_ops_batch_normalization_for!(f32, f64);
+impl_ops_layer_normalization_for!(f32, f64);
+impl_ops_group_normalization_for!(f32, f64);
+impl_ops_dropout_for!(f32, f64);
@@ -0,0 +1,372 @@
+//! Provides the multithreaded execution of native NN operations.
+//!
+//! Operations split their output into contiguous chunks, which are computed on the threads of a
+//! [ThreadPool][pool]. Every output element is computed by exactly one thread in the same way as on
+//! a single thread, so the results don't depend on the number of threads.
+//!
+//! `Backend<Native>` runs its operations on a pool with one thread per logical CPU, which is created
+//! once and shared by all of its instances. A [ThreadedBackend][threaded] owns a pool of its own, so
+//! its thread count never affects any other Backend.
+//!
+//! [pool]: ./struct.ThreadPool.html
+//! [threaded]: ./struct.ThreadedBackend.html
+
+use co::prelude::*;
+use num_cpus;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fmt;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+/// The minimal number of elements a thread is used for.
+///
+/// Smaller operations are run on the calling thread, where they are cheaper than a hand-off to the pool.
+pub const MIN_ELEMENTS_PER_THREAD: usize = 16384;
+
+lazy_static! {
+    static ref DEFAULT_POOL: ThreadPool = ThreadPool::new(0);
+}
+
+/// A chunk of an operation, queued for the workers of a pool.
+type Job = Box<FnOnce() + Send + 'static>;
+
+#[derive(Default)]
+struct Queue {
+    jobs: VecDeque<Job>,
+    closed: bool,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    available: Condvar,
+}
+
+impl Shared {
+    /// Takes the next queued job.
+    ///
+    /// With `wait` it blocks until there is a job or the pool is closed.
+    fn pop(&self, wait: bool) -> Option<Job> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if let Some(job) = queue.jobs.pop_front() {
+                return Some(job);
+            }
+            if !wait || queue.closed {
+                return None;
+            }
+            queue = self.available.wait(queue).unwrap();
+        }
+    }
+}
+
+/// Counts the pending jobs of one operation and keeps the first panic among them.
+struct Latch {
+    state: Mutex<(usize, Option<Box<Any + Send>>)>,
+    done: Condvar,
+}
+
+impl Latch {
+    fn new() -> Latch {
+        Latch { state: Mutex::new((0, None)), done: Condvar::new() }
+    }
+
+    /// Locks the state, which stays consistent even if a thread panicked while holding it.
+    fn state<'a>(&'a self) -> MutexGuard<'a, (usize, Option<Box<Any + Send>>)> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Counts one more pending job.
+    fn add(&self) {
+        self.state().0 += 1;
+    }
+
+    /// Runs `job` and counts it as done, even if it panics.
+    fn run<J: FnOnce()>(&self, job: J) {
+        let result = panic::catch_unwind(AssertUnwindSafe(job));
+        let mut state = self.state();
+        state.0 -= 1;
+        if let Err(payload) = result {
+            if state.1.is_none() {
+                state.1 = Some(payload);
+            }
+        }
+        if state.0 == 0 {
+            self.done.notify_all();
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.state().0 == 0
+    }
+
+    /// Blocks until every job is done and returns the first panic among them.
+    fn wait(&self) -> Option<Box<Any + Send>> {
+        let mut state = self.state();
+        while state.0 > 0 {
+            state = self.done.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        state.1.take()
+    }
+}
+
+/// Waits for the jobs of a latch when it is dropped, also while the calling thread unwinds.
+struct WaitOnDrop<'a>(&'a Latch);
+
+impl<'a> Drop for WaitOnDrop<'a> {
+    fn drop(&mut self) {
+        self.0.wait();
+    }
+}
+
+/// A pool of threads native operations are split across.
+///
+/// The threads are started once, when the pool is created, and stopped when it is dropped.
+/// The thread which runs an operation always takes part in it, so a pool of `num_threads`
+/// threads starts `num_threads - 1` workers.
+pub struct ThreadPool {
+    num_threads: usize,
+    shared: Arc<Shared>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Creates a pool of `num_threads` threads.
+    ///
+    /// A `num_threads` of `0` creates one thread per logical CPU and
+    /// a `num_threads` of `1` runs every operation on the calling thread.
+    pub fn new(num_threads: usize) -> ThreadPool {
+        let num_threads = match num_threads {
+            0 => num_cpus::get(),
+            n => n,
+        };
+        let shared = Arc::new(Shared { queue: Mutex::new(Queue::default()), available: Condvar::new() });
+        let workers = (1..num_threads).map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                while let Some(job) = shared.pop(true) {
+                    job();
+                }
+            })
+        }).collect();
+        ThreadPool { num_threads: num_threads, shared: shared, workers: workers }
+    }
+
+    /// Returns the number of threads of the pool.
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Runs every job of `jobs` and returns once all of them are done.
+    ///
+    /// The calling thread runs the first job itself and helps with queued jobs while it waits,
+    /// so an operation which is split again inside a job never waits for a free thread.
+    /// A panic of any job is resumed on the calling thread.
+    fn run_all<'a, J: FnOnce() + Send + 'a>(&self, mut jobs: Vec<J>) {
+        if jobs.is_empty() {
+            return;
+        }
+        let latch = Arc::new(Latch::new());
+        let guard = WaitOnDrop(&latch);
+        let first = jobs.remove(0);
+        {
+            let mut queue = self.shared.queue.lock().unwrap();
+            for job in jobs {
+                let job_latch = latch.clone();
+                let job: Box<FnOnce() + Send + 'a> = Box::new(move || job_latch.run(job));
+                // SAFETY: The job borrows from the caller for `'a`, which the transmute erases.
+                // It is counted by the latch before it is queued and `guard` blocks until the
+                // latch counted every job as done, when this call returns as well as when it
+                // unwinds. So no queued job runs after its borrows end.
+                latch.add();
+                queue.jobs.push_back(unsafe { mem::transmute::<Box<FnOnce() + Send + 'a>, Job>(job) });
+            }
+        }
+        self.shared.available.notify_all();
+        latch.add();
+        latch.run(first);
+        while !latch.is_done() {
+            match self.shared.pop(false) {
+                Some(job) => job(),
+                None => break,
+            }
+        }
+        let panic = latch.wait();
+        drop(guard);
+        if let Some(payload) = panic {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+impl fmt::Debug for ThreadPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ThreadPool").field("num_threads", &self.num_threads).finish()
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().closed = true;
+        self.shared.available.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Provides the device and the thread pool native operations of a Backend run on.
+///
+/// The NN operations of the native framework are implemented for every `NativeThreads`.
+pub trait NativeThreads {
+    /// Returns the native device the operations of the Backend run on.
+    fn device(&self) -> &DeviceType;
+
+    /// Returns the thread pool the operations of the Backend are split across.
+    fn thread_pool(&self) -> &ThreadPool;
+
+    /// Returns the number of threads the operations of the Backend are split across.
+    fn num_threads(&self) -> usize {
+        self.thread_pool().num_threads()
+    }
+}
+
+impl NativeThreads for Backend<Native> {
+    fn device(&self) -> &DeviceType {
+        Backend::device(self)
+    }
+
+    fn thread_pool(&self) -> &ThreadPool {
+        &DEFAULT_POOL
+    }
+}
+
+/// A native Backend which splits its operations across a thread pool of its own.
+///
+/// Provides the same NN operations as `Backend<Native>`.
+#[derive(Debug)]
+pub struct ThreadedBackend {
+    backend: Backend<Native>,
+    pool: ThreadPool,
+}
+
+impl ThreadedBackend {
+    /// Wraps `backend` with a new pool of `num_threads` threads.
+    ///
+    /// A `num_threads` of `0` creates one thread per logical CPU and
+    /// a `num_threads` of `1` runs every operation on the calling thread.
+    pub fn new(backend: Backend<Native>, num_threads: usize) -> ThreadedBackend {
+        ThreadedBackend { backend: backend, pool: ThreadPool::new(num_threads) }
+    }
+
+    /// Returns the wrapped Backend.
+    pub fn backend(&self) -> &Backend<Native> {
+        &self.backend
+    }
+
+    /// Returns the device of the wrapped Backend.
+    pub fn device(&self) -> &DeviceType {
+        self.backend.device()
+    }
+}
+
+impl NativeThreads for ThreadedBackend {
+    fn device(&self) -> &DeviceType {
+        self.backend.device()
+    }
+
+    fn thread_pool(&self) -> &ThreadPool {
+        &self.pool
+    }
+}
+
+/// Calls `func` with the offset and the slice of every chunk of `data` in parallel on `pool`.
+///
+/// Chunks contain a multiple of `unit` elements, so a `unit` of e.g. a sample size
+/// never splits a sample across threads.
+pub fn for_each_chunk_mut<T, F>(pool: &ThreadPool, data: &mut [T], unit: usize, func: F)
+    where T: Send, F: Fn(usize, &mut [T]) + Sync
+{
+    let elements = data.len();
+    split(pool, data, unit, elements, func)
+}
+
+/// Writes `func` of the index of every element of `dest` into it in parallel on `pool`.
+///
+/// `cost` is the number of elements `func` reads for one index, so a short `dest` of
+/// e.g. per-feature statistics is still split across threads.
+pub fn map_indices<T, F>(pool: &ThreadPool, dest: &mut [T], cost: usize, func: F)
+    where T: Send, F: Fn(usize) -> T + Sync
+{
+    let elements = dest.len() * cost;
+    split(pool, dest, 1, elements, |offset, chunk| {
+        for (index, value) in chunk.iter_mut().enumerate() {
+            *value = func(offset + index);
+        }
+    })
+}
+
+/// Splits `data` into chunks of a multiple of `unit` elements for an operation on `elements` elements.
+fn split<T, F>(pool: &ThreadPool, data: &mut [T], unit: usize, elements: usize, func: F)
+    where T: Send, F: Fn(usize, &mut [T]) + Sync
+{
+    let unit = ::std::cmp::max(unit, 1);
+    let units = data.len() / unit;
+    let threads = ::std::cmp::min(pool.num_threads(), elements / MIN_ELEMENTS_PER_THREAD);
+    if threads <= 1 || units <= 1 {
+        return func(0, data);
+    }
+    let chunk_len = (units + threads - 1) / threads * unit;
+    let func = &func;
+    pool.run_all(data.chunks_mut(chunk_len).enumerate().map(|(index, chunk)| {
+        move || func(index * chunk_len, chunk)
+    }).collect());
+}
+
+/// Writes `func` of every element of `src` into `dest` in parallel on `pool`.
+pub fn map<T, F>(pool: &ThreadPool, src: &[T], dest: &mut [T], func: F)
+    where T: Send + Sync, F: Fn(&T) -> T + Sync
+{
+    for_each_chunk_mut(pool, &mut dest[..src.len()], 1, |offset, chunk| {
+        for (out, input) in chunk.iter_mut().zip(src[offset..].iter()) {
+            *out = func(input);
+        }
+    });
+}
+
+/// Writes `func` of every element pair of `a` and `b` into `dest` in parallel on `pool`.
+pub fn zip_map<T, F>(pool: &ThreadPool, a: &[T], b: &[T], dest: &mut [T], func: F)
+    where T: Send + Sync, F: Fn(&T, &T) -> T + Sync
+{
+    let len = ::std::cmp::min(a.len(), b.len());
+    for_each_chunk_mut(pool, &mut dest[..len], 1, |offset, chunk| {
+        for (out, (x, y)) in chunk.iter_mut().zip(a[offset..].iter().zip(b[offset..].iter())) {
+            *out = func(x, y);
+        }
+    });
+}
+
+/// Replaces every element of `data` with `func` of it in parallel on `pool`.
+pub fn map_in_place<T, F>(pool: &ThreadPool, data: &mut [T], func: F)
+    where T: Send, F: Fn(&T) -> T + Sync
+{
+    for_each_chunk_mut(pool, data, 1, |_, chunk| {
+        for value in chunk.iter_mut() {
+            *value = func(value);
+        }
+    });
+}
+
+/// Replaces every element of `data` with `func` of the matching element of `a` and itself in parallel on `pool`.
+pub fn zip_map_in_place<T, F>(pool: &ThreadPool, a: &[T], data: &mut [T], func: F)
+    where T: Send + Sync, F: Fn(&T, &T) -> T + Sync
+{
+    let len = ::std::cmp::min(a.len(), data.len());
+    for_each_chunk_mut(pool, &mut data[..len], 1, |offset, chunk| {
+        for (value, x) in chunk.iter_mut().zip(a[offset..].iter()) {
+            *value = func(x, value);
+        }
+    });
+}
@@ -0,0 +1,478 @@
+//! Provides the vectorized native activation kernels.
+//!
+//! On x86 CPUs the kernels run with AVX2 or SSE2, whichever the CPU supports at runtime.
+//! Every vector width executes the same sequence of operations and the tail of a slice is
+//! computed in a padded vector, so the result of an element depends neither on the vector width
+//! nor on its position in the slice.
+//! All other CPUs use the scalar functions of the [helper][helper] module, whose exponential
+//! comes from the standard library and may differ from the vector kernels in the last bits.
+//! The kernels run on the calling thread, operations split them across threads with
+//! [for_each_chunk_mut][parallel].
+//!
+//! [helper]: ../helper/index.html
+//! [parallel]: ../parallel/fn.for_each_chunk_mut.html
+
+use co::plugin::numeric_helpers::Float;
+use super::helper;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Describes the activation function a kernel computes.
+pub enum Function {
+    /// The logistic function `1 / (1 + e^-x)`.
+    Sigmoid,
+    /// The rectified linear unit `max(x, 0)`.
+    Relu,
+    /// The hyperbolic tangent.
+    Tanh,
+}
+
+/// Provides the activation kernels of a float type.
+pub trait Activations: Float + Send + Sync {
+    /// Writes `function` of the `len` elements at `x` to `y`.
+    ///
+    /// `x` and `y` may point to the same memory.
+    unsafe fn forward_raw(function: Function, x: *const Self, y: *mut Self, len: usize);
+
+    /// Writes the gradient of `function` at the `len` outputs `y` and output gradients `dy` to `dx`.
+    ///
+    /// `dy` and `dx` may point to the same memory.
+    unsafe fn backward_raw(function: Function, y: *const Self, dy: *const Self, dx: *mut Self, len: usize);
+}
+
+/// Writes `function` of every element of `x` into `y`.
+pub fn forward<T: Activations>(function: Function, x: &[T], y: &mut [T]) {
+    let len = ::std::cmp::min(x.len(), y.len());
+    unsafe { T::forward_raw(function, x.as_ptr(), y.as_mut_ptr(), len) }
+}
+
+/// Replaces every element of `x` with `function` of it.
+pub fn forward_in_place<T: Activations>(function: Function, x: &mut [T]) {
+    let ptr = x.as_mut_ptr();
+    unsafe { T::forward_raw(function, ptr, ptr, x.len()) }
+}
+
+/// Writes the gradient of `function` at the outputs `y` and output gradients `dy` into `dx`.
+pub fn backward<T: Activations>(function: Function, y: &[T], dy: &[T], dx: &mut [T]) {
+    let len = ::std::cmp::min(::std::cmp::min(y.len(), dy.len()), dx.len());
+    unsafe { T::backward_raw(function, y.as_ptr(), dy.as_ptr(), dx.as_mut_ptr(), len) }
+}
+
+/// Replaces every output gradient of `dy` with the gradient of `function` at the outputs `y`.
+pub fn backward_in_place<T: Activations>(function: Function, y: &[T], dy: &mut [T]) {
+    let len = ::std::cmp::min(y.len(), dy.len());
+    let ptr = dy.as_mut_ptr();
+    unsafe { T::backward_raw(function, y.as_ptr(), ptr, ptr, len) }
+}
+
+macro_rules! impl_activations_for {
+    ($t:ident, $sse:ident, $avx:ident) => (
+        impl Activations for $t {
+            unsafe fn forward_raw(function: Function, x: *const $t, y: *mut $t, len: usize) {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                {
+                    if is_x86_feature_detected!("avx2") {
+                        return x86::avx2::forward::<x86::$avx>(function, x, y, len);
+                    }
+                    if is_x86_feature_detected!("sse2") {
+                        return x86::sse2::forward::<x86::$sse>(function, x, y, len);
+                    }
+                }
+                scalar::forward(function, x, y, len)
+            }
+
+            unsafe fn backward_raw(function: Function, y: *const $t, dy: *const $t, dx: *mut $t, len: usize) {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                {
+                    if is_x86_feature_detected!("avx2") {
+                        return x86::avx2::backward::<x86::$avx>(function, y, dy, dx, len);
+                    }
+                    if is_x86_feature_detected!("sse2") {
+                        return x86::sse2::backward::<x86::$sse>(function, y, dy, dx, len);
+                    }
+                }
+                scalar::backward(function, y, dy, dx, len)
+            }
+        }
+    );
+}
+
+impl_activations_for!(f32, F32x4, F32x8);
+impl_activations_for!(f64, F64x2, F64x4);
+
+mod scalar {
+    use super::*;
+
+    pub unsafe fn forward<T: Float>(function: Function, x: *const T, y: *mut T, len: usize) {
+        let f: fn(&T) -> T = match function {
+            Function::Sigmoid => helper::sigmoid,
+            Function::Relu => helper::relu,
+            Function::Tanh => helper::tanh,
+        };
+        for i in 0..len {
+            *y.offset(i as isize) = f(&*x.offset(i as isize));
+        }
+    }
+
+    pub unsafe fn backward<T: Float>(function: Function, y: *const T, dy: *const T, dx: *mut T, len: usize) {
+        let f: fn(&T, &T) -> T = match function {
+            Function::Sigmoid => helper::sigmoid_grad,
+            Function::Relu => helper::relu_grad,
+            Function::Tanh => helper::tanh_grad,
+        };
+        for i in 0..len {
+            *dx.offset(i as isize) = f(&*y.offset(i as isize), &*dy.offset(i as isize));
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+    use super::Function;
+
+    /// The widest vector of all instruction sets in elements.
+    const MAX_LANES: usize = 8;
+
+    /// Provides the operations of a SIMD vector.
+    ///
+    /// The operations are compiled for the instruction set of their vector width.
+    pub trait Vector: Copy {
+        type Elem: Copy + Default;
+        const LANES: usize;
+        /// The input magnitude above which the exponential function saturates to infinity or zero.
+        const EXP_MAX: f64;
+        /// The coefficients of `e^r` for `|r| <= ln(2) / 2`, starting with the highest power.
+        const EXP_POLY: &'static [f64];
+        /// The high and low part of `ln(2)`, the high part has few enough bits for an exact product.
+        const LN2: (f64, f64);
+        /// The numerator coefficients of `(tanh(x) - x) / x^3` at `x^2` for `|x| <= 0.625`.
+        const TANH_P: &'static [f64];
+        /// The denominator coefficients of `(tanh(x) - x) / x^3` at `x^2` for `|x| <= 0.625`.
+        const TANH_Q: &'static [f64];
+
+        unsafe fn load(ptr: *const Self::Elem) -> Self;
+        unsafe fn store(self, ptr: *mut Self::Elem);
+        unsafe fn splat(value: f64) -> Self;
+        unsafe fn add(self, other: Self) -> Self;
+        unsafe fn sub(self, other: Self) -> Self;
+        unsafe fn mul(self, other: Self) -> Self;
+        unsafe fn div(self, other: Self) -> Self;
+        /// Returns `other` where either element is NaN.
+        unsafe fn max(self, other: Self) -> Self;
+        /// Returns `other` where either element is NaN.
+        unsafe fn min(self, other: Self) -> Self;
+        /// Returns `then` where `self > other` and `otherwise` elsewhere.
+        unsafe fn select_gt(self, other: Self, then: Self, otherwise: Self) -> Self;
+        unsafe fn abs(self) -> Self;
+        /// Returns the magnitude of `self` with the sign of `sign`.
+        unsafe fn copysign(self, sign: Self) -> Self;
+        unsafe fn floor(self) -> Self;
+        /// Returns `2^self` of integral elements within the normal exponent range.
+        unsafe fn exp2i(self) -> Self;
+    }
+
+    /// Applies `$body` to every vector `$v` of the `$len` elements at `$x` and stores it at `$y`.
+    macro_rules! map_vectors {
+        ($V:ident, $len:ident, $y:ident, |$($v:ident = $x:ident),+| $body:expr) => ({
+            let len = $len;
+            let mut i = 0;
+            while i + $V::LANES <= len {
+                $(let $v = $V::load($x.offset(i as isize));)+
+                $body.store($y.offset(i as isize));
+                i += $V::LANES;
+            }
+            if i < len {
+                let mut buffer = [$V::Elem::default(); MAX_LANES];
+                $(
+                    ::std::ptr::copy($x.offset(i as isize), buffer.as_mut_ptr(), len - i);
+                    let $v = $V::load(buffer.as_ptr());
+                )+
+                $body.store(buffer.as_mut_ptr());
+                ::std::ptr::copy(buffer.as_ptr(), $y.offset(i as isize), len - i);
+            }
+        })
+    }
+
+    /// Implements the kernels for the instruction set `$feature`.
+    ///
+    /// Every function is compiled with `$feature`, so the intrinsics of the `Vector` operations
+    /// are inlined into the loops.
+    macro_rules! impl_kernels {
+        ($name:ident, $feature:tt) => (
+            pub mod $name {
+                use super::*;
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                unsafe fn polynomial<V: Vector>(x: V, coefficients: &[f64]) -> V {
+                    let mut result = V::splat(coefficients[0]);
+                    for c in &coefficients[1..] {
+                        result = result.mul(x).add(V::splat(*c));
+                    }
+                    result
+                }
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                unsafe fn exp<V: Vector>(x: V) -> V {
+                    let (max, min) = (V::splat(V::EXP_MAX), V::splat(-V::EXP_MAX));
+                    // `x` is the second operand of the clamping, so NaNs pass through.
+                    let clamped = min.max(max.min(x));
+                    let n = clamped.mul(V::splat(::std::f64::consts::LOG2_E)).add(V::splat(0.5)).floor();
+                    let r = clamped.sub(n.mul(V::splat(V::LN2.0))).sub(n.mul(V::splat(V::LN2.1)));
+                    // `n` lies within a rounding error of the largest and smallest exponent of a float,
+                    // so `2^n` is applied in two halves which are both normal for any `n` in [-128, 128].
+                    let half = n.mul(V::splat(0.5)).floor();
+                    let result = polynomial(r, V::EXP_POLY).mul(half.exp2i()).mul(n.sub(half).exp2i());
+                    let result = x.select_gt(max, V::splat(::std::f64::INFINITY), result);
+                    min.select_gt(x, V::splat(0.0), result)
+                }
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                unsafe fn sigmoid<V: Vector>(x: V) -> V {
+                    let one = V::splat(1.0);
+                    one.div(one.add(exp(V::splat(0.0).sub(x))))
+                }
+
+                #[inline]
+                #[target_feature(enable = $feature)]
+                unsafe fn tanh<V: Vector>(x: V) -> V {
+                    let a = x.abs();
+                    let z = x.mul(x);
+                    let small = x.add(x.mul(z).mul(polynomial(z, V::TANH_P).div(polynomial(z, V::TANH_Q))));
+                    let (one, two) = (V::splat(1.0), V::splat(2.0));
+                    let large = one.sub(two.div(exp(two.mul(a)).add(one))).copysign(x);
+                    a.select_gt(V::splat(0.625), large, small)
+                }
+
+                #[target_feature(enable = $feature)]
+                pub unsafe fn forward<V: Vector>(function: Function, x: *const V::Elem, y: *mut V::Elem, len: usize) {
+                    let zero = V::splat(0.0);
+                    match function {
+                        Function::Sigmoid => map_vectors!(V, len, y, |v = x| sigmoid(v)),
+                        Function::Relu => map_vectors!(V, len, y, |v = x| v.max(zero)),
+                        Function::Tanh => map_vectors!(V, len, y, |v = x| tanh(v)),
+                    }
+                }
+
+                #[target_feature(enable = $feature)]
+                pub unsafe fn backward<V: Vector>(function: Function, y: *const V::Elem, dy: *const V::Elem, dx: *mut V::Elem, len: usize) {
+                    let (zero, one) = (V::splat(0.0), V::splat(1.0));
+                    match function {
+                        Function::Sigmoid => map_vectors!(V, len, dx, |a = y, b = dy| a.mul(one.sub(a)).mul(b)),
+                        Function::Relu => map_vectors!(V, len, dx, |a = y, b = dy| a.select_gt(zero, b, zero)),
+                        Function::Tanh => map_vectors!(V, len, dx, |a = y, b = dy| one.sub(a.mul(a)).mul(b)),
+                    }
+                }
+            }
+        );
+    }
+
+    impl_kernels!(sse2, "sse2");
+    impl_kernels!(avx2, "avx2");
+
+    // Cephes `expf` and `tanhf`.
+    const F32_EXP_POLY: &'static [f64] = &[1.9875691500E-4, 1.3981999507E-3, 8.3334519073E-3, 4.1665795894E-2,
+                                           1.6666665459E-1, 5.0000001201E-1, 1.0, 1.0];
+    const F32_LN2: (f64, f64) = (0.693359375, -2.12194440E-4);
+    const F32_TANH_P: &'static [f64] = &[-5.70498872745E-3, 2.06390887954E-2, -5.37397155531E-2,
+                                         1.33314422036E-1, -3.33332819422E-1];
+    const F32_TANH_Q: &'static [f64] = &[1.0];
+    // The Taylor series of `e^r` up to `r^13` and Cephes `tanh`.
+    const F64_EXP_POLY: &'static [f64] = &[1.0 / 6227020800.0, 1.0 / 479001600.0, 1.0 / 39916800.0, 1.0 / 3628800.0,
+                                           1.0 / 362880.0, 1.0 / 40320.0, 1.0 / 5040.0, 1.0 / 720.0, 1.0 / 120.0,
+                                           1.0 / 24.0, 1.0 / 6.0, 0.5, 1.0, 1.0];
+    const F64_LN2: (f64, f64) = (6.93145751953125E-1, 1.42860682030941723212E-6);
+    const F64_TANH_P: &'static [f64] = &[-9.64399179425052238628E-1, -9.92877231001918586564E1,
+                                         -1.61468768441708447952E3];
+    const F64_TANH_Q: &'static [f64] = &[1.0, 1.12811678491632931402E2, 2.23548839060100448583E3,
+                                         4.84406305325125486048E3];
+
+    #[derive(Copy, Clone)]
+    pub struct F32x4(__m128);
+
+    impl Vector for F32x4 {
+        type Elem = f32;
+        const LANES: usize = 4;
+        const EXP_MAX: f64 = 88.3762626647949;
+        const EXP_POLY: &'static [f64] = F32_EXP_POLY;
+        const LN2: (f64, f64) = F32_LN2;
+        const TANH_P: &'static [f64] = F32_TANH_P;
+        const TANH_Q: &'static [f64] = F32_TANH_Q;
+
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn load(ptr: *const f32) -> Self { F32x4(_mm_loadu_ps(ptr)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn store(self, ptr: *mut f32) { _mm_storeu_ps(ptr, self.0) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn splat(value: f64) -> Self { F32x4(_mm_set1_ps(value as f32)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn add(self, other: Self) -> Self { F32x4(_mm_add_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn sub(self, other: Self) -> Self { F32x4(_mm_sub_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn mul(self, other: Self) -> Self { F32x4(_mm_mul_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn div(self, other: Self) -> Self { F32x4(_mm_div_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn max(self, other: Self) -> Self { F32x4(_mm_max_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn min(self, other: Self) -> Self { F32x4(_mm_min_ps(self.0, other.0)) }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn select_gt(self, other: Self, then: Self, otherwise: Self) -> Self {
+            let mask = _mm_cmpgt_ps(self.0, other.0);
+            F32x4(_mm_or_ps(_mm_and_ps(mask, then.0), _mm_andnot_ps(mask, otherwise.0)))
+        }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn abs(self) -> Self { F32x4(_mm_andnot_ps(_mm_set1_ps(-0.0), self.0)) }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn copysign(self, sign: Self) -> Self {
+            let mask = _mm_set1_ps(-0.0);
+            F32x4(_mm_or_ps(_mm_andnot_ps(mask, self.0), _mm_and_ps(mask, sign.0)))
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn floor(self) -> Self {
+            // SSE2 has no rounding, truncate and correct the negative fractions.
+            let truncated = _mm_cvtepi32_ps(_mm_cvttps_epi32(self.0));
+            let correction = _mm_and_ps(_mm_cmpgt_ps(truncated, self.0), _mm_set1_ps(1.0));
+            F32x4(_mm_sub_ps(truncated, correction))
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn exp2i(self) -> Self {
+            let exponent = _mm_add_epi32(_mm_cvttps_epi32(self.0), _mm_set1_epi32(127));
+            F32x4(_mm_castsi128_ps(_mm_slli_epi32(exponent, 23)))
+        }
+    }
+
+    #[derive(Copy, Clone)]
+    pub struct F64x2(__m128d);
+
+    impl Vector for F64x2 {
+        type Elem = f64;
+        const LANES: usize = 2;
+        const EXP_MAX: f64 = 708.3964185322641;
+        const EXP_POLY: &'static [f64] = F64_EXP_POLY;
+        const LN2: (f64, f64) = F64_LN2;
+        const TANH_P: &'static [f64] = F64_TANH_P;
+        const TANH_Q: &'static [f64] = F64_TANH_Q;
+
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn load(ptr: *const f64) -> Self { F64x2(_mm_loadu_pd(ptr)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn store(self, ptr: *mut f64) { _mm_storeu_pd(ptr, self.0) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn splat(value: f64) -> Self { F64x2(_mm_set1_pd(value)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn add(self, other: Self) -> Self { F64x2(_mm_add_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn sub(self, other: Self) -> Self { F64x2(_mm_sub_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn mul(self, other: Self) -> Self { F64x2(_mm_mul_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn div(self, other: Self) -> Self { F64x2(_mm_div_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn max(self, other: Self) -> Self { F64x2(_mm_max_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn min(self, other: Self) -> Self { F64x2(_mm_min_pd(self.0, other.0)) }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn select_gt(self, other: Self, then: Self, otherwise: Self) -> Self {
+            let mask = _mm_cmpgt_pd(self.0, other.0);
+            F64x2(_mm_or_pd(_mm_and_pd(mask, then.0), _mm_andnot_pd(mask, otherwise.0)))
+        }
+        #[inline] #[target_feature(enable = "sse2")] unsafe fn abs(self) -> Self { F64x2(_mm_andnot_pd(_mm_set1_pd(-0.0), self.0)) }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn copysign(self, sign: Self) -> Self {
+            let mask = _mm_set1_pd(-0.0);
+            F64x2(_mm_or_pd(_mm_andnot_pd(mask, self.0), _mm_and_pd(mask, sign.0)))
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn floor(self) -> Self {
+            let truncated = _mm_cvtepi32_pd(_mm_cvttpd_epi32(self.0));
+            let correction = _mm_and_pd(_mm_cmpgt_pd(truncated, self.0), _mm_set1_pd(1.0));
+            F64x2(_mm_sub_pd(truncated, correction))
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn exp2i(self) -> Self {
+            let exponent = _mm_add_epi32(_mm_cvttpd_epi32(self.0), _mm_set1_epi32(1023));
+            let exponent = _mm_unpacklo_epi32(exponent, _mm_setzero_si128());
+            F64x2(_mm_castsi128_pd(_mm_slli_epi64(exponent, 52)))
+        }
+    }
+
+    #[derive(Copy, Clone)]
+    pub struct F32x8(__m256);
+
+    impl Vector for F32x8 {
+        type Elem = f32;
+        const LANES: usize = 8;
+        const EXP_MAX: f64 = 88.3762626647949;
+        const EXP_POLY: &'static [f64] = F32_EXP_POLY;
+        const LN2: (f64, f64) = F32_LN2;
+        const TANH_P: &'static [f64] = F32_TANH_P;
+        const TANH_Q: &'static [f64] = F32_TANH_Q;
+
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn load(ptr: *const f32) -> Self { F32x8(_mm256_loadu_ps(ptr)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn store(self, ptr: *mut f32) { _mm256_storeu_ps(ptr, self.0) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn splat(value: f64) -> Self { F32x8(_mm256_set1_ps(value as f32)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn add(self, other: Self) -> Self { F32x8(_mm256_add_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn sub(self, other: Self) -> Self { F32x8(_mm256_sub_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn mul(self, other: Self) -> Self { F32x8(_mm256_mul_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn div(self, other: Self) -> Self { F32x8(_mm256_div_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn max(self, other: Self) -> Self { F32x8(_mm256_max_ps(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn min(self, other: Self) -> Self { F32x8(_mm256_min_ps(self.0, other.0)) }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn select_gt(self, other: Self, then: Self, otherwise: Self) -> Self {
+            F32x8(_mm256_blendv_ps(otherwise.0, then.0, _mm256_cmp_ps(self.0, other.0, _CMP_GT_OQ)))
+        }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn abs(self) -> Self { F32x8(_mm256_andnot_ps(_mm256_set1_ps(-0.0), self.0)) }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn copysign(self, sign: Self) -> Self {
+            let mask = _mm256_set1_ps(-0.0);
+            F32x8(_mm256_or_ps(_mm256_andnot_ps(mask, self.0), _mm256_and_ps(mask, sign.0)))
+        }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn floor(self) -> Self { F32x8(_mm256_floor_ps(self.0)) }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn exp2i(self) -> Self {
+            let exponent = _mm256_add_epi32(_mm256_cvttps_epi32(self.0), _mm256_set1_epi32(127));
+            F32x8(_mm256_castsi256_ps(_mm256_slli_epi32(exponent, 23)))
+        }
+    }
+
+    #[derive(Copy, Clone)]
+    pub struct F64x4(__m256d);
+
+    impl Vector for F64x4 {
+        type Elem = f64;
+        const LANES: usize = 4;
+        const EXP_MAX: f64 = 708.3964185322641;
+        const EXP_POLY: &'static [f64] = F64_EXP_POLY;
+        const LN2: (f64, f64) = F64_LN2;
+        const TANH_P: &'static [f64] = F64_TANH_P;
+        const TANH_Q: &'static [f64] = F64_TANH_Q;
+
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn load(ptr: *const f64) -> Self { F64x4(_mm256_loadu_pd(ptr)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn store(self, ptr: *mut f64) { _mm256_storeu_pd(ptr, self.0) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn splat(value: f64) -> Self { F64x4(_mm256_set1_pd(value)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn add(self, other: Self) -> Self { F64x4(_mm256_add_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn sub(self, other: Self) -> Self { F64x4(_mm256_sub_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn mul(self, other: Self) -> Self { F64x4(_mm256_mul_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn div(self, other: Self) -> Self { F64x4(_mm256_div_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn max(self, other: Self) -> Self { F64x4(_mm256_max_pd(self.0, other.0)) }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn min(self, other: Self) -> Self { F64x4(_mm256_min_pd(self.0, other.0)) }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn select_gt(self, other: Self, then: Self, otherwise: Self) -> Self {
+            F64x4(_mm256_blendv_pd(otherwise.0, then.0, _mm256_cmp_pd(self.0, other.0, _CMP_GT_OQ)))
+        }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn abs(self) -> Self { F64x4(_mm256_andnot_pd(_mm256_set1_pd(-0.0), self.0)) }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn copysign(self, sign: Self) -> Self {
+            let mask = _mm256_set1_pd(-0.0);
+            F64x4(_mm256_or_pd(_mm256_andnot_pd(mask, self.0), _mm256_and_pd(mask, sign.0)))
+        }
+        #[inline] #[target_feature(enable = "avx2")] unsafe fn floor(self) -> Self { F64x4(_mm256_floor_pd(self.0)) }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn exp2i(self) -> Self {
+            let exponent = _mm_add_epi32(_mm256_cvttpd_epi32(self.0), _mm_set1_epi32(1023));
+            F64x4(_mm256_castsi256_pd(_mm256_slli_epi64(_mm256_cvtepi32_epi64(exponent), 52)))
+        }
+    }
+}
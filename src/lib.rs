@@ -83,21 +83,33 @@
 //! | Operation            | CUDA       | OpenCL    | Native    |
 //! |---	               |---	        |---        |---        |
 //! | Sigmoid  	           | cuDNN v3  	| -  	    | Rust  	|
-//! | SigmoidPointwise     | cuDNN v3  	| -  	    |   	    |
+//! | SigmoidPointwise     | cuDNN v3  	| -  	    | Rust      |
 //! | ReLU  	           | cuDNN v3   | -  	    | Rust 	    |
-//! | ReLUPointwise        | cuDNN v3  	| -  	    |   	    |
+//! | ReLUPointwise        | cuDNN v3  	| -  	    | Rust      |
+//! | ClippedReLU          | -          | -  	    | Rust      |
+//! | ClippedReLUPointwise | -          | -  	    | Rust      |
 //! | Tanh  	   	       | cudNN v3   | - 	    | Rust      |
-//! | TanhPointwise        | cuDNN v3  	| -  	    |   	    |
+//! | TanhPointwise        | cuDNN v3  	| -  	    | Rust      |
+//! | Activation           | -          | -  	    | Rust      |
 //! |   	   	           |  	        |  	        |           |
-//! | Normalization (LRN)  | cudNN v3   | - 	    | -         |
+//! | Normalization (LRN)  | cudNN v3   | - 	    | Rust      |
+//! | Batch Normalization  | -          | - 	    | Rust      |
+//! | Layer Normalization  | -          | - 	    | Rust      |
+//! | Group Normalization  | -          | - 	    | Rust      |
+//! | Dropout              | -          | - 	    | Rust      |
 //! |   	   	           |  	        |  	        |           |
-//! | Convolution          | cudNN v3   | - 	    | -         |
+//! | Convolution          | cudNN v3   | - 	    | Rust      |
+//! | Convolution Bias     | -          | - 	    | Rust      |
+//! | Deconvolution        | -          | - 	    | Rust      |
+//! | Linear               | -          | - 	    | Rust      |
 //! |   	   	           |  	        |  	        |           |
 //! | Softmax              | cudNN v3   | - 	    | Rust      |
 //! | LogSoftmax           | cudNN v3   | - 	    | Rust      |
+//! | Loss                 | -          | - 	    | Rust      |
+//! | SoftmaxCrossEntropy  | -          | - 	    | Rust      |
 //! |   	   	           |  	        |  	        |           |
-//! | Pooling Max          | cudNN v3   | - 	    | -         |
-//! | Pooling Avg          | cudNN v3   | - 	    | -         |
+//! | Pooling Max          | cudNN v3   | - 	    | Rust      |
+//! | Pooling Avg          | cudNN v3   | - 	    | Rust      |
 //!
 //! [collenchyma]: https://github.com/autumnai/collenchyma
 //! [collenchyma-docs]: http://autumnai.github.io/collenchyma
@@ -114,6 +126,8 @@ extern crate collenchyma as co;
 #[cfg(feature = "cuda")]
 extern crate cudnn;
 extern crate libc;
+#[cfg(feature = "native")]
+extern crate num_cpus;
 #[macro_use]
 extern crate lazy_static;
 #[macro_use]
@@ -30,6 +30,10 @@ pub enum ConvForwardAlgo {
     ///
     /// Listed in cuDNN docs but cuDNN does not provide a implementation.
     Direct,
+    /// Compute the convolution with the Winograd F(2x2, 3x3) minimal filtering algorithm.
+    ///
+    /// Only supports 3x3 filters with a stride and dilation of 1 and needs a memory workspace for the transformed tiles.
+    Winograd,
 }
 
 impl ConvForwardAlgo {
@@ -47,6 +51,12 @@ impl ConvForwardAlgo {
 pub enum ConvBackwardFilterAlgo {
     /// Attempt to automatically find the best algorithm of all the other available ones.
     Auto,
+    /// Compute the convolution as explicit matrix product.
+    ///
+    /// Needs a significant memory workspace.
+    ///
+    /// The results are deterministic.
+    GEMM,
     /// Compute the convolution as matrix product without forming the matrix that holds the input data.
     ///
     /// Does not need any memory workspace.
@@ -86,6 +96,12 @@ impl ConvBackwardFilterAlgo {
 pub enum ConvBackwardDataAlgo {
     /// Attempt to automatically find the best algorithm of all the other available ones.
     Auto,
+    /// Compute the convolution as explicit matrix product.
+    ///
+    /// Needs a significant memory workspace.
+    ///
+    /// The results are deterministic.
+    GEMM,
     /// Compute the convolution as matrix product without forming the matrix that holds the input data.
     ///
     /// Does not need any memory workspace.
@@ -302,6 +318,78 @@ pub trait ReluPointwise<F> : NN<F> {
     fn relu_pointwise_grad_plain(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
 }
 
+/// Provides the functionality for a Backend to support clipped ReLU operations.
+pub trait ClippedRelu<F> : NN<F> {
+    /// Computes the ReLU clipped at `ceiling`, `min(max(x, 0), ceiling)`, over the input Tensor `x` with complete memory management.
+    ///
+    /// Saves the result to `result`.
+    /// A `ceiling` of `6.0` gives the ReLU6 of MobileNet.
+    ///
+    /// For a no-memory managed version see `clipped_relu_plain`.
+    fn clipped_relu(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+
+    /// Computes the clipped ReLU over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `clipped_relu`.
+    fn clipped_relu_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the clipped ReLU over the input Tensor `x` with complete memory management.
+    ///
+    /// The gradient is passed through only where the output lies strictly between `0` and `ceiling`.
+    /// Saves the result to `result_diff`.
+    ///
+    /// For a no-memory managed version see `clipped_relu_grad_plain`.
+    fn clipped_relu_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, result: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the clipped ReLU over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `clipped_relu_grad`.
+    fn clipped_relu_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise clipped ReLU operations (overwrites the input with the result of the operation).
+pub trait ClippedReluPointwise<F> : NN<F> {
+    /// Computes the ReLU clipped at `ceiling` over the input Tensor `x` with complete memory management.
+    ///
+    /// Saves the result back to `x`.
+    ///
+    /// For a no-memory managed version see `clipped_relu_pointwise_plain`.
+    fn clipped_relu_pointwise(&self, x: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+
+    /// Computes the clipped ReLU over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result back to `x`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `clipped_relu_pointwise`.
+    fn clipped_relu_pointwise_plain(&self, x: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the clipped ReLU over the input Tensor `x` with complete memory management.
+    ///
+    /// Saves the result back to `x_diff`.
+    ///
+    /// For a no-memory managed version see `clipped_relu_pointwise_grad_plain`.
+    fn clipped_relu_pointwise_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the clipped ReLU over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result back to `x_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `clipped_relu_pointwise_grad`.
+    fn clipped_relu_pointwise_grad_plain(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>, ceiling: f64) -> Result<(), ::co::error::Error>;
+}
+
 /// Provides the functionality for a Backend to support TanH operations.
 pub trait Tanh<F> : NN<F> {
     /// Computes the [hyperbolic Tangent][tanh] over the input Tensor `x` with complete memory management.
@@ -379,9 +467,18 @@ pub trait TanhPointwise<F> : NN<F> {
 /// Provides the functionality for a Backend to support Convolution operations.
 pub trait Convolution<F> : NN<F> {
     /// Creates a new ConvolutionConfig, which needs to be passed to further convolution Operations.
+    ///
+    /// `stride`, `zero_padding` and `dilation` hold one value per spatial dimension. A [dilation][atrous]
+    /// greater than 1 spaces out the filter elements, so a filter of size `k` covers a window of
+    /// `(k - 1) * dilation + 1` input elements.
+    /// [atrous]: https://arxiv.org/abs/1511.07122
+    ///
+    /// `groups` splits the input and output channels into that many groups, which are convolved separately,
+    /// so the filter has the shape `[output channels, input channels / groups, height, width]`.
+    /// With one group per input channel this is a depthwise convolution.
     fn new_convolution_config(&self, src: &SharedTensor<F>, dest: &SharedTensor<F>, filter: &mut SharedTensor<F>,
                             algo_fwd: ConvForwardAlgo, algo_bwd_filter: ConvBackwardFilterAlgo, algo_bwd_data: ConvBackwardDataAlgo,
-                            stride: &[i32], zero_padding: &[i32]) -> Result<Self::CC, ::co::error::Error>;
+                            stride: &[i32], zero_padding: &[i32], dilation: &[i32], groups: usize) -> Result<Self::CC, ::co::error::Error>;
 
     /// Computes a [CNN convolution][convolution] over the input Tensor `x` with complete memory management.
     /// [convolution]: https://en.wikipedia.org/wiki/Convolutional_neural_network
@@ -435,18 +532,6 @@ pub trait Convolution<F> : NN<F> {
     fn convolution_grad_data_plain(&self, filter: &SharedTensor<F>, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, workspace: &mut SharedTensor<u8>, config: &Self::CC) -> Result<(), ::co::error::Error>;
 }
 
-    // /// Computes the backward Convolution function w.r.t the bias.
-    // ///
-    // /// Writes the result of the computation to `bias_data`.
-    // pub fn convolution_backward_bias<T>(
-    //     &self,
-    //     dest_grad_desc: &TensorDescriptor,
-    //     dest_grad_data: *const ::libc::c_void,
-    //     bias_desc: &TensorDescriptor,
-    //     bias_data: *mut ::libc::c_void,
-    //     scale: ScalParams<T>,
-    // }
-    //
     // /// Computes the backward Convolution function w.r.t the filter.
     // ///
     // /// Writes the result of the computation to `filter_data`.
@@ -461,11 +546,210 @@ pub trait Convolution<F> : NN<F> {
     //     scale: ScalParams<T>,
     // }
 
+/// Provides the functionality for a Backend to support the bias of Convolution operations.
+///
+/// The bias holds one value per channel of a `[batch, channels, spatial..]` output.
+pub trait ConvolutionBias<F> : NN<F> {
+    /// Adds the per-channel `bias` to every sample and position of the convolution output `result` with complete memory management.
+    ///
+    /// Saves the result back to `result`.
+    ///
+    /// For a no-memory managed version see `convolution_bias_plain`.
+    fn convolution_bias(&self, bias: &mut SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Adds the per-channel `bias` to the convolution output `result` without any memory management.
+    ///
+    /// Saves the result back to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `convolution_bias`.
+    fn convolution_bias_plain(&self, bias: &SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a convolution with respect to the bias and complete memory management.
+    ///
+    /// Saves the sums of the output gradients `dest_diff` of every channel to `bias_diff`.
+    ///
+    /// For a no-memory managed version see `convolution_grad_bias_plain`.
+    fn convolution_grad_bias(&self, dest_diff: &mut SharedTensor<F>, bias_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a convolution with respect to the bias and without any memory management.
+    ///
+    /// Saves the result to `bias_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `convolution_grad_bias`.
+    fn convolution_grad_bias_plain(&self, dest_diff: &SharedTensor<F>, bias_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support transposed Convolution operations.
+///
+/// A transposed convolution, also known as deconvolution, maps every input position to a window of the
+/// output. Its forward pass is the adjoint of `Convolution::convolution`, i.e. the gradient of a convolution
+/// with respect to the data, and upsamples the input by the stride.
+///
+/// The filter has the shape `[input channels, output channels, height, width]`, which is the filter
+/// of the convolution from the output of the transposed convolution to its input.
+pub trait TransposedConvolution<F> : NN<F> {
+    /// The Transposed Convolution Operation Config representation for this Plugin.
+    type CTC: NNOperationConfig<F> + ConvolutionConfig<F>;
+
+    /// Computes the shape of the output of a transposed convolution of an input of `src_shape` with a filter of `filter_shape`.
+    ///
+    /// Every spatial dimension of the output is `(input - 1) * stride - 2 * zero_padding + filter + output_padding`.
+    /// The `output_padding` has to be smaller than the stride and selects one of the output sizes
+    /// a strided convolution maps onto the same input size.
+    fn transposed_convolution_output_shape(&self, src_shape: &[usize], filter_shape: &[usize], stride: &[i32], zero_padding: &[i32],
+                                           output_padding: &[i32]) -> Result<Vec<usize>, ::co::error::Error>;
+
+    /// Creates a new TransposedConvolutionConfig, which needs to be passed to further transposed convolution Operations.
+    ///
+    /// The forward pass is computed with a data gradient algorithm `algo_fwd` of convolution and the gradient
+    /// with respect to the data with a forward algorithm `algo_bwd_data` of convolution.
+    fn new_transposed_convolution_config(&self, src: &SharedTensor<F>, dest: &SharedTensor<F>, filter: &mut SharedTensor<F>,
+                                         algo_fwd: ConvBackwardDataAlgo, algo_bwd_filter: ConvBackwardFilterAlgo, algo_bwd_data: ConvForwardAlgo,
+                                         stride: &[i32], zero_padding: &[i32], output_padding: &[i32]) -> Result<Self::CTC, ::co::error::Error>;
+
+    /// Computes a [transposed convolution][deconvolution] over the input Tensor `x` with complete memory management.
+    /// [deconvolution]: https://arxiv.org/abs/1603.07285
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// For a no-memory managed version see `transposed_convolution_plain`.
+    fn transposed_convolution(&self, filter: &mut SharedTensor<F>, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>,
+                              workspace: &mut SharedTensor<u8>, config: &Self::CTC) -> Result<(), ::co::error::Error>;
+
+    /// Computes the transposed convolution over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `transposed_convolution`.
+    fn transposed_convolution_plain(&self, filter: &SharedTensor<F>, x: &SharedTensor<F>, result: &mut SharedTensor<F>,
+                                    workspace: &mut SharedTensor<u8>, config: &Self::CTC) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a [transposed convolution][deconvolution] with respect to the filter and complete memory management.
+    /// [deconvolution]: https://arxiv.org/abs/1603.07285
+    ///
+    /// Takes the input of the forward pass as `src_data` and the gradient of its output as `dest_diff`.
+    /// Saves the result to `filter_diff`.
+    ///
+    /// For a no-memory managed version see `transposed_convolution_grad_filter_plain`.
+    fn transposed_convolution_grad_filter(&self, src_data: &mut SharedTensor<F>, dest_diff: &mut SharedTensor<F>, filter_diff: &mut SharedTensor<F>,
+                                          workspace: &mut SharedTensor<u8>, config: &Self::CTC) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a transposed convolution with respect to the filter and without any memory management.
+    ///
+    /// Saves the result to `filter_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `transposed_convolution_grad_filter`.
+    fn transposed_convolution_grad_filter_plain(&self, src_data: &SharedTensor<F>, dest_diff: &SharedTensor<F>, filter_diff: &mut SharedTensor<F>,
+                                                workspace: &mut SharedTensor<u8>, config: &Self::CTC) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a [transposed convolution][deconvolution] with respect to the data and complete memory management.
+    /// [deconvolution]: https://arxiv.org/abs/1603.07285
+    ///
+    /// Takes the gradient of the output as `x_diff` and saves the gradient of the input to `result_diff`.
+    ///
+    /// For a no-memory managed version see `transposed_convolution_grad_data_plain`.
+    fn transposed_convolution_grad_data(&self, filter: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>,
+                                        workspace: &mut SharedTensor<u8>, config: &Self::CTC) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a transposed convolution with respect to the data and without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `transposed_convolution_grad_data`.
+    fn transposed_convolution_grad_data_plain(&self, filter: &SharedTensor<F>, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>,
+                                              workspace: &mut SharedTensor<u8>, config: &Self::CTC) -> Result<(), ::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support fully connected (linear) layers.
+///
+/// Computes `y = x * W^T + b` for an input `x` of `[batch, inputs]`, weights `W` of `[outputs, inputs]`
+/// and a bias `b` of `[outputs]`. All dimensions of `x` after the first are flattened into the inputs.
+pub trait Linear<F> : NN<F> {
+    /// Computes the [fully connected layer][linear] over the input Tensor `x` with complete memory management.
+    /// [linear]: https://en.wikipedia.org/wiki/Multilayer_perceptron
+    ///
+    /// Saves the result of `[batch, outputs]` to `result`.
+    ///
+    /// For a no-memory managed version see `linear_plain`.
+    fn linear(&self, weight: &mut SharedTensor<F>, bias: &mut SharedTensor<F>, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the fully connected layer over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `linear`.
+    fn linear_plain(&self, weight: &SharedTensor<F>, bias: &SharedTensor<F>, x: &SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a fully connected layer with respect to its input and complete memory management.
+    ///
+    /// Takes the gradient of the output as `x_diff` and saves the gradient of the input, `x_diff * W`, to `result_diff`.
+    ///
+    /// For a no-memory managed version see `linear_grad_input_plain`.
+    fn linear_grad_input(&self, weight: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a fully connected layer with respect to its input and without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `linear_grad_input`.
+    fn linear_grad_input_plain(&self, weight: &SharedTensor<F>, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a fully connected layer with respect to the weights and complete memory management.
+    ///
+    /// Takes the input of the forward pass as `src_data` and the gradient of the output as `dest_diff`.
+    /// Saves the gradient of the weights, `dest_diff^T * src_data`, to `weight_diff`.
+    ///
+    /// For a no-memory managed version see `linear_grad_weight_plain`.
+    fn linear_grad_weight(&self, src_data: &mut SharedTensor<F>, dest_diff: &mut SharedTensor<F>, weight_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a fully connected layer with respect to the weights and without any memory management.
+    ///
+    /// Saves the result to `weight_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `linear_grad_weight`.
+    fn linear_grad_weight_plain(&self, src_data: &SharedTensor<F>, dest_diff: &SharedTensor<F>, weight_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a fully connected layer with respect to the bias and complete memory management.
+    ///
+    /// Saves the sum of the output gradients `dest_diff` over the batch to `bias_diff`.
+    ///
+    /// For a no-memory managed version see `linear_grad_bias_plain`.
+    fn linear_grad_bias(&self, dest_diff: &mut SharedTensor<F>, bias_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a fully connected layer with respect to the bias and without any memory management.
+    ///
+    /// Saves the result to `bias_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `linear_grad_bias`.
+    fn linear_grad_bias_plain(&self, dest_diff: &SharedTensor<F>, bias_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+}
+
 /// Provides the functionality for a Backend to support Softmax operations.
 pub trait Softmax<F> : NN<F> {
     /// Computes a [Softmax][softmax] over the input Tensor `x` with complete memory management.
     /// [softmax]: https://en.wikipedia.org/wiki/Softmax_function
     ///
+    /// A Tensor with two or at least four dimensions is treated as a batch and every sample is
+    /// normalized on its own. For a specific dimension see `softmax_axis`.
+    /// A slice of only negative infinities is treated like a slice of equal values, instead of giving NaN.
     /// Saves the result to `result`.
     ///
     /// For a no-memory managed version see `softmax_plain`.
@@ -496,12 +780,51 @@ pub trait Softmax<F> : NN<F> {
     /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
     /// For a memory managed version see `softmax_grad`.
     fn softmax_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes a [Softmax][softmax] along the dimension `axis` of the input Tensor `x` with complete memory management.
+    /// [softmax]: https://en.wikipedia.org/wiki/Softmax_function
+    ///
+    /// Every slice of `x` along `axis` is normalized on its own, e.g. an `axis` of `1`
+    /// normalizes every sample of a `[batch, classes]` Tensor.
+    /// Saves the result to `result`.
+    ///
+    /// For a no-memory managed version see `softmax_axis_plain`.
+    fn softmax_axis(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
+
+    /// Computes the softmax along the dimension `axis` of the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `softmax_axis`.
+    fn softmax_axis_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a [Softmax][softmax] along the dimension `axis` of the input Tensor `x` with complete memory management.
+    /// [softmax]: https://en.wikipedia.org/wiki/Softmax_function
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// For a no-memory managed version see `softmax_axis_grad_plain`.
+    fn softmax_axis_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a softmax along the dimension `axis` of the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `softmax_axis_grad`.
+    fn softmax_axis_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
 }
 
 /// Provides the functionality for a Backend to support LogSoftmax operations.
 pub trait LogSoftmax<F> : NN<F> {
     /// Computes a logarithmic softmax over the input Tensor `x` with complete memory management.
     ///
+    /// A Tensor with two or at least four dimensions is treated as a batch and every sample is
+    /// normalized on its own. For a specific dimension see `log_softmax_axis`.
+    /// A slice of only negative infinities is treated like a slice of equal values, instead of giving NaN.
     /// Saves the result to `result`.
     ///
     /// For a no-memory managed version see `log_softmax_plain`.
@@ -531,6 +854,160 @@ pub trait LogSoftmax<F> : NN<F> {
     /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
     /// For a memory managed version see `log_softmax_grad`.
     fn log_softmax_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes a logarithmic softmax along the dimension `axis` of the input Tensor `x` with complete memory management.
+    ///
+    /// Every slice of `x` along `axis` is normalized on its own.
+    /// Saves the result to `result`.
+    ///
+    /// For a no-memory managed version see `log_softmax_axis_plain`.
+    fn log_softmax_axis(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
+
+    /// Computes the logarithmic softmax along the dimension `axis` of the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `log_softmax_axis`.
+    fn log_softmax_axis_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a logarithmic softmax along the dimension `axis` of the input Tensor `x` with complete memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// For a no-memory managed version see `log_softmax_axis_grad_plain`.
+    fn log_softmax_axis_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a logarithmic softmax along the dimension `axis` of the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `log_softmax_axis_grad`.
+    fn log_softmax_axis_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, axis: usize) -> Result<(), ::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Different loss functions and their parameters.
+pub enum LossMode {
+    /// The negative log-likelihood `-x[label]` of log-probabilities, e.g. the output of `LogSoftmax`.
+    ///
+    /// The target holds one integral class label per sample.
+    NegativeLogLikelihood,
+    /// The squared error `(x - target)^2`.
+    ///
+    /// The target has the shape of the input.
+    MeanSquaredError,
+    /// The binary cross-entropy `-(target * ln(x) + (1 - target) * ln(1 - x))` of probabilities `x`.
+    ///
+    /// The target has the shape of the input, the logarithms are clamped at `-100`.
+    BinaryCrossEntropy,
+    /// The multiclass hinge loss `sum(max(0, margin - x[label] + x[j]))` over all classes `j` but the label.
+    ///
+    /// The target holds one integral class label per sample.
+    Hinge(f64),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Different reductions of the element-wise losses to a scalar.
+pub enum LossReduction {
+    /// Averages the losses over the samples for class losses and over all elements otherwise.
+    Mean,
+    /// Sums the losses.
+    Sum,
+    /// Keeps one loss per sample, summed over its classes.
+    ///
+    /// The gradient is the one of `Sum`, i.e. every sample loss receives a gradient of one.
+    None,
+}
+
+/// Provides the functionality for a Backend to support loss functions.
+///
+/// The input Tensor `x` is treated as `[batch, classes]`, where all dimensions after the first
+/// are flattened into the classes.
+pub trait Loss<F> : NN<F> {
+    /// Computes the loss `mode` of the input Tensor `x` against `target` with complete memory management.
+    ///
+    /// Saves the loss reduced by `reduction` to `result`, which holds a single element
+    /// or one element per sample for `LossReduction::None`.
+    ///
+    /// For a no-memory managed version see `loss_plain`.
+    fn loss(&self, mode: LossMode, reduction: LossReduction, x: &mut SharedTensor<F>, target: &mut SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the loss `mode` of the input Tensor `x` against `target` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `loss`.
+    fn loss_plain(&self, mode: LossMode, reduction: LossReduction, x: &SharedTensor<F>, target: &SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the loss `mode` with respect to the input Tensor `x` with complete memory management.
+    ///
+    /// Saves the gradient of the reduced loss to `result_diff`.
+    ///
+    /// For a no-memory managed version see `loss_grad_plain`.
+    fn loss_grad(&self, mode: LossMode, reduction: LossReduction, x: &mut SharedTensor<F>, target: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the loss `mode` with respect to the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `loss_grad`.
+    fn loss_grad_plain(&self, mode: LossMode, reduction: LossReduction, x: &SharedTensor<F>, target: &SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Different kinds of targets of a cross-entropy.
+pub enum CrossEntropyTarget {
+    /// The target holds one integral class label per sample.
+    ClassIndices,
+    /// The target holds a probability distribution over the classes of every sample.
+    Probabilities,
+}
+
+/// Provides the functionality for a Backend to support the fused softmax cross-entropy.
+///
+/// Computes the cross-entropy of the softmax of the logits `x` of `[batch, classes]` against `target`
+/// in a single numerically stable pass, without materializing the (logarithmic) softmax.
+pub trait SoftmaxCrossEntropy<F> : NN<F> {
+    /// Computes the softmax cross-entropy of the logits `x` against `target` with complete memory management.
+    ///
+    /// Saves the loss reduced by `reduction` to `result`, which holds a single element
+    /// or one element per sample for `LossReduction::None`.
+    ///
+    /// For a no-memory managed version see `softmax_cross_entropy_plain`.
+    fn softmax_cross_entropy(&self, target_kind: CrossEntropyTarget, reduction: LossReduction, x: &mut SharedTensor<F>, target: &mut SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the softmax cross-entropy of the logits `x` against `target` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `softmax_cross_entropy`.
+    fn softmax_cross_entropy_plain(&self, target_kind: CrossEntropyTarget, reduction: LossReduction, x: &SharedTensor<F>, target: &SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the softmax cross-entropy with respect to the logits `x` with complete memory management.
+    ///
+    /// Saves `softmax(x) - target`, with class labels expanded to one-hot rows, scaled by the `reduction` to `result_diff`.
+    ///
+    /// For a no-memory managed version see `softmax_cross_entropy_grad_plain`.
+    fn softmax_cross_entropy_grad(&self, target_kind: CrossEntropyTarget, reduction: LossReduction, x: &mut SharedTensor<F>, target: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the softmax cross-entropy with respect to the logits `x` without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `softmax_cross_entropy_grad`.
+    fn softmax_cross_entropy_grad_plain(&self, target_kind: CrossEntropyTarget, reduction: LossReduction, x: &SharedTensor<F>, target: &SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
 }
 
 /// Provides the functionality for a Backend to support Local Response Normalization operations.
@@ -573,11 +1050,25 @@ pub trait LRN<F> : NN<F> {
     fn lrn_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, config: &Self::CLRN) -> Result<(), ::co::error::Error>;
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Different ways to average the pooling windows that overlap the zero padding.
+pub enum PoolingAvgMode {
+    /// Divide by the full window size, counting the zero padding as elements.
+    IncludePadding,
+    /// Divide only by the number of window elements that lie inside the input.
+    ExcludePadding,
+}
+
 /// Provides the functionality for a Backend to support Pooling operations.
 pub trait Pooling<F> : NN<F> {
     /// Creates a new PoolingConfig, which needs to be passed to further pooling Operations.
+    ///
+    /// Average pooling with this config excludes the zero padding.
     fn new_pooling_config(&self, window: &[i32], padding: &[i32], stride: &[i32]) -> Result<Self::CPOOL, ::co::error::Error>;
 
+    /// Creates a new PoolingConfig whose average pooling treats the zero padding according to `avg_mode`.
+    fn new_pooling_avg_config(&self, window: &[i32], padding: &[i32], stride: &[i32], avg_mode: PoolingAvgMode) -> Result<Self::CPOOL, ::co::error::Error>;
+
     /// Computes non-linear down-sampling ([max Pooling][pooling]) over the input Tensor `x` with complete memory management.
     /// [pooling]: https://en.wikipedia.org/wiki/Convolutional_neural_network#Pooling_layer
     ///
@@ -611,4 +1102,393 @@ pub trait Pooling<F> : NN<F> {
     /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
     /// For a memory managed version see `pooling_max_grad`.
     fn pooling_max_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, config: &Self::CPOOL) -> Result<(), ::co::error::Error>;
+
+    /// Computes non-linear down-sampling ([average Pooling][pooling]) over the input Tensor `x` with complete memory management.
+    /// [pooling]: https://en.wikipedia.org/wiki/Convolutional_neural_network#Pooling_layer
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// For a no-memory managed version see `pooling_avg_plain`.
+    fn pooling_avg(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, config: &Self::CPOOL) -> Result<(), ::co::error::Error>;
+
+    /// Computes the average pooling over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `pooling_avg`.
+    fn pooling_avg_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, config: &Self::CPOOL) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of [average Pooling][pooling] over the input Tensor `x` with complete memory management.
+    /// [pooling]: https://en.wikipedia.org/wiki/Convolutional_neural_network#Pooling_layer
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// For a no-memory managed version see `pooling_avg_grad_plain`.
+    fn pooling_avg_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, result: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>, config: &Self::CPOOL) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of average pooling over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `pooling_avg_grad`.
+    fn pooling_avg_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, config: &Self::CPOOL) -> Result<(), ::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Different sets of elements over which batch normalization computes its statistics.
+pub enum BatchNormMode {
+    /// Normalize every activation over the batch.
+    ///
+    /// The scale, bias and statistics have one element per activation of a sample.
+    PerActivation,
+    /// Normalize every channel over the batch and the spatial dimensions.
+    ///
+    /// The scale, bias and statistics have one element per channel.
+    Spatial,
+}
+
+/// Provides the functionality for a Backend to support Batch Normalization operations.
+///
+/// The input Tensor `x` is treated as `[batch, channels, spatial dimensions...]`.
+/// The scale `gamma`, the bias `beta` and all statistics are Tensors with one element per normalized feature,
+/// as selected by the `BatchNormMode` of the config.
+pub trait BatchNormalization<F> : NN<F> {
+    /// The Batch Normalization Operation Config representation for this Plugin.
+    type CBN: NNOperationConfig<F>;
+
+    /// Creates a new BatchNormalizationConfig, which needs to be passed to further batch normalization Operations.
+    ///
+    /// `epsilon` is added to the variance for numerical stability and `momentum` is the weight of
+    /// the batch statistics when updating the running statistics.
+    fn new_batch_normalization_config(&self, mode: BatchNormMode, epsilon: f64, momentum: f64) -> Result<Self::CBN, ::co::error::Error>;
+
+    /// Computes a [Batch Normalization][bn] with the statistics of the input Tensor `x` with complete memory management.
+    /// [bn]: https://arxiv.org/abs/1502.03167
+    ///
+    /// Saves the result to `result`, moves `running_mean` and `running_variance` towards the batch mean and
+    /// unbiased batch variance and saves the batch mean and inverse standard deviation for the backward pass
+    /// to `saved_mean` and `saved_inv_variance`.
+    ///
+    /// For a no-memory managed version see `batch_normalization_training_plain`.
+    fn batch_normalization_training(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &mut SharedTensor<F>, beta: &mut SharedTensor<F>,
+                                    running_mean: &mut SharedTensor<F>, running_variance: &mut SharedTensor<F>,
+                                    saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>, config: &Self::CBN) -> Result<(), ::co::error::Error>;
+
+    /// Computes a Batch Normalization with the statistics of the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result` and updates the running and saved statistics like `batch_normalization_training`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `batch_normalization_training`.
+    fn batch_normalization_training_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &SharedTensor<F>, beta: &SharedTensor<F>,
+                                          running_mean: &mut SharedTensor<F>, running_variance: &mut SharedTensor<F>,
+                                          saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>, config: &Self::CBN) -> Result<(), ::co::error::Error>;
+
+    /// Computes a [Batch Normalization][bn] with the running statistics over the input Tensor `x` with complete memory management.
+    /// [bn]: https://arxiv.org/abs/1502.03167
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// For a no-memory managed version see `batch_normalization_inference_plain`.
+    fn batch_normalization_inference(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &mut SharedTensor<F>, beta: &mut SharedTensor<F>,
+                                     running_mean: &mut SharedTensor<F>, running_variance: &mut SharedTensor<F>, config: &Self::CBN) -> Result<(), ::co::error::Error>;
+
+    /// Computes a Batch Normalization with the running statistics over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `batch_normalization_inference`.
+    fn batch_normalization_inference_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &SharedTensor<F>, beta: &SharedTensor<F>,
+                                           running_mean: &SharedTensor<F>, running_variance: &SharedTensor<F>, config: &Self::CBN) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a [Batch Normalization][bn] in training mode over the input Tensor `x` with complete memory management.
+    /// [bn]: https://arxiv.org/abs/1502.03167
+    ///
+    /// Takes the gradient of the output as `x_diff` and the statistics saved by the forward pass.
+    /// Saves the gradient of the input to `result_diff`, of the scale to `gamma_diff` and of the bias to `beta_diff`.
+    ///
+    /// For a no-memory managed version see `batch_normalization_grad_plain`.
+    fn batch_normalization_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, gamma: &mut SharedTensor<F>,
+                                saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>,
+                                result_diff: &mut SharedTensor<F>, gamma_diff: &mut SharedTensor<F>, beta_diff: &mut SharedTensor<F>, config: &Self::CBN) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a Batch Normalization in training mode over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the results to `result_diff`, `gamma_diff` and `beta_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `batch_normalization_grad`.
+    fn batch_normalization_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, gamma: &SharedTensor<F>,
+                                      saved_mean: &SharedTensor<F>, saved_inv_variance: &SharedTensor<F>,
+                                      result_diff: &mut SharedTensor<F>, gamma_diff: &mut SharedTensor<F>, beta_diff: &mut SharedTensor<F>, config: &Self::CBN) -> Result<(), ::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support Layer Normalization operations.
+pub trait LayerNormalization<F> : NN<F> {
+    /// The Layer Normalization Operation Config representation for this Plugin.
+    type CLN: NNOperationConfig<F>;
+
+    /// Creates a new LayerNormalizationConfig, which needs to be passed to further layer normalization Operations.
+    ///
+    /// Every index of the leading dimensions is normalized over the trailing `normalized_dims` dimensions
+    /// and `epsilon` is added to the variance for numerical stability.
+    fn new_layer_normalization_config(&self, normalized_dims: usize, epsilon: f64) -> Result<Self::CLN, ::co::error::Error>;
+
+    /// Computes a [Layer Normalization][ln] over the input Tensor `x` with complete memory management.
+    /// [ln]: https://arxiv.org/abs/1607.06450
+    ///
+    /// `gamma` and `beta` hold one scale and bias per element of the normalized dimensions.
+    /// Saves the result to `result` and the mean and inverse standard deviation of every normalized group
+    /// for the backward pass to `saved_mean` and `saved_inv_variance`.
+    ///
+    /// For a no-memory managed version see `layer_normalization_plain`.
+    fn layer_normalization(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &mut SharedTensor<F>, beta: &mut SharedTensor<F>,
+                           saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>, config: &Self::CLN) -> Result<(), ::co::error::Error>;
+
+    /// Computes a Layer Normalization over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result` and the statistics to `saved_mean` and `saved_inv_variance`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `layer_normalization`.
+    fn layer_normalization_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &SharedTensor<F>, beta: &SharedTensor<F>,
+                                 saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>, config: &Self::CLN) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a [Layer Normalization][ln] over the input Tensor `x` with complete memory management.
+    /// [ln]: https://arxiv.org/abs/1607.06450
+    ///
+    /// Takes the gradient of the output as `x_diff` and the statistics saved by the forward pass.
+    /// Saves the gradient of the input to `result_diff`, of the scale to `gamma_diff` and of the bias to `beta_diff`.
+    ///
+    /// For a no-memory managed version see `layer_normalization_grad_plain`.
+    fn layer_normalization_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, gamma: &mut SharedTensor<F>,
+                                saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>,
+                                result_diff: &mut SharedTensor<F>, gamma_diff: &mut SharedTensor<F>, beta_diff: &mut SharedTensor<F>, config: &Self::CLN) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a Layer Normalization over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the results to `result_diff`, `gamma_diff` and `beta_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `layer_normalization_grad`.
+    fn layer_normalization_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, gamma: &SharedTensor<F>,
+                                      saved_mean: &SharedTensor<F>, saved_inv_variance: &SharedTensor<F>,
+                                      result_diff: &mut SharedTensor<F>, gamma_diff: &mut SharedTensor<F>, beta_diff: &mut SharedTensor<F>, config: &Self::CLN) -> Result<(), ::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support Group Normalization operations.
+pub trait GroupNormalization<F> : NN<F> {
+    /// The Group Normalization Operation Config representation for this Plugin.
+    type CGN: NNOperationConfig<F>;
+
+    /// Creates a new GroupNormalizationConfig, which needs to be passed to further group normalization Operations.
+    ///
+    /// The channels of every sample are split into `groups` groups of consecutive channels, which are normalized
+    /// over their channels and the spatial dimensions. `epsilon` is added to the variance for numerical stability.
+    fn new_group_normalization_config(&self, groups: usize, epsilon: f64) -> Result<Self::CGN, ::co::error::Error>;
+
+    /// Creates a new GroupNormalizationConfig for an [Instance Normalization][in] of inputs with `channels` channels.
+    /// [in]: https://arxiv.org/abs/1607.08022
+    ///
+    /// Every channel of every sample is normalized over the spatial dimensions on its own,
+    /// which is a group normalization with one channel per group.
+    fn new_instance_normalization_config(&self, channels: usize, epsilon: f64) -> Result<Self::CGN, ::co::error::Error>;
+
+    /// Computes a [Group Normalization][gn] over the NCHW input Tensor `x` with complete memory management.
+    /// [gn]: https://arxiv.org/abs/1803.08494
+    ///
+    /// `gamma` and `beta` hold one scale and bias per channel.
+    /// Saves the result to `result` and the mean and inverse standard deviation of every group of every sample
+    /// for the backward pass to `saved_mean` and `saved_inv_variance`.
+    ///
+    /// For a no-memory managed version see `group_normalization_plain`.
+    fn group_normalization(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &mut SharedTensor<F>, beta: &mut SharedTensor<F>,
+                           saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>, config: &Self::CGN) -> Result<(), ::co::error::Error>;
+
+    /// Computes a Group Normalization over the NCHW input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result` and the statistics to `saved_mean` and `saved_inv_variance`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `group_normalization`.
+    fn group_normalization_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, gamma: &SharedTensor<F>, beta: &SharedTensor<F>,
+                                 saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>, config: &Self::CGN) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a [Group Normalization][gn] over the NCHW input Tensor `x` with complete memory management.
+    /// [gn]: https://arxiv.org/abs/1803.08494
+    ///
+    /// Takes the gradient of the output as `x_diff` and the statistics saved by the forward pass.
+    /// Saves the gradient of the input to `result_diff`, of the scale to `gamma_diff` and of the bias to `beta_diff`.
+    ///
+    /// For a no-memory managed version see `group_normalization_grad_plain`.
+    fn group_normalization_grad(&self, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, gamma: &mut SharedTensor<F>,
+                                saved_mean: &mut SharedTensor<F>, saved_inv_variance: &mut SharedTensor<F>,
+                                result_diff: &mut SharedTensor<F>, gamma_diff: &mut SharedTensor<F>, beta_diff: &mut SharedTensor<F>, config: &Self::CGN) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a Group Normalization over the NCHW input Tensor `x` without any memory management.
+    ///
+    /// Saves the results to `result_diff`, `gamma_diff` and `beta_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `group_normalization_grad`.
+    fn group_normalization_grad_plain(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, gamma: &SharedTensor<F>,
+                                      saved_mean: &SharedTensor<F>, saved_inv_variance: &SharedTensor<F>,
+                                      result_diff: &mut SharedTensor<F>, gamma_diff: &mut SharedTensor<F>, beta_diff: &mut SharedTensor<F>, config: &Self::CGN) -> Result<(), ::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support Dropout operations.
+pub trait Dropout<F> : NN<F> {
+    /// The Dropout Operation Config representation for this Plugin.
+    type CDROP: NNOperationConfig<F>;
+
+    /// Creates a new DropoutConfig, which needs to be passed to further dropout Operations.
+    ///
+    /// Every element is dropped with `probability`. The masks drawn with one config are determined by `seed`
+    /// and the mask index passed to the forward pass.
+    fn new_dropout_config(&self, probability: f64, seed: u64) -> Result<Self::CDROP, ::co::error::Error>;
+
+    /// Computes a [Dropout][dropout] over the input Tensor `x` with complete memory management.
+    /// [dropout]: https://www.cs.toronto.edu/~hinton/absps/JMLRdropout.pdf
+    ///
+    /// Draws the mask number `mask_index` of `config`, which is zero for dropped elements and scales the kept
+    /// elements by `1 / (1 - probability)`. The same seed and mask index always give the same mask.
+    /// Saves the mask to `mask` and the masked input to `result`.
+    ///
+    /// For a no-memory managed version see `dropout_plain`.
+    fn dropout(&self, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>, mask: &mut SharedTensor<F>, mask_index: u64, config: &Self::CDROP) -> Result<(), ::co::error::Error>;
+
+    /// Computes the Dropout over the input Tensor `x` without any memory management.
+    ///
+    /// Draws the mask number `mask_index` of `config`.
+    /// Saves the mask to `mask` and the masked input to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `dropout`.
+    fn dropout_plain(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, mask: &mut SharedTensor<F>, mask_index: u64, config: &Self::CDROP) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a [Dropout][dropout] with the `mask` of the forward pass with complete memory management.
+    /// [dropout]: https://www.cs.toronto.edu/~hinton/absps/JMLRdropout.pdf
+    ///
+    /// Takes the gradient of the output as `x_diff` and saves the gradient of the input to `result_diff`.
+    ///
+    /// For a no-memory managed version see `dropout_grad_plain`.
+    fn dropout_grad(&self, x_diff: &mut SharedTensor<F>, mask: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>, config: &Self::CDROP) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of a Dropout with the `mask` of the forward pass without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `dropout_grad`.
+    fn dropout_grad_plain(&self, x_diff: &SharedTensor<F>, mask: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, config: &Self::CDROP) -> Result<(), ::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Different activation functions and their parameters.
+pub enum ActivationMode {
+    /// The logistic function `1 / (1 + e^-x)`.
+    Sigmoid,
+    /// The rectified linear unit `max(x, 0)`.
+    Relu,
+    /// The rectified linear unit clipped at a ceiling, `min(max(x, 0), ceiling)`.
+    ClippedRelu(f64),
+    /// The hyperbolic tangent.
+    Tanh,
+    /// The leaky rectified linear unit, `x` for positive `x` and `alpha * x` otherwise.
+    LeakyRelu(f64),
+    /// The exponential linear unit, `x` for positive `x` and `alpha * (e^x - 1)` otherwise.
+    Elu(f64),
+    /// The scaled exponential linear unit with the self-normalizing constants `lambda = 1.0507` and `alpha = 1.67326`.
+    Selu,
+    /// The Gaussian error linear unit in its common tanh approximation.
+    Gelu,
+    /// The smooth rectifier `ln(1 + e^x)`.
+    Softplus,
+    /// The self-gated function `x / (1 + e^(-beta * x))`.
+    Swish(f64),
+}
+
+/// Provides the functionality for a Backend to support the activation functions of `ActivationMode`.
+///
+/// The gradients take the same arguments as the ones of the single activation traits like `Sigmoid`.
+pub trait Activation<F> : NN<F> {
+    /// Computes the activation function `mode` over the input Tensor `x` with complete memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// For a no-memory managed version see `activation_plain`.
+    fn activation(&self, mode: ActivationMode, x: &mut SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the activation function `mode` over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result to `result`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `activation`.
+    fn activation_plain(&self, mode: ActivationMode, x: &SharedTensor<F>, result: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the activation function `mode` with complete memory management.
+    ///
+    /// Takes the output of the forward pass as `x`, its gradient as `x_diff` and the input of the forward pass as `result`.
+    /// Saves the gradient of the input to `result_diff`.
+    ///
+    /// For a no-memory managed version see `activation_grad_plain`.
+    fn activation_grad(&self, mode: ActivationMode, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>, result: &mut SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the activation function `mode` without any memory management.
+    ///
+    /// Saves the result to `result_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `activation_grad`.
+    fn activation_grad_plain(&self, mode: ActivationMode, x: &SharedTensor<F>, x_diff: &SharedTensor<F>, result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the activation function `mode` over the input Tensor `x` with complete memory management.
+    ///
+    /// Saves the result back to `x`.
+    ///
+    /// For a no-memory managed version see `activation_pointwise_plain`.
+    fn activation_pointwise(&self, mode: ActivationMode, x: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the activation function `mode` over the input Tensor `x` without any memory management.
+    ///
+    /// Saves the result back to `x`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `activation_pointwise`.
+    fn activation_pointwise_plain(&self, mode: ActivationMode, x: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the activation function `mode` from its output `x` with complete memory management.
+    ///
+    /// Saves the result back to `x_diff`.
+    /// Fails for `Gelu`, `Swish` and a negative `LeakyRelu` alpha, whose gradient cannot be recovered from the output.
+    ///
+    /// For a no-memory managed version see `activation_pointwise_grad_plain`.
+    fn activation_pointwise_grad(&self, mode: ActivationMode, x: &mut SharedTensor<F>, x_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
+
+    /// Computes the gradient of the activation function `mode` from its output `x` without any memory management.
+    ///
+    /// Saves the result back to `x_diff`.
+    ///
+    /// *Attention*:<br/>
+    /// For a correct computation result, you need to manage the memory allocation and synchronization yourself.<br/>
+    /// For a memory managed version see `activation_pointwise_grad`.
+    fn activation_pointwise_grad_plain(&self, mode: ActivationMode, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>) -> Result<(), ::co::error::Error>;
 }
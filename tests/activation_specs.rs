@@ -0,0 +1,170 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod activation_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn get_tensor(backend: &Backend<Native>, data: &[f64]) -> SharedTensor<f64> {
+        let mut x = SharedTensor::<f64>::new(backend.device(), &(data.len())).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        x
+    }
+
+    fn read_tensor(backend: &Backend<Native>, x: &SharedTensor<f64>) -> Vec<f64> {
+        x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().to_vec()
+    }
+
+    fn assert_all_close(expected: &[f64], actual: &[f64], tolerance: f64) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!((e - a).abs() <= tolerance, "expected {:?}, got {:?}", expected, actual);
+        }
+    }
+
+    const MODES: [ActivationMode; 10] = [ActivationMode::Sigmoid, ActivationMode::Relu, ActivationMode::ClippedRelu(2.0), ActivationMode::Tanh,
+                                         ActivationMode::LeakyRelu(0.1), ActivationMode::Elu(1.5), ActivationMode::Selu,
+                                         ActivationMode::Gelu, ActivationMode::Softplus, ActivationMode::Swish(1.25)];
+
+    /// Inputs on both sides of zero which keep away from the kinks of the rectifiers.
+    fn inputs() -> Vec<f64> {
+        (0..24).map(|i| (i as f64 - 11.5) / 4f64).collect()
+    }
+
+    fn forward(backend: &Backend<Native>, mode: ActivationMode, data: &[f64]) -> Vec<f64> {
+        let mut x = get_tensor(backend, data);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(data.len())).unwrap();
+        backend.activation(mode, &mut x, &mut result).unwrap();
+        read_tensor(backend, &result)
+    }
+
+    fn grad(backend: &Backend<Native>, mode: ActivationMode, data: &[f64], diff: &[f64]) -> Vec<f64> {
+        let mut x = get_tensor(backend, &forward(backend, mode, data));
+        let mut x_diff = get_tensor(backend, diff);
+        let mut result = get_tensor(backend, data);
+        let mut result_diff = SharedTensor::<f64>::new(backend.device(), &(data.len())).unwrap();
+        backend.activation_grad(mode, &mut x, &mut x_diff, &mut result, &mut result_diff).unwrap();
+        read_tensor(backend, &result_diff)
+    }
+
+    #[test]
+    fn it_computes_correct_activations_on_native() {
+        let backend = get_native_backend();
+        let data = [-2f64, -0.5f64, 0f64, 1f64, 3f64];
+        let selu = |x: f64| 1.0507009873554805f64 * if x > 0f64 { x } else { 1.6732632423543772f64 * (x.exp() - 1f64) };
+        let gelu = |x: f64| 0.5f64 * x * (1f64 + (0.7978845608028654f64 * (x + 0.044715f64 * x * x * x)).tanh());
+        let expected: Vec<(ActivationMode, Vec<f64>)> = vec![
+            (ActivationMode::LeakyRelu(0.1), vec![-0.2f64, -0.05f64, 0f64, 1f64, 3f64]),
+            (ActivationMode::Elu(2.0), data.iter().map(|&x| if x > 0f64 { x } else { 2f64 * (x.exp() - 1f64) }).collect()),
+            (ActivationMode::Selu, data.iter().map(|&x| selu(x)).collect()),
+            (ActivationMode::Gelu, data.iter().map(|&x| gelu(x)).collect()),
+            (ActivationMode::Softplus, data.iter().map(|&x| (1f64 + x.exp()).ln()).collect()),
+            (ActivationMode::Swish(1.5), data.iter().map(|&x| x / (1f64 + (-1.5f64 * x).exp())).collect()),
+        ];
+        for (mode, values) in expected {
+            assert_all_close(&values, &forward(&backend, mode, &data), 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_computes_stable_softplus_on_native() {
+        let backend = get_native_backend();
+        let result = forward(&backend, ActivationMode::Softplus, &[-800f64, 800f64]);
+        assert_all_close(&[0f64, 800f64], &result, 1e-12);
+    }
+
+    #[test]
+    fn it_matches_the_single_activations_on_native() {
+        let backend = get_native_backend();
+        let data = inputs();
+        let diff: Vec<f64> = (0..24).map(|i| 1f64 + i as f64 / 10f64).collect();
+        let mut x = get_tensor(&backend, &data);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(24)).unwrap();
+        backend.sigmoid(&mut x, &mut result).unwrap();
+        assert_eq!(read_tensor(&backend, &result), forward(&backend, ActivationMode::Sigmoid, &data));
+        backend.relu(&mut x, &mut result).unwrap();
+        assert_eq!(read_tensor(&backend, &result), forward(&backend, ActivationMode::Relu, &data));
+        backend.tanh(&mut x, &mut result).unwrap();
+        assert_eq!(read_tensor(&backend, &result), forward(&backend, ActivationMode::Tanh, &data));
+
+        let mut x_diff = get_tensor(&backend, &diff);
+        let mut result_diff = SharedTensor::<f64>::new(backend.device(), &(24)).unwrap();
+        backend.tanh_grad(&mut result, &mut x_diff, &mut x, &mut result_diff).unwrap();
+        assert_eq!(read_tensor(&backend, &result_diff), grad(&backend, ActivationMode::Tanh, &data, &diff));
+    }
+
+    #[test]
+    fn it_computes_correct_activation_grads_on_native() {
+        let backend = get_native_backend();
+        let data = inputs();
+        // the loss is the weighted sum of the outputs, so its output gradient is the weights.
+        let weights: Vec<f64> = (0..24).map(|i| 1f64 + i as f64 / 10f64).collect();
+        let h = 1e-6;
+        for &mode in MODES.iter() {
+            let numeric: Vec<f64> = data.iter().zip(weights.iter()).map(|(&x, w)| {
+                let values = forward(&backend, mode, &[x + h, x - h]);
+                (values[0] - values[1]) / (2f64 * h) * w
+            }).collect();
+            assert_all_close(&numeric, &grad(&backend, mode, &data, &weights), 1e-6);
+        }
+    }
+
+    #[test]
+    fn it_computes_consistent_pointwise_activations_on_native() {
+        let backend = get_native_backend();
+        let data = inputs();
+        let diff: Vec<f64> = (0..24).map(|i| 2f64 - i as f64 / 8f64).collect();
+        for &mode in MODES.iter() {
+            let mut x = get_tensor(&backend, &data);
+            backend.activation_pointwise(mode, &mut x).unwrap();
+            assert_eq!(forward(&backend, mode, &data), read_tensor(&backend, &x));
+
+            let mut x_diff = get_tensor(&backend, &diff);
+            match mode {
+                ActivationMode::Gelu | ActivationMode::Swish(_) => {
+                    assert!(backend.activation_pointwise_grad(mode, &mut x, &mut x_diff).is_err());
+                },
+                _ => {
+                    backend.activation_pointwise_grad(mode, &mut x, &mut x_diff).unwrap();
+                    assert_all_close(&grad(&backend, mode, &data, &diff), &read_tensor(&backend, &x_diff), 1e-12);
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_activations_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &[1f64, 2f64]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        assert!(backend.activation(ActivationMode::Elu(-1.0), &mut x, &mut result).is_err());
+        assert!(backend.activation(ActivationMode::LeakyRelu(::std::f64::NAN), &mut x, &mut result).is_err());
+        assert!(backend.activation(ActivationMode::Swish(::std::f64::INFINITY), &mut x, &mut result).is_err());
+
+        let mut x_diff = get_tensor(&backend, &[1f64, 1f64]);
+        assert!(backend.activation_pointwise_grad(ActivationMode::LeakyRelu(-0.5), &mut x, &mut x_diff).is_err());
+
+        let mut wrong = SharedTensor::<f64>::new(backend.device(), &(3)).unwrap();
+        assert!(backend.activation(ActivationMode::Selu, &mut x, &mut wrong).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(feature = "native")]
+mod common;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod batch_normalization_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+    use common::*;
+
+    /// The input, scale and bias of a batch normalization with `features` normalized features.
+    struct Problem {
+        dims: Vec<usize>,
+        features: usize,
+        x: Vec<f64>,
+        gamma: Vec<f64>,
+        beta: Vec<f64>,
+    }
+
+    impl Problem {
+        fn new(mode: BatchNormMode) -> Problem {
+            let dims = vec![3, 2, 2, 3];
+            let features = match mode {
+                BatchNormMode::PerActivation => 12,
+                BatchNormMode::Spatial => 2,
+            };
+            Problem {
+                x: random_data(36, 0),
+                gamma: random_data(features, 3).iter().map(|g| g + 3f64).collect(),
+                beta: random_data(features, 5),
+                dims: dims,
+                features: features,
+            }
+        }
+
+        /// Runs the training forward pass and returns the output and the saved mean and inverse standard deviation.
+        fn forward(&self, backend: &Backend<Native>, conf: &<Backend<Native> as BatchNormalization<f64>>::CBN,
+                   x: &[f64], gamma: &[f64], beta: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+            let features = [self.features];
+            let mut x = get_tensor(backend, &self.dims, x);
+            let mut result = SharedTensor::<f64>::new(backend.device(), &self.dims).unwrap();
+            let mut gamma = get_tensor(backend, &features, gamma);
+            let mut beta = get_tensor(backend, &features, beta);
+            let mut running_mean = get_tensor(backend, &features, &vec![0f64; self.features]);
+            let mut running_variance = get_tensor(backend, &features, &vec![1f64; self.features]);
+            let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &features.to_vec()).unwrap();
+            let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &features.to_vec()).unwrap();
+            backend.batch_normalization_training(&mut x, &mut result, &mut gamma, &mut beta, &mut running_mean, &mut running_variance,
+                                                 &mut saved_mean, &mut saved_inv_variance, conf).unwrap();
+            (read_tensor(backend, &result), read_tensor(backend, &saved_mean), read_tensor(backend, &saved_inv_variance))
+        }
+    }
+
+    fn check_gradient(mode: BatchNormMode) {
+        let backend = get_native_backend();
+        let conf = BatchNormalization::<f64>::new_batch_normalization_config(&backend, mode, 1e-5, 0.1).unwrap();
+        let problem = Problem::new(mode);
+        // the loss is the weighted sum of the outputs, so its output gradient is the weights.
+        let weights = random_data(36, 11);
+        let loss = |x: &[f64], gamma: &[f64], beta: &[f64]| dot(&problem.forward(&backend, &conf, x, gamma, beta).0, &weights);
+
+        let (_, saved_mean, saved_inv_variance) = problem.forward(&backend, &conf, &problem.x, &problem.gamma, &problem.beta);
+        let features = [problem.features];
+        let mut x = get_tensor(&backend, &problem.dims, &problem.x);
+        let mut x_diff = get_tensor(&backend, &problem.dims, &weights);
+        let mut gamma = get_tensor(&backend, &features, &problem.gamma);
+        let mut saved_mean = get_tensor(&backend, &features, &saved_mean);
+        let mut saved_inv_variance = get_tensor(&backend, &features, &saved_inv_variance);
+        let mut result_diff = SharedTensor::<f64>::new(backend.device(), &problem.dims).unwrap();
+        let mut gamma_diff = SharedTensor::<f64>::new(backend.device(), &features.to_vec()).unwrap();
+        let mut beta_diff = SharedTensor::<f64>::new(backend.device(), &features.to_vec()).unwrap();
+        backend.batch_normalization_grad(&mut x, &mut x_diff, &mut gamma, &mut saved_mean, &mut saved_inv_variance,
+                                         &mut result_diff, &mut gamma_diff, &mut beta_diff, &conf).unwrap();
+
+        assert_all_close(&numeric_gradient(&problem.x, &|x| loss(x, &problem.gamma, &problem.beta)), &read_tensor(&backend, &result_diff), 1e-5);
+        assert_all_close(&numeric_gradient(&problem.gamma, &|gamma| loss(&problem.x, gamma, &problem.beta)), &read_tensor(&backend, &gamma_diff), 1e-5);
+        assert_all_close(&numeric_gradient(&problem.beta, &|beta| loss(&problem.x, &problem.gamma, beta)), &read_tensor(&backend, &beta_diff), 1e-5);
+    }
+
+    #[test]
+    fn it_normalizes_per_channel_in_training_on_native() {
+        let backend = get_native_backend();
+        let conf = BatchNormalization::<f64>::new_batch_normalization_config(&backend, BatchNormMode::Spatial, 1e-5, 0.1).unwrap();
+        let mut x = get_tensor(&backend, &[2, 2, 1, 2], &[1f64, 2f64, 10f64, 20f64, 3f64, 4f64, 30f64, 40f64]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 2, 1, 2)).unwrap();
+        let mut gamma = get_tensor(&backend, &[2], &[1f64, 2f64]);
+        let mut beta = get_tensor(&backend, &[2], &[0f64, 1f64]);
+        let mut running_mean = get_tensor(&backend, &[2], &[0f64, 0f64]);
+        let mut running_variance = get_tensor(&backend, &[2], &[1f64, 1f64]);
+        let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        backend.batch_normalization_training(&mut x, &mut result, &mut gamma, &mut beta, &mut running_mean, &mut running_variance,
+                                             &mut saved_mean, &mut saved_inv_variance, &conf).unwrap();
+
+        // channel 0 holds 1, 2, 3, 4 and channel 1 holds 10, 20, 30, 40.
+        let (inv_std_0, inv_std_1) = (1f64 / (1.25f64 + 1e-5).sqrt(), 1f64 / (125f64 + 1e-5).sqrt());
+        assert_all_close(&[2.5f64, 25f64], &read_tensor(&backend, &saved_mean), 1e-12);
+        assert_all_close(&[inv_std_0, inv_std_1], &read_tensor(&backend, &saved_inv_variance), 1e-12);
+        assert_all_close(&[-1.5f64 * inv_std_0, -0.5f64 * inv_std_0, 1f64 - 30f64 * inv_std_1, 1f64 - 10f64 * inv_std_1,
+                           0.5f64 * inv_std_0, 1.5f64 * inv_std_0, 1f64 + 10f64 * inv_std_1, 1f64 + 30f64 * inv_std_1],
+                         &read_tensor(&backend, &result), 1e-12);
+        // the running variance moves towards the unbiased variance.
+        assert_all_close(&[0.25f64, 2.5f64], &read_tensor(&backend, &running_mean), 1e-12);
+        assert_all_close(&[0.9f64 + 0.1f64 * 5f64 / 3f64, 0.9f64 + 0.1f64 * 500f64 / 3f64], &read_tensor(&backend, &running_variance), 1e-12);
+    }
+
+    #[test]
+    fn it_normalizes_per_activation_in_inference_on_native() {
+        let backend = get_native_backend();
+        let conf = BatchNormalization::<f64>::new_batch_normalization_config(&backend, BatchNormMode::PerActivation, 1e-5, 0.1).unwrap();
+        let mut x = get_tensor(&backend, &[2, 2], &[1f64, 2f64, 3f64, 4f64]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 2)).unwrap();
+        let mut gamma = get_tensor(&backend, &[2], &[1f64, 2f64]);
+        let mut beta = get_tensor(&backend, &[2], &[0f64, 1f64]);
+        let mut running_mean = get_tensor(&backend, &[2], &[1f64, -1f64]);
+        let mut running_variance = get_tensor(&backend, &[2], &[4f64, 9f64]);
+        backend.batch_normalization_inference(&mut x, &mut result, &mut gamma, &mut beta, &mut running_mean, &mut running_variance, &conf).unwrap();
+
+        let (scale_0, scale_1) = (1f64 / (4f64 + 1e-5).sqrt(), 2f64 / (9f64 + 1e-5).sqrt());
+        assert_all_close(&[0f64, 3f64 * scale_1 + 1f64, 2f64 * scale_0, 5f64 * scale_1 + 1f64], &read_tensor(&backend, &result), 1e-12);
+    }
+
+    #[test]
+    fn it_computes_correct_spatial_batch_normalization_grad_on_native() {
+        check_gradient(BatchNormMode::Spatial);
+    }
+
+    #[test]
+    fn it_computes_correct_per_activation_batch_normalization_grad_on_native() {
+        check_gradient(BatchNormMode::PerActivation);
+    }
+
+    #[test]
+    fn it_rejects_invalid_batch_normalization_on_native() {
+        let backend = get_native_backend();
+        assert!(BatchNormalization::<f32>::new_batch_normalization_config(&backend, BatchNormMode::Spatial, 0f64, 0.1).is_err());
+        assert!(BatchNormalization::<f32>::new_batch_normalization_config(&backend, BatchNormMode::Spatial, 1e-5, 1.5).is_err());
+
+        let conf = BatchNormalization::<f64>::new_batch_normalization_config(&backend, BatchNormMode::Spatial, 1e-5, 0.1).unwrap();
+        let mut x = get_tensor(&backend, &[2, 3, 2], &random_data(12, 0));
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 3, 2)).unwrap();
+        let mut gamma = get_tensor(&backend, &[2], &[1f64, 1f64]);
+        let mut beta = get_tensor(&backend, &[2], &[0f64, 0f64]);
+        let mut running_mean = get_tensor(&backend, &[2], &[0f64, 0f64]);
+        let mut running_variance = get_tensor(&backend, &[2], &[1f64, 1f64]);
+        assert!(backend.batch_normalization_inference(&mut x, &mut result, &mut gamma, &mut beta, &mut running_mean, &mut running_variance, &conf).is_err());
+    }
+}
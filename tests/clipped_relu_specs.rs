@@ -0,0 +1,106 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod clipped_relu_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn get_tensor<T: Copy>(backend: &Backend<Native>, data: &[T]) -> SharedTensor<T> {
+        let mut x = SharedTensor::<T>::new(backend.device(), &(data.len())).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        x
+    }
+
+    fn read_tensor<T: Copy>(backend: &Backend<Native>, x: &SharedTensor<T>) -> Vec<T> {
+        x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<T>().to_vec()
+    }
+
+    /// Inputs below zero, exactly at zero, inside, exactly at the ceiling of 6 and above it.
+    const INPUTS: [f32; 7] = [-1.5f32, 0f32, 0.5f32, 3f32, 5.75f32, 6f32, 7.25f32];
+    const OUTPUTS: [f32; 7] = [0f32, 0f32, 0.5f32, 3f32, 5.75f32, 6f32, 6f32];
+    const DIFF: [f32; 7] = [1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32];
+    /// The gradient passes through only strictly between zero and the ceiling.
+    const GRADS: [f32; 7] = [0f32, 0f32, 3f32, 4f32, 5f32, 0f32, 0f32];
+
+    #[test]
+    fn it_computes_correct_clipped_relu_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &INPUTS);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(7)).unwrap();
+        backend.clipped_relu(&mut x, &mut result, 6.0).unwrap();
+        assert_eq!(OUTPUTS.to_vec(), read_tensor(&backend, &result));
+    }
+
+    #[test]
+    fn it_computes_correct_clipped_relu_on_native_for_f64() {
+        let backend = get_native_backend();
+        let inputs: Vec<f64> = INPUTS.iter().map(|&x| x as f64 / 6f64 * 0.3f64).collect();
+        let mut x = get_tensor(&backend, &inputs);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(7)).unwrap();
+        backend.clipped_relu(&mut x, &mut result, 0.3).unwrap();
+        let expected: Vec<f64> = inputs.iter().map(|x| x.max(0f64).min(0.3f64)).collect();
+        assert_eq!(expected, read_tensor(&backend, &result));
+    }
+
+    #[test]
+    fn it_computes_correct_clipped_relu_grad_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &OUTPUTS);
+        let mut x_diff = get_tensor(&backend, &DIFF);
+        let mut result = get_tensor(&backend, &INPUTS);
+        let mut result_diff = SharedTensor::<f32>::new(backend.device(), &(7)).unwrap();
+        backend.clipped_relu_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, 6.0).unwrap();
+        assert_eq!(GRADS.to_vec(), read_tensor(&backend, &result_diff));
+    }
+
+    #[test]
+    fn it_computes_correct_clipped_relu_pointwise_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &INPUTS);
+        backend.clipped_relu_pointwise(&mut x, 6.0).unwrap();
+        assert_eq!(OUTPUTS.to_vec(), read_tensor(&backend, &x));
+
+        let mut x_diff = get_tensor(&backend, &DIFF);
+        backend.clipped_relu_pointwise_grad(&mut x, &mut x_diff, 6.0).unwrap();
+        assert_eq!(GRADS.to_vec(), read_tensor(&backend, &x_diff));
+    }
+
+    #[test]
+    fn it_matches_the_clipped_relu_activation_mode_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &INPUTS);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(7)).unwrap();
+        backend.activation(ActivationMode::ClippedRelu(6.0), &mut x, &mut result).unwrap();
+        assert_eq!(OUTPUTS.to_vec(), read_tensor(&backend, &result));
+    }
+
+    #[test]
+    fn it_rejects_invalid_ceilings_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &INPUTS);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(7)).unwrap();
+        assert!(backend.clipped_relu(&mut x, &mut result, 0.0).is_err());
+        assert!(backend.clipped_relu(&mut x, &mut result, -6.0).is_err());
+        assert!(backend.clipped_relu_pointwise(&mut x, ::std::f64::INFINITY).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! Provides the helpers shared by the native specs.
+#![allow(dead_code)]
+
+use co::prelude::*;
+
+pub fn get_native_backend() -> Backend<Native> {
+    Backend::<Native>::default().unwrap()
+}
+
+pub fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+    match mem {
+        &mut MemoryType::Native(ref mut mem) => {
+            let mut mem_buffer = mem.as_mut_slice::<T>();
+            for (index, datum) in data.iter().enumerate() {
+                mem_buffer[index] = *datum;
+            }
+        },
+        #[cfg(any(feature = "opencl", feature = "cuda"))]
+        _ => {}
+    }
+}
+
+pub fn get_tensor(backend: &Backend<Native>, dims: &[usize], data: &[f64]) -> SharedTensor<f64> {
+    let mut x = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+    write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+    x
+}
+
+pub fn read_tensor(backend: &Backend<Native>, x: &SharedTensor<f64>) -> Vec<f64> {
+    x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().to_vec()
+}
+
+/// Returns `size` reproducible values in `[-2, 2)`.
+pub fn random_data(size: usize, seed: usize) -> Vec<f64> {
+    (0..size).map(|i| ((i * 7919 + seed) % 200) as f64 / 50f64 - 2f64).collect()
+}
+
+pub fn assert_all_close(expected: &[f64], actual: &[f64], tolerance: f64) {
+    assert_eq!(expected.len(), actual.len());
+    for (e, a) in expected.iter().zip(actual.iter()) {
+        assert!((e - a).abs() <= tolerance, "expected {:?}, got {:?}", expected, actual);
+    }
+}
+
+pub fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).fold(0f64, |acc, (a, b)| acc + a * b)
+}
+
+/// Approximates the gradient of `loss` at `values` by central differences.
+pub fn numeric_gradient(values: &[f64], loss: &Fn(&[f64]) -> f64) -> Vec<f64> {
+    let h = 1e-6;
+    (0..values.len()).map(|i| {
+        let (mut plus, mut minus) = (values.to_vec(), values.to_vec());
+        plus[i] += h;
+        minus[i] -= h;
+        (loss(&plus) - loss(&minus)) / (2f64 * h)
+    }).collect()
+}
@@ -1,6 +1,9 @@
 extern crate collenchyma_nn as co_nn;
 extern crate collenchyma as co;
 
+#[cfg(feature = "native")]
+mod common;
+
 #[cfg(test)]
 #[cfg(feature = "cuda")]
 mod convolution_spec_cuda {
@@ -123,7 +126,7 @@ mod convolution_spec_cuda {
         let native = get_native_backend();
         let (mut x, mut result, mut filter, mut workspace) = get_memory::<f32, Cuda, Native>(&backend, &native);
 
-        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0)).unwrap();
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
         match backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
             Ok(_) => {
                 result.sync(native.device()).unwrap();
@@ -143,7 +146,7 @@ mod convolution_spec_cuda {
         let native = get_native_backend();
         let (mut x, mut result, mut filter, mut workspace) = get_memory::<f64, Cuda, Native>(&backend, &native);
 
-        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0)).unwrap();
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
         match backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
             Ok(_) => {
                 result.sync(native.device()).unwrap();
@@ -163,7 +166,7 @@ mod convolution_spec_cuda {
         let native = get_native_backend();
         let (mut x, mut result, mut filter, mut workspace) = get_memory::<f32, Cuda, Native>(&backend, &native);
 
-        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0)).unwrap();
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
         match backend.convolution_plain(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
             Ok(_) => {
                 result.sync(native.device()).unwrap();
@@ -183,7 +186,7 @@ mod convolution_spec_cuda {
         let native = get_native_backend();
         let (mut x, mut result, mut filter, mut workspace) = get_memory::<f64, Cuda, Native>(&backend, &native);
 
-        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0)).unwrap();
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
         match backend.convolution_plain(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
             Ok(_) => {
                 result.sync(native.device()).unwrap();
@@ -276,170 +279,674 @@ Err(err) => { println!("{:?}", err); assert!(false) }
 #[cfg(feature = "native")]
 mod convolution_spec_native{
 
-    // use co::backend::{Backend, BackendConfig};
-    // use co::framework::IFramework;
-    // use co::frameworks::Native;
-    // use co_nn::*;
-    // use co::memory::MemoryType;
-    // use co::tensor::SharedTensor;
-    // use co::plugin::numeric_helpers::{cast, Float};
-    //
-    // fn get_native_backend() -> Backend<Native> {
-    //     let framework = Native::new();
-    //     let hardwares = framework.hardwares();
-    //     let backend_config = BackendConfig::new(framework, hardwares);
-    //     Backend::new(backend_config).unwrap()
-    // }
-    //
-    // fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
-    //     match mem {
-    //         &mut MemoryType::Native(ref mut mem) => {
-    //             let mut mem_buffer = mem.as_mut_slice::<T>();
-    //             for (index, datum) in data.iter().enumerate() {
-    //                 mem_buffer[index] = *datum;
-    //             }
-    //         },
-    //         #[cfg(any(feature = "opencl", feature = "cuda"))]
-    //         _ => {}
-    //     }
-    // }
-    //
-    // fn get_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
-    //     let val = cast::<f64, T>(1f64).unwrap();
-    //     let val2 = cast::<f64, T>(2f64).unwrap();
-    //     let batch = 4;
-    //     let w1 = 9;
-    //     let h1 = 9;
-    //     let d1 = 3;
-    //     let k = 6;
-    //     let f = 3;
-    //     let w2 = (w1 - f + 0) / 1;
-    //     let h2 = (h1 - f + 0) / 1;
-    //     let mut x = SharedTensor::<T>::new(backend.device(), &(batch, d1, h1, w1)).unwrap();
-    //     let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x.capacity()).collect::<Vec<T>>();
-    //     payload[0] = val2;
-    //     write_to_memory(x.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let mut filter = SharedTensor::<T>::new(backend.device(), &(k, d1, f, f)).unwrap();
-    //     let payload: &[T] = &::std::iter::repeat(val).take(filter.capacity()).collect::<Vec<T>>();
-    //     write_to_memory(filter.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let mut result = SharedTensor::<T>::new(backend.device(), &(batch, k, h2, w2)).unwrap();
-    //     let payload: &[T] = &::std::iter::repeat(val2).take(result.capacity()).collect::<Vec<T>>();
-    //     write_to_memory(result.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     (x, result, filter)
-    // }
-    //
-    // #[allow(dead_code)]
-    // fn get_grad_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
-    //     let val = cast::<f64, T>(1f64).unwrap();
-    //     let val2 = cast::<f64, T>(2f64).unwrap();
-    //     let batch = 4;
-    //     let w1 = 9;
-    //     let h1 = 9;
-    //     let d1 = 3;
-    //     let k = 6;
-    //     let f = 3;
-    //     let w2 = (w1 - f + 0) / 1;
-    //     let h2 = (h1 - f + 0) / 1;
-    //
-    //     let mut x = SharedTensor::<T>::new(backend.device(), &(batch, d1, h1, w1)).unwrap();
-    //     let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x.capacity()).collect::<Vec<T>>();
-    //     payload[0] = val2;
-    //     write_to_memory(x.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let mut x_diff = SharedTensor::<T>::new(backend.device(), &(batch, k, h2, w2)).unwrap();
-    //     let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x_diff.capacity()).collect::<Vec<T>>();
-    //     payload[0] = val2;
-    //     write_to_memory(x_diff.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let mut filter = SharedTensor::<T>::new(backend.device(), &(k, d1, f, f)).unwrap();
-    //     let payload: &[T] = &::std::iter::repeat(val).take(filter.capacity()).collect::<Vec<T>>();
-    //     write_to_memory(filter.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let mut result = SharedTensor::<T>::new(backend.device(), &(batch, k, h2, w2)).unwrap();
-    //     let payload: &[T] = &::std::iter::repeat(val).take(result.capacity()).collect::<Vec<T>>();
-    //     write_to_memory(result.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let result_diff = SharedTensor::<T>::new(backend.device(), &(batch, k, h2, w2)).unwrap();
-    //
-    //     (x, x_diff, result, result_diff, filter)
-    // }
-
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_convolution_on_native_for_f32() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result, mut filter) = get_memory::<f32, Native>(&backend);
-    //
-    //     let conf = backend.new_convolution_config(&x, &result, &mut filter, &vec!(1,1), &vec!(0,0)).unwrap();
-    //     match backend.convolution(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f32] = &mut ::std::iter::repeat(27f32).take(result.capacity()).collect::<Vec<f32>>();
-    //                 payload[0] = 28f32;
-    //                 assert_eq!(payload, mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_convolution_on_native_for_f64() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result, mut filter) = get_memory::<f64, Native>(&backend);
-    //
-    //     let conf = backend.new_convolution_config(&x, &result, &mut filter, &vec!(1,1), &vec!(0,0)).unwrap();
-    //     match backend.convolution(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f64] = &mut ::std::iter::repeat(27f64).take(result.capacity()).collect::<Vec<f64>>();
-    //                 payload[0] = 28f64;
-    //                 assert_eq!(payload, mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_convolution_on_native_for_f32_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result, mut filter) = get_memory::<f32, Native>(&backend);
-    //
-    //     let conf = backend.new_convolution_config(&x, &result, &mut filter, &vec!(1,1), &vec!(0,0)).unwrap();
-    //     match backend.convolution_plain(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f32] = &mut ::std::iter::repeat(27f32).take(result.capacity()).collect::<Vec<f32>>();
-    //                 payload[0] = 28f32;
-    //                 assert_eq!(payload, mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_convolution_on_native_for_f64_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result, mut filter) = get_memory::<f64, Native>(&backend);
-    //
-    //     let conf = backend.new_convolution_config(&x, &result, &mut filter, &vec!(1,1), &vec!(0,0)).unwrap();
-    //     match backend.convolution_plain(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f64] = &mut ::std::iter::repeat(27f64).take(result.capacity()).collect::<Vec<f64>>();
-    //                 payload[0] = 28f64;
-    //                 assert_eq!(payload, mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
+    use co::prelude::*;
+    use co_nn::*;
+    use co_nn::frameworks::native::{convolution, NativeThreads};
+    use co_nn::frameworks::native::helper::{self, ConvAlgo};
+    use co::plugin::numeric_helpers::{cast, Float};
+    use common::*;
+
+    fn get_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
+        let val = cast::<f64, T>(1f64).unwrap();
+        let val2 = cast::<f64, T>(2f64).unwrap();
+        let batch = 4;
+        let w1 = 9;
+        let h1 = 9;
+        let d1 = 3;
+        let k = 6;
+        let f = 3;
+        let w2 = (w1 - f + 0) / 1 + 1;
+        let h2 = (h1 - f + 0) / 1 + 1;
+        let mut x = SharedTensor::<T>::new(backend.device(), &(batch, d1, h1, w1)).unwrap();
+        let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x.capacity()).collect::<Vec<T>>();
+        payload[0] = val2;
+        write_to_memory(x.get_mut(backend.device()).unwrap(), payload);
+
+        let mut filter = SharedTensor::<T>::new(backend.device(), &(k, d1, f, f)).unwrap();
+        let payload: &[T] = &::std::iter::repeat(val).take(filter.capacity()).collect::<Vec<T>>();
+        write_to_memory(filter.get_mut(backend.device()).unwrap(), payload);
+
+        let mut result = SharedTensor::<T>::new(backend.device(), &(batch, k, h2, w2)).unwrap();
+        let payload: &[T] = &::std::iter::repeat(val2).take(result.capacity()).collect::<Vec<T>>();
+        write_to_memory(result.get_mut(backend.device()).unwrap(), payload);
+
+        (x, result, filter)
+    }
+
+    fn get_grad_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
+        let vals = [1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64, 8f64, 9f64].iter().map(|v| cast::<f64, T>(*v).unwrap()).collect::<Vec<T>>();
+
+        let mut x = SharedTensor::<T>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &vals);
+
+        let mut x_diff = SharedTensor::<T>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        write_to_memory(x_diff.get_mut(backend.device()).unwrap(), &vals[..4]);
+
+        let mut filter = SharedTensor::<T>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        write_to_memory(filter.get_mut(backend.device()).unwrap(), &vals[..4]);
+
+        let filter_diff = SharedTensor::<T>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        let result_diff = SharedTensor::<T>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+
+        (x, x_diff, filter, filter_diff, result_diff)
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut x, mut result, mut filter) = get_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        match backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f32] = &mut ::std::iter::repeat(27f32).take(result.capacity()).collect::<Vec<f32>>();
+                    for k in 0..6 {
+                        payload[k * 49] = 28f32;
+                    }
+                    assert_eq!(payload, mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut x, mut result, mut filter) = get_memory::<f64, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        match backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f64] = &mut ::std::iter::repeat(27f64).take(result.capacity()).collect::<Vec<f64>>();
+                    for k in 0..6 {
+                        payload[k * 49] = 28f64;
+                    }
+                    assert_eq!(payload, mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_on_native_for_f32_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut result, mut filter) = get_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        match backend.convolution_plain(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f32] = &mut ::std::iter::repeat(27f32).take(result.capacity()).collect::<Vec<f32>>();
+                    for k in 0..6 {
+                        payload[k * 49] = 28f32;
+                    }
+                    assert_eq!(payload, mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut result, mut filter) = get_memory::<f64, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        match backend.convolution_plain(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f64] = &mut ::std::iter::repeat(27f64).take(result.capacity()).collect::<Vec<f64>>();
+                    for k in 0..6 {
+                        payload[k * 49] = 28f64;
+                    }
+                    assert_eq!(payload, mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_filter_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut filter, mut filter_diff, _) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_filter(&mut x, &mut x_diff, &mut filter_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = filter_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[37f32, 47f32, 67f32, 77f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_data_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (x, mut x_diff, mut filter, _, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_data(&mut filter, &mut x_diff, &mut result_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[1f32, 4f32, 4f32, 6f32, 20f32, 16f32, 9f32, 24f32, 16f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_filter_on_native_for_f32_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut filter, mut filter_diff, _) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_filter_plain(&mut x, &mut x_diff, &mut filter_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = filter_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[37f32, 47f32, 67f32, 77f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_data_on_native_for_f32_plain() {
+        let backend = get_native_backend();
+        let (x, mut x_diff, mut filter, _, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_data_plain(&mut filter, &mut x_diff, &mut result_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[1f32, 4f32, 4f32, 6f32, 20f32, 16f32, 9f32, 24f32, 16f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_filter_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut filter, mut filter_diff, _) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_filter(&mut x, &mut x_diff, &mut filter_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = filter_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[37f64, 47f64, 67f64, 77f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_data_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (x, mut x_diff, mut filter, _, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_data(&mut filter, &mut x_diff, &mut result_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[1f64, 4f64, 4f64, 6f64, 20f64, 16f64, 9f64, 24f64, 16f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_filter_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut filter, mut filter_diff, _) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_filter_plain(&mut x, &mut x_diff, &mut filter_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = filter_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[37f64, 47f64, 67f64, 77f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_data_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (x, mut x_diff, mut filter, _, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &x_diff, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        match backend.convolution_grad_data_plain(&mut filter, &mut x_diff, &mut result_diff, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[1f64, 4f64, 4f64, 6f64, 20f64, 16f64, 9f64, 24f64, 16f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_reports_the_im2col_workspace_size_on_native() {
+        let backend = get_native_backend();
+        let (x, result, mut filter) = get_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        assert_eq!(3 * 3 * 3 * 7 * 7 * 4, ConvolutionConfig::<f32>::workspace_size(&conf));
+        assert_eq!(3 * 3 * 3 * 7 * 7 * 8, ConvolutionConfig::<f64>::workspace_size(&conf));
+    }
+
+    #[test]
+    fn it_rejects_a_too_small_workspace_on_native() {
+        let backend = get_native_backend();
+        let (mut x, mut result, mut filter) = get_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(4)).unwrap();
+        assert!(backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf).is_err());
+    }
+
+    #[test]
+    fn it_computes_correct_padded_strided_convolution_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+        let mut filter = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        write_to_memory(filter.get_mut(backend.device()).unwrap(), &[1f32, 1f32, 1f32, 1f32]);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(2,2), &vec!(1,1), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        match backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[1f32, 5f32, 11f32, 28f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_rejects_mismatching_convolution_shapes_on_native() {
+        let backend = get_native_backend();
+        let (x, _, mut filter) = get_memory::<f32, Native>(&backend);
+        let result = SharedTensor::<f32>::new(backend.device(), &(4, 6, 9, 9)).unwrap();
+
+        assert!(backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).is_err());
+    }
+
+    #[test]
+    fn it_rejects_a_filter_of_another_shape_on_native() {
+        let backend = get_native_backend();
+        let (mut x, mut result, mut filter) = get_memory::<f32, Native>(&backend);
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        let mut x_diff = SharedTensor::<f32>::new(backend.device(), &(4, 3, 9, 9)).unwrap();
+
+        // the filter has the configured number of elements, but a 1x9 window.
+        let mut wide_filter = SharedTensor::<f32>::new(backend.device(), &(6, 3, 1, 9)).unwrap();
+        write_to_memory(wide_filter.get_mut(backend.device()).unwrap(), &vec![1f32; 6 * 3 * 9]);
+        assert!(backend.convolution(&mut wide_filter, &mut x, &mut result, &mut workspace, &conf).is_err());
+        assert!(backend.convolution_grad_filter(&mut x, &mut result, &mut wide_filter, &mut workspace, &conf).is_err());
+        assert!(backend.convolution_grad_data(&mut wide_filter, &mut result, &mut x_diff, &mut workspace, &conf).is_err());
+    }
+
+    fn len(dims: &[usize]) -> usize {
+        dims.iter().fold(1, |acc, d| acc * d)
+    }
+
+    /// Runs all three passes of a convolution with `filter_dims`, `stride`, `dilation` and `groups` and a padding of `(1, 0)`
+    /// and returns the output, filter gradient and data gradient.
+    fn run_algos(backend: &Backend<Native>, filter_dims: &[usize], stride: &[i32], dilation: &[i32], groups: usize, algo_fwd: ConvForwardAlgo,
+                 algo_bwd_filter: ConvBackwardFilterAlgo, algo_bwd_data: ConvBackwardDataAlgo) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let out_h = (7 + 2 - (filter_dims[2] - 1) * dilation[0] as usize - 1) / stride[0] as usize + 1;
+        let out_w = (6 - (filter_dims[3] - 1) * dilation[1] as usize - 1) / stride[1] as usize + 1;
+        let (channels, out_channels) = (filter_dims[1] * groups, filter_dims[0]);
+        let mut x = get_tensor(backend, &[2, channels, 7, 6], &random_data(len(&[2, channels, 7, 6]), 0));
+        let mut filter = get_tensor(backend, filter_dims, &random_data(len(filter_dims), 13));
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, out_channels, out_h, out_w)).unwrap();
+        let mut result_diff = get_tensor(backend, &[2, out_channels, out_h, out_w], &random_data(len(&[2, out_channels, out_h, out_w]), 29));
+        let mut filter_diff = SharedTensor::<f64>::new(backend.device(), &filter_dims.to_vec()).unwrap();
+        let mut x_diff = SharedTensor::<f64>::new(backend.device(), &(2, channels, 7, 6)).unwrap();
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, algo_fwd, algo_bwd_filter, algo_bwd_data, stride, &vec!(1,0), dilation, groups).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf).unwrap();
+        backend.convolution_grad_filter(&mut x, &mut result_diff, &mut filter_diff, &mut workspace, &conf).unwrap();
+        backend.convolution_grad_data(&mut filter, &mut result_diff, &mut x_diff, &mut workspace, &conf).unwrap();
+        (read_tensor(backend, &result), read_tensor(backend, &filter_diff), read_tensor(backend, &x_diff))
+    }
+
+    #[test]
+    fn it_computes_equal_results_for_all_native_convolution_algos() {
+        let backend = get_native_backend();
+        let (output, filter_diff, data_diff) = run_algos(&backend, &[4, 3, 3, 2], &[2, 1], &[1, 1], 1, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM);
+        let algos = [(ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM),
+                     (ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::GEMM, ConvBackwardDataAlgo::GEMM),
+                     (ConvForwardAlgo::ImplicitPrecompiledGEMM, ConvBackwardFilterAlgo::FFT, ConvBackwardDataAlgo::FFT),
+                     (ConvForwardAlgo::FFT, ConvBackwardFilterAlgo::FFT, ConvBackwardDataAlgo::FFT),
+                     (ConvForwardAlgo::Auto, ConvBackwardFilterAlgo::Auto, ConvBackwardDataAlgo::Auto)];
+        for &(algo_fwd, algo_bwd_filter, algo_bwd_data) in &algos {
+            let (other_output, other_filter_diff, other_data_diff) = run_algos(&backend, &[4, 3, 3, 2], &[2, 1], &[1, 1], 1, algo_fwd, algo_bwd_filter, algo_bwd_data);
+            assert_all_close(&output, &other_output, 1e-9);
+            assert_all_close(&filter_diff, &other_filter_diff, 1e-9);
+            assert_all_close(&data_diff, &other_data_diff, 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_computes_equal_results_for_winograd_on_native() {
+        let backend = get_native_backend();
+        let (output, filter_diff, data_diff) = run_algos(&backend, &[4, 3, 3, 3], &[1, 1], &[1, 1], 1, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM);
+        for &algo_fwd in &[ConvForwardAlgo::Winograd, ConvForwardAlgo::Auto] {
+            let (other_output, other_filter_diff, other_data_diff) = run_algos(&backend, &[4, 3, 3, 3], &[1, 1], &[1, 1], 1, algo_fwd, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM);
+            assert_all_close(&output, &other_output, 1e-9);
+            assert_all_close(&filter_diff, &other_filter_diff, 1e-9);
+            assert_all_close(&data_diff, &other_data_diff, 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_dilated_convolution_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f32>::new(backend.device(), &(1, 1, 5, 5)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &(1..26).map(|v| v as f32).collect::<Vec<f32>>());
+        let mut filter = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        write_to_memory(filter.get_mut(backend.device()).unwrap(), &[1f32, 1f32, 1f32, 1f32]);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+
+        // the dilated 2x2 filter sums the corners of every 3x3 window.
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(2,2), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+            assert_eq!(&[28f32, 32f32, 36f32, 48f32, 52f32, 56f32, 68f32, 72f32, 76f32], mem.as_slice::<f32>());
+        }
+    }
+
+    #[test]
+    fn it_computes_equal_results_for_dilated_native_convolution_algos() {
+        let backend = get_native_backend();
+        let (output, filter_diff, data_diff) = run_algos(&backend, &[4, 3, 3, 2], &[1, 2], &[2, 3], 1, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM);
+        let algos = [(ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::Auto, ConvBackwardDataAlgo::Auto),
+                     (ConvForwardAlgo::FFT, ConvBackwardFilterAlgo::FFT, ConvBackwardDataAlgo::FFT),
+                     (ConvForwardAlgo::Auto, ConvBackwardFilterAlgo::Auto, ConvBackwardDataAlgo::Auto)];
+        for &(algo_fwd, algo_bwd_filter, algo_bwd_data) in &algos {
+            let (other_output, other_filter_diff, other_data_diff) = run_algos(&backend, &[4, 3, 3, 2], &[1, 2], &[2, 3], 1, algo_fwd, algo_bwd_filter, algo_bwd_data);
+            assert_all_close(&output, &other_output, 1e-9);
+            assert_all_close(&filter_diff, &other_filter_diff, 1e-9);
+            assert_all_close(&data_diff, &other_data_diff, 1e-9);
+        }
+
+        // the gradients are the adjoints of the convolution: <conv(w, x), dy> = <w, grad_filter(x, dy)> = <x, grad_data(w, dy)>.
+        let x = random_data(len(&[2, 3, 7, 6]), 0);
+        let filter = random_data(len(&[4, 3, 3, 2]), 13);
+        let output_diff = random_data(len(&[2, 4, 5, 2]), 29);
+        assert_all_close(&[dot(&output, &output_diff)], &[dot(&filter, &filter_diff)], 1e-9);
+        assert_all_close(&[dot(&output, &output_diff)], &[dot(&x, &data_diff)], 1e-9);
+    }
+
+    #[test]
+    fn it_rejects_invalid_dilations_on_native() {
+        let backend = get_native_backend();
+        let x = SharedTensor::<f32>::new(backend.device(), &(1, 1, 5, 5)).unwrap();
+        let mut filter = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+        let undilated = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+        let dilated = SharedTensor::<f32>::new(backend.device(), &(1, 1, 1, 1)).unwrap();
+        let new_config = |dest: &SharedTensor<f32>, filter: &mut SharedTensor<f32>, algo_fwd: ConvForwardAlgo, dilation: &[i32]| {
+            backend.new_convolution_config(&x, dest, filter, algo_fwd, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), dilation, 1)
+        };
+
+        assert!(new_config(&dilated, &mut filter, ConvForwardAlgo::GEMM, &[2, 2]).is_ok());
+        // the output shape accounts for the dilation.
+        assert!(new_config(&undilated, &mut filter, ConvForwardAlgo::GEMM, &[2, 2]).is_err());
+        assert!(new_config(&dilated, &mut filter, ConvForwardAlgo::GEMM, &[3, 3]).is_err());
+        assert!(new_config(&dilated, &mut filter, ConvForwardAlgo::GEMM, &[0, 2]).is_err());
+        assert!(new_config(&dilated, &mut filter, ConvForwardAlgo::GEMM, &[2]).is_err());
+        // Winograd only computes undilated filters.
+        assert!(new_config(&dilated, &mut filter, ConvForwardAlgo::Winograd, &[2, 2]).is_err());
+        assert!(new_config(&dilated, &mut filter, ConvForwardAlgo::Auto, &[2, 2]).is_ok());
+    }
+
+    /// Runs all three passes of the native kernels with `algo` on `config` for the filter `weights`
+    /// and returns the output, filter gradient and data gradient.
+    fn run_kernels(config: &mut helper::ConvolutionConfig, algo: ConvAlgo, weights: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        config.forward_algo = algo;
+        config.backward_filter_algo = algo;
+        config.backward_data_algo = algo;
+        let src = config.input_shape.clone();
+        let (out_h, out_w) = config.output_dims(src[2], src[3]).unwrap();
+        let dest = [src[0], config.filter_shape[0], out_h, out_w];
+        let dims = config.check_shapes(&src, &dest, &config.filter_shape).unwrap();
+        let (input, output_diff) = (random_data(len(&src), 0), random_data(len(&dest), 29));
+        let mut workspace = vec![0f64; config.workspace_len()];
+        let (mut output, mut weights_diff, mut input_diff) = (vec![0f64; len(&dest)], vec![0f64; weights.len()], vec![0f64; input.len()]);
+        let backend = get_native_backend();
+        let pool = backend.thread_pool();
+        convolution::forward(pool, config, dims, weights, &input, &mut output, &mut workspace);
+        convolution::backward_filter(pool, config, dims, &input, &output_diff, &mut weights_diff, &mut workspace);
+        convolution::backward_data(pool, config, dims, weights, &output_diff, &mut input_diff, &mut workspace);
+        (output, weights_diff, input_diff)
+    }
+
+    #[test]
+    fn it_computes_grouped_convolution_as_block_diagonal_filter_on_native() {
+        // 6 input and 6 output channels in 3 groups of 2.
+        let (groups, out_channels, group_channels, filter_size) = (3, 6, 2, 3 * 2);
+        let dense_index = |k: usize, c: usize, i: usize| (k * groups * group_channels + k / (out_channels / groups) * group_channels + c) * filter_size + i;
+        let weights = random_data(out_channels * group_channels * filter_size, 13);
+        let mut dense_weights = vec![0f64; out_channels * groups * group_channels * filter_size];
+        for (index, weight) in weights.iter().enumerate() {
+            dense_weights[dense_index(index / (group_channels * filter_size), index / filter_size % group_channels, index % filter_size)] = *weight;
+        }
+        let mut dense = helper::ConvolutionConfig::new(&[2, 6, 7, 6], &[6, 6, 3, 2], &[2, 1], &[1, 0], &[1, 2], 1).unwrap();
+        let (output, dense_weights_diff, input_diff) = run_kernels(&mut dense, ConvAlgo::GEMM, &dense_weights);
+        let weights_diff: Vec<f64> = (0..weights.len())
+            .map(|index| dense_weights_diff[dense_index(index / (group_channels * filter_size), index / filter_size % group_channels, index % filter_size)])
+            .collect();
+
+        let mut grouped = helper::ConvolutionConfig::new(&[2, 6, 7, 6], &[6, 2, 3, 2], &[2, 1], &[1, 0], &[1, 2], groups).unwrap();
+        for &algo in &[ConvAlgo::GEMM, ConvAlgo::ImplicitGEMM, ConvAlgo::FFT] {
+            let (grouped_output, grouped_weights_diff, grouped_input_diff) = run_kernels(&mut grouped, algo, &weights);
+            assert_all_close(&output, &grouped_output, 1e-9);
+            assert_all_close(&weights_diff, &grouped_weights_diff, 1e-9);
+            assert_all_close(&input_diff, &grouped_input_diff, 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_computes_grouped_winograd_convolution_on_native() {
+        let weights = random_data(6 * 2 * 9, 13);
+        let mut config = helper::ConvolutionConfig::new(&[2, 4, 7, 6], &[6, 2, 3, 3], &[1, 1], &[1, 1], &[1, 1], 2).unwrap();
+        let (output, _, _) = run_kernels(&mut config, ConvAlgo::ImplicitGEMM, &weights);
+        config.forward_algo = ConvAlgo::Winograd;
+        let dims = config.check_shapes(&[2, 4, 7, 6], &[2, 6, 7, 6], &[6, 2, 3, 3]).unwrap();
+        let mut workspace = vec![0f64; config.workspace_len()];
+        let mut winograd_output = vec![0f64; output.len()];
+        convolution::forward(get_native_backend().thread_pool(), &config, dims, &weights, &random_data(2 * 4 * 7 * 6, 0), &mut winograd_output, &mut workspace);
+        assert_all_close(&output, &winograd_output, 1e-9);
+    }
+
+    #[test]
+    fn it_computes_equal_results_for_the_depthwise_native_convolution() {
+        // one and two output channels per input channel, with the filter crossing every border.
+        let cases: [(&[usize], &[i32], &[i32], &[i32]); 4] = [(&[3, 1, 3, 3], &[1, 1], &[1, 1], &[1, 1]),
+                                                              (&[6, 1, 3, 3], &[1, 1], &[1, 1], &[1, 1]),
+                                                              (&[3, 1, 3, 3], &[2, 1], &[0, 2], &[1, 1]),
+                                                              (&[6, 1, 3, 3], &[1, 2], &[2, 1], &[2, 1])];
+        for &(filter_shape, stride, padding, dilation) in cases.iter() {
+            let weights = random_data(filter_shape[0] * 9, 13);
+            let mut config = helper::ConvolutionConfig::new(&[2, 3, 7, 6], filter_shape, stride, padding, dilation, 3).unwrap();
+            assert!(config.supports_depthwise());
+            let (output, weights_diff, input_diff) = run_kernels(&mut config, ConvAlgo::ImplicitGEMM, &weights);
+            let (depthwise_output, depthwise_weights_diff, depthwise_input_diff) = run_kernels(&mut config, ConvAlgo::Depthwise, &weights);
+            assert_all_close(&output, &depthwise_output, 1e-9);
+            assert_all_close(&weights_diff, &depthwise_weights_diff, 1e-9);
+            assert_all_close(&input_diff, &depthwise_input_diff, 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_computes_equal_results_for_grouped_native_convolution_algos() {
+        let backend = get_native_backend();
+        let (output, filter_diff, data_diff) = run_algos(&backend, &[6, 2, 3, 2], &[2, 1], &[1, 1], 3, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM);
+        let (other_output, other_filter_diff, other_data_diff) = run_algos(&backend, &[6, 2, 3, 2], &[2, 1], &[1, 1], 3, ConvForwardAlgo::Auto, ConvBackwardFilterAlgo::Auto, ConvBackwardDataAlgo::Auto);
+        assert_all_close(&output, &other_output, 1e-9);
+        assert_all_close(&filter_diff, &other_filter_diff, 1e-9);
+        assert_all_close(&data_diff, &other_data_diff, 1e-9);
+
+        // the Direct forward algorithm is the depthwise fast path.
+        let (output, filter_diff, data_diff) = run_algos(&backend, &[6, 1, 3, 3], &[1, 1], &[1, 1], 3, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM);
+        let (other_output, other_filter_diff, other_data_diff) = run_algos(&backend, &[6, 1, 3, 3], &[1, 1], &[1, 1], 3, ConvForwardAlgo::Direct, ConvBackwardFilterAlgo::Auto, ConvBackwardDataAlgo::Auto);
+        assert_all_close(&output, &other_output, 1e-9);
+        assert_all_close(&filter_diff, &other_filter_diff, 1e-9);
+        assert_all_close(&data_diff, &other_data_diff, 1e-9);
+    }
+
+    #[test]
+    fn it_rejects_invalid_groups_on_native() {
+        let backend = get_native_backend();
+        let x = SharedTensor::<f32>::new(backend.device(), &(1, 4, 5, 5)).unwrap();
+        let result = SharedTensor::<f32>::new(backend.device(), &(1, 4, 3, 3)).unwrap();
+        let new_config = |filter_dims: (usize, usize, usize, usize), algo_fwd: ConvForwardAlgo, groups: usize| {
+            let mut filter = SharedTensor::<f32>::new(backend.device(), &filter_dims).unwrap();
+            backend.new_convolution_config(&x, &result, &mut filter, algo_fwd, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), groups)
+        };
+
+        assert!(new_config((4, 2, 3, 3), ConvForwardAlgo::GEMM, 2).is_ok());
+        assert!(new_config((4, 1, 3, 3), ConvForwardAlgo::Direct, 4).is_ok());
+        assert!(new_config((4, 2, 3, 3), ConvForwardAlgo::GEMM, 0).is_err());
+        assert!(new_config((4, 2, 3, 3), ConvForwardAlgo::GEMM, 3).is_err());
+        // the filter holds the input channels of one group.
+        assert!(new_config((4, 4, 3, 3), ConvForwardAlgo::GEMM, 2).is_err());
+        // the Direct forward algorithm is only available for depthwise 3x3 filters.
+        assert!(new_config((4, 2, 3, 3), ConvForwardAlgo::Direct, 2).is_err());
+    }
+
+    #[test]
+    fn it_reports_the_winograd_workspace_size_on_native() {
+        let backend = get_native_backend();
+        let (x, result, mut filter) = get_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::Winograd, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        // 16 transformed positions of 6x3 filters, 3 input and 6 output channels of 4x4 tiles.
+        assert_eq!(16 * (6 * 3 + 3 * 16 + 6 * 16) * 4, ConvolutionConfig::<f32>::workspace_size(&conf));
+    }
+
+    #[test]
+    fn it_falls_back_from_winograd_for_other_filters_on_native() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+        let mut filter = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+        write_to_memory(filter.get_mut(backend.device()).unwrap(), &[1f32, 1f32, 1f32, 1f32]);
+
+        assert!(backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::Winograd, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).is_err());
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::Auto, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conf))).unwrap();
+        backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+            assert_eq!(&[12f32, 16f32, 24f32, 28f32], mem.as_slice::<f32>());
+        }
+    }
+
+    #[test]
+    fn it_needs_no_workspace_for_implicit_gemm_on_native() {
+        let backend = get_native_backend();
+        let (x, result, mut filter) = get_memory::<f32, Native>(&backend);
+
+        let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+        assert_eq!(0, ConvolutionConfig::<f32>::workspace_size(&conf));
+        // the explicit GEMM backward passes need a column buffer.
+        for &(algo_bwd_filter, algo_bwd_data) in &[(ConvBackwardFilterAlgo::GEMM, ConvBackwardDataAlgo::ImplicitGEMM),
+                                                   (ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::GEMM)] {
+            let conf = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, algo_bwd_filter, algo_bwd_data, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).unwrap();
+            assert!(ConvolutionConfig::<f32>::workspace_size(&conf) > 0);
+        }
+    }
+
+    #[test]
+    fn it_rejects_unsupported_convolution_algos_on_native() {
+        let backend = get_native_backend();
+        let (x, result, mut filter) = get_memory::<f32, Native>(&backend);
+
+        let algos = [(ConvForwardAlgo::FFTTiling, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM),
+                     (ConvForwardAlgo::Direct, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMM),
+                     (ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMMSum, ConvBackwardDataAlgo::ImplicitGEMM),
+                     (ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitPrecompiledGEMMSum, ConvBackwardDataAlgo::ImplicitGEMM),
+                     (ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::ImplicitGEMMSum),
+                     (ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM, ConvBackwardDataAlgo::FFTTiling)];
+        for &(algo_fwd, algo_bwd_filter, algo_bwd_data) in &algos {
+            assert!(backend.new_convolution_config(&x, &result, &mut filter, algo_fwd, algo_bwd_filter, algo_bwd_data, &vec!(1,1), &vec!(0,0), &vec!(1,1), 1).is_err());
+        }
+    }
+
+    #[test]
+    fn it_adds_the_convolution_bias_per_channel_on_native() {
+        let backend = get_native_backend();
+        let mut bias = SharedTensor::<f64>::new(backend.device(), &(3)).unwrap();
+        write_to_memory(bias.get_mut(backend.device()).unwrap(), &[1f64, -2f64, 0.5f64]);
+        let mut result = get_tensor(&backend, &[2, 3, 2, 2], &random_data(len(&[2, 3, 2, 2]), 0));
+        let before = read_tensor(&backend, &result);
+        backend.convolution_bias(&mut bias, &mut result).unwrap();
+
+        let expected: Vec<f64> = before.iter().enumerate().map(|(i, v)| v + [1f64, -2f64, 0.5f64][(i / 4) % 3]).collect();
+        assert_all_close(&expected, &read_tensor(&backend, &result), 1e-9);
+    }
+
+    #[test]
+    fn it_computes_correct_convolution_grad_bias_on_native() {
+        let backend = get_native_backend();
+        let mut result_diff = SharedTensor::<f64>::new(backend.device(), &(2, 2, 1, 3)).unwrap();
+        write_to_memory(result_diff.get_mut(backend.device()).unwrap(),
+                        &[1f64, 2f64, 3f64, 10f64, 20f64, 30f64, 4f64, 5f64, 6f64, 40f64, 50f64, 60f64]);
+        let mut bias_diff = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        backend.convolution_grad_bias(&mut result_diff, &mut bias_diff).unwrap();
+        assert_all_close(&[21f64, 210f64], &read_tensor(&backend, &bias_diff), 1e-9);
+    }
+
+    #[test]
+    fn it_rejects_a_mismatching_convolution_bias_on_native() {
+        let backend = get_native_backend();
+        let mut bias = SharedTensor::<f32>::new(backend.device(), &(4)).unwrap();
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(2, 3, 2, 2)).unwrap();
+        assert!(backend.convolution_bias(&mut bias, &mut result).is_err());
+        assert!(backend.convolution_grad_bias(&mut result, &mut bias).is_err());
+    }
+
+    #[test]
+    fn it_rejects_a_convolution_bias_without_spatial_elements_on_native() {
+        let backend = get_native_backend();
+        let mut bias = SharedTensor::<f32>::new(backend.device(), &(3)).unwrap();
+        let mut result = SharedTensor::<f32>::new(backend.device(), &vec![2, 3, 0, 2]).unwrap();
+        assert!(backend.convolution_bias(&mut bias, &mut result).is_err());
+        assert!(backend.convolution_grad_bias(&mut result, &mut bias).is_err());
+    }
 }
@@ -0,0 +1,122 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod dropout_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn get_tensor(backend: &Backend<Native>, data: &[f32]) -> SharedTensor<f32> {
+        let mut x = SharedTensor::<f32>::new(backend.device(), &(data.len())).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        x
+    }
+
+    fn read_tensor(backend: &Backend<Native>, x: &SharedTensor<f32>) -> Vec<f32> {
+        x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f32>().to_vec()
+    }
+
+    /// Runs a dropout forward pass for every mask index below `masks` and returns every mask.
+    fn draw_masks(backend: &Backend<Native>, probability: f64, seed: u64, masks: u64) -> Vec<Vec<f32>> {
+        let config = Dropout::<f32>::new_dropout_config(backend, probability, seed).unwrap();
+        let mut x = get_tensor(backend, &vec![1f32; 1000]);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(1000)).unwrap();
+        let mut mask = SharedTensor::<f32>::new(backend.device(), &(1000)).unwrap();
+        (0..masks).map(|index| {
+            backend.dropout(&mut x, &mut result, &mut mask, index, &config).unwrap();
+            read_tensor(backend, &mask)
+        }).collect()
+    }
+
+    #[test]
+    fn it_draws_reproducible_masks_on_native() {
+        let backend = get_native_backend();
+        let masks = draw_masks(&backend, 0.5, 42, 3);
+        assert_eq!(masks, draw_masks(&backend, 0.5, 42, 3));
+        assert!(masks[0] != masks[1] && masks[1] != masks[2]);
+        assert!(masks[0] != draw_masks(&backend, 0.5, 43, 1)[0]);
+    }
+
+    #[test]
+    fn it_draws_the_same_mask_for_the_same_index_on_native() {
+        let backend = get_native_backend();
+        let config = Dropout::<f32>::new_dropout_config(&backend, 0.5, 42).unwrap();
+        let copy = config.clone();
+        let mut x = get_tensor(&backend, &vec![1f32; 1000]);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(1000)).unwrap();
+        let mut mask = SharedTensor::<f32>::new(backend.device(), &(1000)).unwrap();
+        // the mask only depends on the seed and the index, not on earlier passes or the config instance.
+        backend.dropout(&mut x, &mut result, &mut mask, 1, &config).unwrap();
+        let first = read_tensor(&backend, &mask);
+        backend.dropout(&mut x, &mut result, &mut mask, 1, &config).unwrap();
+        assert_eq!(first, read_tensor(&backend, &mask));
+        backend.dropout(&mut x, &mut result, &mut mask, 1, &copy).unwrap();
+        assert_eq!(first, read_tensor(&backend, &mask));
+        assert_eq!(draw_masks(&backend, 0.5, 42, 2)[1], first);
+    }
+
+    #[test]
+    fn it_drops_the_configured_fraction_on_native() {
+        let backend = get_native_backend();
+        for mask in draw_masks(&backend, 0.25, 7, 4) {
+            let dropped = mask.iter().filter(|&&m| m == 0f32).count();
+            assert!(dropped > 200 && dropped < 300, "dropped {} of 1000", dropped);
+            assert!(mask.iter().all(|&m| m == 0f32 || m == 1f32 / 0.75f32));
+        }
+        assert!(draw_masks(&backend, 0.0, 7, 1)[0].iter().all(|&m| m == 1f32));
+    }
+
+    #[test]
+    fn it_computes_correct_dropout_on_native() {
+        let backend = get_native_backend();
+        let config = Dropout::<f32>::new_dropout_config(&backend, 0.5, 1).unwrap();
+        let data: Vec<f32> = (0..64).map(|i| i as f32).collect();
+        let mut x = get_tensor(&backend, &data);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(64)).unwrap();
+        let mut mask = SharedTensor::<f32>::new(backend.device(), &(64)).unwrap();
+        backend.dropout(&mut x, &mut result, &mut mask, 0, &config).unwrap();
+        let mask_values = read_tensor(&backend, &mask);
+        let expected: Vec<f32> = data.iter().zip(mask_values.iter()).map(|(x, m)| x * m).collect();
+        assert_eq!(expected, read_tensor(&backend, &result));
+
+        // the backward pass reuses the mask of the forward pass.
+        let diff: Vec<f32> = (0..64).map(|i| 1f32 + i as f32 / 8f32).collect();
+        let mut x_diff = get_tensor(&backend, &diff);
+        let mut result_diff = SharedTensor::<f32>::new(backend.device(), &(64)).unwrap();
+        backend.dropout_grad(&mut x_diff, &mut mask, &mut result_diff, &config).unwrap();
+        let expected: Vec<f32> = diff.iter().zip(mask_values.iter()).map(|(dy, m)| dy * m).collect();
+        assert_eq!(expected, read_tensor(&backend, &result_diff));
+    }
+
+    #[test]
+    fn it_rejects_invalid_dropout_on_native() {
+        let backend = get_native_backend();
+        assert!(Dropout::<f32>::new_dropout_config(&backend, 1.0, 1).is_err());
+        assert!(Dropout::<f32>::new_dropout_config(&backend, -0.1, 1).is_err());
+
+        let config = Dropout::<f32>::new_dropout_config(&backend, 0.5, 1).unwrap();
+        let mut x = get_tensor(&backend, &[1f32, 2f32]);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(2)).unwrap();
+        let mut mask = SharedTensor::<f32>::new(backend.device(), &(3)).unwrap();
+        assert!(backend.dropout(&mut x, &mut result, &mut mask, 0, &config).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(feature = "native")]
+mod common;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod group_normalization_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+    use common::*;
+
+    /// Runs the group normalization `conf` with `stats` groups per sample over the NCHW input `x`
+    /// with the per-channel `gamma` and `beta` and returns the output and the saved mean and inverse standard deviation.
+    fn group_norm(backend: &Backend<Native>, conf: &<Backend<Native> as GroupNormalization<f64>>::CGN, stats: usize,
+                  dims: &[usize], x: &[f64], gamma: &[f64], beta: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let channels = [dims[1]];
+        let mut x = get_tensor(backend, dims, x);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        let mut gamma = get_tensor(backend, &channels, gamma);
+        let mut beta = get_tensor(backend, &channels, beta);
+        let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &vec![dims[0] * stats]).unwrap();
+        let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &vec![dims[0] * stats]).unwrap();
+        backend.group_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, conf).unwrap();
+        (read_tensor(backend, &result), read_tensor(backend, &saved_mean), read_tensor(backend, &saved_inv_variance))
+    }
+
+    /// Runs an unscaled layer normalization over the last `normalized_dims` dimensions of `x`
+    /// and returns the output and the saved mean and inverse standard deviation.
+    fn layer_norm(backend: &Backend<Native>, dims: &[usize], normalized_dims: usize, x: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let conf = LayerNormalization::<f64>::new_layer_normalization_config(backend, normalized_dims, 1e-5).unwrap();
+        let features = dims[dims.len() - normalized_dims..].iter().fold(1, |acc, d| acc * d);
+        let samples = vec![x.len() / features];
+        let mut x = get_tensor(backend, dims, x);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        let mut gamma = get_tensor(backend, &[features], &vec![1f64; features]);
+        let mut beta = get_tensor(backend, &[features], &vec![0f64; features]);
+        let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &samples).unwrap();
+        let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &samples).unwrap();
+        backend.layer_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &conf).unwrap();
+        (read_tensor(backend, &result), read_tensor(backend, &saved_mean), read_tensor(backend, &saved_inv_variance))
+    }
+
+    /// Scales and shifts every channel of the `[batch, channels, spatial]` values by its `gamma` and `beta`.
+    fn scale_channels(values: &[f64], channels: usize, spatial: usize, gamma: &[f64], beta: &[f64]) -> Vec<f64> {
+        values.iter().enumerate().map(|(i, v)| {
+            let c = i / spatial % channels;
+            gamma[c] * v + beta[c]
+        }).collect()
+    }
+
+    #[test]
+    fn it_normalizes_groups_of_channels_on_native() {
+        let backend = get_native_backend();
+        let conf = GroupNormalization::<f64>::new_group_normalization_config(&backend, 2, 1e-5).unwrap();
+        let (result, mean, inv_std) = group_norm(&backend, &conf, 2, &[1, 4, 1, 2], &[0f64, 2f64, 4f64, 6f64, -3f64, -3f64, 1f64, 5f64],
+                                                 &[1f64, 2f64, 1f64, 2f64], &[0f64, 1f64, 0f64, 1f64]);
+
+        // the channels 0 and 1 form a group with a variance of 5, the channels 2 and 3 one with a variance of 11.
+        let (inv_std_0, inv_std_1) = (1f64 / (5f64 + 1e-5).sqrt(), 1f64 / (11f64 + 1e-5).sqrt());
+        assert_all_close(&[3f64, 0f64], &mean, 1e-12);
+        assert_all_close(&[inv_std_0, inv_std_1], &inv_std, 1e-12);
+        assert_all_close(&[-3f64 * inv_std_0, -inv_std_0, 1f64 + 2f64 * inv_std_0, 1f64 + 6f64 * inv_std_0,
+                           -3f64 * inv_std_1, -3f64 * inv_std_1, 1f64 + 2f64 * inv_std_1, 1f64 + 10f64 * inv_std_1], &result, 1e-12);
+    }
+
+    #[test]
+    fn it_matches_layer_normalization_for_one_group_on_native() {
+        let backend = get_native_backend();
+        let dims = [2, 4, 2, 3];
+        let (x, gamma, beta) = (random_data(48, 0), random_data(4, 3), random_data(4, 5));
+        let conf = GroupNormalization::<f64>::new_group_normalization_config(&backend, 1, 1e-5).unwrap();
+        let (result, mean, inv_std) = group_norm(&backend, &conf, 1, &dims, &x, &gamma, &beta);
+
+        // a single group normalizes every sample over all of its channels and positions.
+        let (layer_result, layer_mean, layer_inv_std) = layer_norm(&backend, &dims, 3, &x);
+        assert_all_close(&scale_channels(&layer_result, 4, 6, &gamma, &beta), &result, 1e-12);
+        assert_all_close(&layer_mean, &mean, 1e-12);
+        assert_all_close(&layer_inv_std, &inv_std, 1e-12);
+    }
+
+    #[test]
+    fn it_matches_instance_normalization_for_one_channel_per_group_on_native() {
+        let backend = get_native_backend();
+        let dims = [2, 4, 2, 3];
+        let (x, gamma, beta) = (random_data(48, 0), random_data(4, 3), random_data(4, 5));
+        let conf = GroupNormalization::<f64>::new_group_normalization_config(&backend, 4, 1e-5).unwrap();
+        let (result, mean, inv_std) = group_norm(&backend, &conf, 4, &dims, &x, &gamma, &beta);
+
+        let instance_conf = GroupNormalization::<f64>::new_instance_normalization_config(&backend, 4, 1e-5).unwrap();
+        let (instance_result, instance_mean, instance_inv_std) = group_norm(&backend, &instance_conf, 4, &dims, &x, &gamma, &beta);
+        assert_all_close(&instance_result, &result, 0f64);
+        assert_all_close(&instance_mean, &mean, 0f64);
+        assert_all_close(&instance_inv_std, &inv_std, 0f64);
+
+        // every channel of every sample is normalized over the spatial dimensions on its own.
+        let (layer_result, layer_mean, layer_inv_std) = layer_norm(&backend, &dims, 2, &x);
+        assert_all_close(&scale_channels(&layer_result, 4, 6, &gamma, &beta), &result, 1e-12);
+        assert_all_close(&layer_mean, &mean, 1e-12);
+        assert_all_close(&layer_inv_std, &inv_std, 1e-12);
+    }
+
+    #[test]
+    fn it_computes_correct_group_normalization_grad_on_native() {
+        let backend = get_native_backend();
+        let dims = [2, 4, 2, 3];
+        for &groups in &[1, 2, 4] {
+            let conf = GroupNormalization::<f64>::new_group_normalization_config(&backend, groups, 1e-5).unwrap();
+            let (x, gamma, beta) = (random_data(48, 0), random_data(4, 3), random_data(4, 5));
+            let output_diff = random_data(48, 11);
+            let loss = |x: &[f64], gamma: &[f64], beta: &[f64]| dot(&group_norm(&backend, &conf, groups, &dims, x, gamma, beta).0, &output_diff);
+
+            let (_, mean, inv_std) = group_norm(&backend, &conf, groups, &dims, &x, &gamma, &beta);
+            let stats = [mean.len()];
+            let mut x_tensor = get_tensor(&backend, &dims, &x);
+            let mut x_diff = get_tensor(&backend, &dims, &output_diff);
+            let mut gamma_tensor = get_tensor(&backend, &[4], &gamma);
+            let mut saved_mean = get_tensor(&backend, &stats, &mean);
+            let mut saved_inv_variance = get_tensor(&backend, &stats, &inv_std);
+            let mut result_diff = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+            let mut gamma_diff = SharedTensor::<f64>::new(backend.device(), &(4)).unwrap();
+            let mut beta_diff = SharedTensor::<f64>::new(backend.device(), &(4)).unwrap();
+            backend.group_normalization_grad(&mut x_tensor, &mut x_diff, &mut gamma_tensor, &mut saved_mean, &mut saved_inv_variance,
+                                             &mut result_diff, &mut gamma_diff, &mut beta_diff, &conf).unwrap();
+
+            assert_all_close(&numeric_gradient(&x, &|x| loss(x, &gamma, &beta)), &read_tensor(&backend, &result_diff), 1e-5);
+            assert_all_close(&numeric_gradient(&gamma, &|gamma| loss(&x, gamma, &beta)), &read_tensor(&backend, &gamma_diff), 1e-5);
+            assert_all_close(&numeric_gradient(&beta, &|beta| loss(&x, &gamma, beta)), &read_tensor(&backend, &beta_diff), 1e-5);
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_group_normalization_on_native() {
+        let backend = get_native_backend();
+        assert!(GroupNormalization::<f32>::new_group_normalization_config(&backend, 0, 1e-5).is_err());
+        assert!(GroupNormalization::<f32>::new_instance_normalization_config(&backend, 2, -1f64).is_err());
+
+        let conf = GroupNormalization::<f64>::new_group_normalization_config(&backend, 3, 1e-5).unwrap();
+        let mut x = get_tensor(&backend, &[2, 4, 3], &random_data(24, 0));
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 4, 3)).unwrap();
+        let mut gamma = get_tensor(&backend, &[4], &[1f64; 4]);
+        let mut beta = get_tensor(&backend, &[4], &[0f64; 4]);
+        let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &(6)).unwrap();
+        let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &(6)).unwrap();
+        assert!(backend.group_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &conf).is_err());
+
+        let conf = GroupNormalization::<f64>::new_group_normalization_config(&backend, 2, 1e-5).unwrap();
+        assert!(backend.group_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &conf).is_err());
+        let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &(4)).unwrap();
+        let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &(4)).unwrap();
+        backend.group_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &conf).unwrap();
+        let mut narrow_gamma = get_tensor(&backend, &[2], &[1f64; 2]);
+        assert!(backend.group_normalization(&mut x, &mut result, &mut narrow_gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &conf).is_err());
+    }
+}
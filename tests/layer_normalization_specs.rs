@@ -0,0 +1,123 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(feature = "native")]
+mod common;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod layer_normalization_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+    use common::*;
+
+    /// Runs layer normalization over the trailing dimensions of `dims` that `gamma_dims` describes
+    /// and returns the output and the saved mean and inverse standard deviation.
+    fn layer_norm(backend: &Backend<Native>, dims: &[usize], gamma_dims: &[usize], x: &[f64], gamma: &[f64], beta: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let conf = LayerNormalization::<f64>::new_layer_normalization_config(backend, gamma_dims.len(), 1e-5).unwrap();
+        let samples = vec![x.len() / gamma.len()];
+        let mut x = get_tensor(backend, dims, x);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        let mut gamma = get_tensor(backend, gamma_dims, gamma);
+        let mut beta = get_tensor(backend, gamma_dims, beta);
+        let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &samples).unwrap();
+        let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &samples).unwrap();
+        backend.layer_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &conf).unwrap();
+        (read_tensor(backend, &result), read_tensor(backend, &saved_mean), read_tensor(backend, &saved_inv_variance))
+    }
+
+    /// Normalizes every `features` long sample of `x` on its own and then scales and shifts each of its elements.
+    fn reference(x: &[f64], features: usize, gamma: &[f64], beta: &[f64]) -> Vec<f64> {
+        x.chunks(features).flat_map(|sample| {
+            let mean = sample.iter().fold(0f64, |acc, v| acc + v) / features as f64;
+            let variance = sample.iter().fold(0f64, |acc, v| acc + (v - mean) * (v - mean)) / features as f64;
+            let inv_std = 1f64 / (variance + 1e-5).sqrt();
+            sample.iter().zip(gamma.iter().zip(beta.iter())).map(|(v, (g, b))| g * (v - mean) * inv_std + b).collect::<Vec<f64>>()
+        }).collect()
+    }
+
+    #[test]
+    fn it_normalizes_every_sample_over_the_last_dimension_on_native() {
+        let backend = get_native_backend();
+        let (result, mean, inv_std) = layer_norm(&backend, &[2, 3], &[3], &[0f64, 3f64, 6f64, -1f64, -1f64, 2f64],
+                                                 &[1f64, 2f64, 3f64], &[0f64, -1f64, 1f64]);
+
+        // the first sample has a variance of 6, the second one of 2.
+        let (inv_std_0, inv_std_1) = (1f64 / (6f64 + 1e-5).sqrt(), 1f64 / (2f64 + 1e-5).sqrt());
+        assert_all_close(&[3f64, 0f64], &mean, 1e-12);
+        assert_all_close(&[inv_std_0, inv_std_1], &inv_std, 1e-12);
+        assert_all_close(&[-3f64 * inv_std_0, -1f64, 9f64 * inv_std_0 + 1f64,
+                           -inv_std_1, -2f64 * inv_std_1 - 1f64, 6f64 * inv_std_1 + 1f64], &result, 1e-12);
+    }
+
+    #[test]
+    fn it_scales_and_shifts_every_normalized_element_on_native() {
+        let backend = get_native_backend();
+        let dims = [2, 3, 2, 2];
+        let x = random_data(24, 0);
+        for normalized_dims in 1..4 {
+            let gamma_dims = &dims[4 - normalized_dims..];
+            let features = gamma_dims.iter().fold(1, |acc, d| acc * d);
+            let (gamma, beta) = (random_data(features, 3), random_data(features, 5));
+            let (result, mean, _) = layer_norm(&backend, &dims, gamma_dims, &x, &gamma, &beta);
+            assert_eq!(24 / features, mean.len());
+            assert_all_close(&reference(&x, features, &gamma, &beta), &result, 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_layer_normalization_grad_on_native() {
+        let backend = get_native_backend();
+        let dims = [3, 2, 2, 3];
+        for &normalized_dims in &[1, 3] {
+            let gamma_dims = &dims[4 - normalized_dims..];
+            let features = gamma_dims.iter().fold(1, |acc, d| acc * d);
+            let (x, gamma, beta) = (random_data(36, 0), random_data(features, 3), random_data(features, 5));
+            let output_diff = random_data(36, 11);
+            let loss = |x: &[f64], gamma: &[f64], beta: &[f64]| dot(&layer_norm(&backend, &dims, gamma_dims, x, gamma, beta).0, &output_diff);
+
+            let conf = LayerNormalization::<f64>::new_layer_normalization_config(&backend, normalized_dims, 1e-5).unwrap();
+            let (_, mean, inv_std) = layer_norm(&backend, &dims, gamma_dims, &x, &gamma, &beta);
+            let samples = [mean.len()];
+            let mut x_tensor = get_tensor(&backend, &dims, &x);
+            let mut x_diff = get_tensor(&backend, &dims, &output_diff);
+            let mut gamma_tensor = get_tensor(&backend, gamma_dims, &gamma);
+            let mut saved_mean = get_tensor(&backend, &samples, &mean);
+            let mut saved_inv_variance = get_tensor(&backend, &samples, &inv_std);
+            let mut result_diff = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+            let mut gamma_diff = SharedTensor::<f64>::new(backend.device(), &gamma_dims.to_vec()).unwrap();
+            let mut beta_diff = SharedTensor::<f64>::new(backend.device(), &gamma_dims.to_vec()).unwrap();
+            backend.layer_normalization_grad(&mut x_tensor, &mut x_diff, &mut gamma_tensor, &mut saved_mean, &mut saved_inv_variance,
+                                             &mut result_diff, &mut gamma_diff, &mut beta_diff, &conf).unwrap();
+
+            assert_all_close(&numeric_gradient(&x, &|x| loss(x, &gamma, &beta)), &read_tensor(&backend, &result_diff), 1e-5);
+            assert_all_close(&numeric_gradient(&gamma, &|gamma| loss(&x, gamma, &beta)), &read_tensor(&backend, &gamma_diff), 1e-5);
+            assert_all_close(&numeric_gradient(&beta, &|beta| loss(&x, &gamma, beta)), &read_tensor(&backend, &beta_diff), 1e-5);
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_layer_normalization_on_native() {
+        let backend = get_native_backend();
+        assert!(LayerNormalization::<f32>::new_layer_normalization_config(&backend, 0, 1e-5).is_err());
+        assert!(LayerNormalization::<f32>::new_layer_normalization_config(&backend, 1, 0f64).is_err());
+
+        let conf = LayerNormalization::<f64>::new_layer_normalization_config(&backend, 1, 1e-5).unwrap();
+        let mut x = get_tensor(&backend, &[2, 3], &random_data(6, 0));
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 3)).unwrap();
+        let mut gamma = get_tensor(&backend, &[2], &[1f64, 1f64]);
+        let mut beta = get_tensor(&backend, &[2], &[0f64, 0f64]);
+        let mut saved_mean = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        let mut saved_inv_variance = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        assert!(backend.layer_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &conf).is_err());
+
+        let mut gamma = get_tensor(&backend, &[3], &[1f64, 1f64, 1f64]);
+        let mut beta = get_tensor(&backend, &[3], &[0f64, 0f64, 0f64]);
+        let mut wide_mean = SharedTensor::<f64>::new(backend.device(), &(3)).unwrap();
+        assert!(backend.layer_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut wide_mean, &mut saved_inv_variance, &conf).is_err());
+
+        let deep_conf = LayerNormalization::<f64>::new_layer_normalization_config(&backend, 3, 1e-5).unwrap();
+        assert!(backend.layer_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &deep_conf).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod linear_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn get_tensor<T: Copy>(backend: &Backend<Native>, dims: &[usize], data: &[T]) -> SharedTensor<T> {
+        let mut x = SharedTensor::<T>::new(backend.device(), &dims.to_vec()).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        x
+    }
+
+    fn read_tensor<T: Copy>(backend: &Backend<Native>, x: &SharedTensor<T>) -> Vec<T> {
+        x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<T>().to_vec()
+    }
+
+    // x = [[1, 2, 3], [4, 5, 6]], W = [[1, 0, -1], [2, 1, 0]], b = [0.5, -1] and dy = [[1, 2], [3, -1]].
+    const X: [f32; 6] = [1f32, 2f32, 3f32, 4f32, 5f32, 6f32];
+    const WEIGHT: [f32; 6] = [1f32, 0f32, -1f32, 2f32, 1f32, 0f32];
+    const BIAS: [f32; 2] = [0.5f32, -1f32];
+    const DIFF: [f32; 4] = [1f32, 2f32, 3f32, -1f32];
+
+    #[test]
+    fn it_computes_correct_linear_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &[2, 3], &X);
+        let mut weight = get_tensor(&backend, &[2, 3], &WEIGHT);
+        let mut bias = get_tensor(&backend, &[2], &BIAS);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(2, 2)).unwrap();
+        backend.linear(&mut weight, &mut bias, &mut x, &mut result).unwrap();
+        assert_eq!(vec![-1.5f32, 3f32, -1.5f32, 12f32], read_tensor(&backend, &result));
+    }
+
+    #[test]
+    fn it_computes_correct_linear_on_native_for_f64() {
+        let backend = get_native_backend();
+        let to_f64 = |data: &[f32]| -> Vec<f64> { data.iter().map(|&v| v as f64).collect() };
+        // every dimension after the batch is flattened into the inputs.
+        let mut x = get_tensor(&backend, &[2, 1, 1, 3], &to_f64(&X));
+        let mut weight = get_tensor(&backend, &[2, 3], &to_f64(&WEIGHT));
+        let mut bias = get_tensor(&backend, &[2], &to_f64(&BIAS));
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 2)).unwrap();
+        backend.linear_plain(&mut weight, &mut bias, &mut x, &mut result).unwrap();
+        assert_eq!(vec![-1.5f64, 3f64, -1.5f64, 12f64], read_tensor(&backend, &result));
+    }
+
+    #[test]
+    fn it_computes_correct_linear_grads_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &[2, 3], &X);
+        let mut weight = get_tensor(&backend, &[2, 3], &WEIGHT);
+        let mut x_diff = get_tensor(&backend, &[2, 2], &DIFF);
+
+        let mut result_diff = SharedTensor::<f32>::new(backend.device(), &(2, 3)).unwrap();
+        backend.linear_grad_input(&mut weight, &mut x_diff, &mut result_diff).unwrap();
+        assert_eq!(vec![5f32, 2f32, -1f32, 1f32, -1f32, -3f32], read_tensor(&backend, &result_diff));
+
+        let mut weight_diff = SharedTensor::<f32>::new(backend.device(), &(2, 3)).unwrap();
+        backend.linear_grad_weight(&mut x, &mut x_diff, &mut weight_diff).unwrap();
+        assert_eq!(vec![13f32, 17f32, 21f32, -2f32, -1f32, 0f32], read_tensor(&backend, &weight_diff));
+
+        let mut bias_diff = SharedTensor::<f32>::new(backend.device(), &(2)).unwrap();
+        backend.linear_grad_bias(&mut x_diff, &mut bias_diff).unwrap();
+        assert_eq!(vec![4f32, 1f32], read_tensor(&backend, &bias_diff));
+    }
+
+    #[test]
+    fn it_rejects_mismatched_linear_shapes_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &[3, 2], &X);
+        let mut weight = get_tensor(&backend, &[2, 3], &WEIGHT);
+        let mut bias = get_tensor(&backend, &[2], &BIAS);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(3, 2)).unwrap();
+        assert!(backend.linear(&mut weight, &mut bias, &mut x, &mut result).is_err());
+
+        let mut x = get_tensor(&backend, &[2, 3], &X);
+        let mut flat_weight = get_tensor(&backend, &[6], &WEIGHT);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(2, 2)).unwrap();
+        assert!(backend.linear(&mut flat_weight, &mut bias, &mut x, &mut result).is_err());
+
+        let mut wide_bias = get_tensor(&backend, &[3], &[0f32, 0f32, 0f32]);
+        assert!(backend.linear(&mut weight, &mut wide_bias, &mut x, &mut result).is_err());
+
+        let mut bias_diff = SharedTensor::<f32>::new(backend.device(), &(3)).unwrap();
+        let mut x_diff = get_tensor(&backend, &[2, 2], &DIFF);
+        assert!(backend.linear_grad_bias(&mut x_diff, &mut bias_diff).is_err());
+    }
+}
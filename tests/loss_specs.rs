@@ -0,0 +1,160 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod loss_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn get_tensor(backend: &Backend<Native>, dims: &[usize], data: &[f64]) -> SharedTensor<f64> {
+        let mut x = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        x
+    }
+
+    fn read_tensor(backend: &Backend<Native>, x: &SharedTensor<f64>) -> Vec<f64> {
+        x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().to_vec()
+    }
+
+    fn assert_all_close(expected: &[f64], actual: &[f64], tolerance: f64) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!((e - a).abs() <= tolerance, "expected {:?}, got {:?}", expected, actual);
+        }
+    }
+
+    fn loss(backend: &Backend<Native>, mode: LossMode, reduction: LossReduction, dims: &[usize], x: &[f64], target: &[f64]) -> f64 {
+        let mut x = get_tensor(backend, dims, x);
+        let mut target = get_tensor(backend, &[target.len()], target);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(1)).unwrap();
+        backend.loss(mode, reduction, &mut x, &mut target, &mut result).unwrap();
+        read_tensor(backend, &result)[0]
+    }
+
+    fn loss_grad(backend: &Backend<Native>, mode: LossMode, reduction: LossReduction, dims: &[usize], x: &[f64], target: &[f64]) -> Vec<f64> {
+        let mut x = get_tensor(backend, dims, x);
+        let mut target = get_tensor(backend, &[target.len()], target);
+        let mut result_diff = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        backend.loss_grad(mode, reduction, &mut x, &mut target, &mut result_diff).unwrap();
+        read_tensor(backend, &result_diff)
+    }
+
+    #[test]
+    fn it_computes_correct_negative_log_likelihood_on_native() {
+        let backend = get_native_backend();
+        let x = [-1f64, -2f64, -3f64, -0.5f64, -1.5f64, -2.5f64];
+        let labels = [2f64, 0f64];
+        let mode = LossMode::NegativeLogLikelihood;
+        assert_eq!(3.5f64, loss(&backend, mode, LossReduction::Sum, &[2, 3], &x, &labels));
+        assert_eq!(1.75f64, loss(&backend, mode, LossReduction::Mean, &[2, 3], &x, &labels));
+        assert_eq!(vec![0f64, 0f64, -0.5f64, -0.5f64, 0f64, 0f64], loss_grad(&backend, mode, LossReduction::Mean, &[2, 3], &x, &labels));
+    }
+
+    #[test]
+    fn it_computes_correct_mean_squared_error_on_native() {
+        let backend = get_native_backend();
+        let (x, target) = ([1f64, 2f64, 3f64, 4f64], [1f64, 0f64, 3f64, 2f64]);
+        let mode = LossMode::MeanSquaredError;
+        assert_eq!(8f64, loss(&backend, mode, LossReduction::Sum, &[2, 2], &x, &target));
+        assert_eq!(2f64, loss(&backend, mode, LossReduction::Mean, &[2, 2], &x, &target));
+        assert_eq!(vec![0f64, 4f64, 0f64, 4f64], loss_grad(&backend, mode, LossReduction::Sum, &[2, 2], &x, &target));
+        assert_eq!(vec![0f64, 1f64, 0f64, 1f64], loss_grad(&backend, mode, LossReduction::Mean, &[2, 2], &x, &target));
+    }
+
+    #[test]
+    fn it_keeps_the_loss_of_every_sample_without_reduction_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &[2, 2], &[1f64, 2f64, 3f64, 4f64]);
+        let mut target = get_tensor(&backend, &[4], &[1f64, 0f64, 3f64, 1f64]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        backend.loss(LossMode::MeanSquaredError, LossReduction::None, &mut x, &mut target, &mut result).unwrap();
+        assert_eq!(vec![4f64, 9f64], read_tensor(&backend, &result));
+
+        let mut labels = get_tensor(&backend, &[2], &[1f64, 0f64]);
+        backend.loss(LossMode::NegativeLogLikelihood, LossReduction::None, &mut x, &mut labels, &mut result).unwrap();
+        assert_eq!(vec![-2f64, -3f64], read_tensor(&backend, &result));
+    }
+
+    #[test]
+    fn it_computes_correct_binary_cross_entropy_on_native() {
+        let backend = get_native_backend();
+        let (x, target) = ([0.9f64, 0.2f64], [1f64, 0f64]);
+        let mode = LossMode::BinaryCrossEntropy;
+        assert_all_close(&[-(0.9f64.ln() + 0.8f64.ln())], &[loss(&backend, mode, LossReduction::Sum, &[2, 1], &x, &target)], 1e-12);
+        assert_all_close(&[-1f64 / 0.9f64 / 2f64, 1f64 / 0.8f64 / 2f64], &loss_grad(&backend, mode, LossReduction::Mean, &[2, 1], &x, &target), 1e-12);
+        // saturated probabilities are clamped instead of producing infinite losses.
+        assert_eq!(100f64, loss(&backend, mode, LossReduction::Sum, &[1, 1], &[0f64], &[1f64]));
+    }
+
+    #[test]
+    fn it_computes_correct_hinge_loss_on_native() {
+        let backend = get_native_backend();
+        let x = [3f64, 1f64, 2.5f64, 0f64, 2f64, 0.5f64];
+        let labels = [0f64, 1f64];
+        let mode = LossMode::Hinge(1.0);
+        // only the third class of the first sample violates the margin, by 0.5.
+        assert_eq!(0.5f64, loss(&backend, mode, LossReduction::Sum, &[2, 3], &x, &labels));
+        assert_eq!(0.25f64, loss(&backend, mode, LossReduction::Mean, &[2, 3], &x, &labels));
+        assert_eq!(vec![-1f64, 0f64, 1f64, 0f64, 0f64, 0f64], loss_grad(&backend, mode, LossReduction::Sum, &[2, 3], &x, &labels));
+        assert_eq!(0f64, loss(&backend, LossMode::Hinge(0.0), LossReduction::Sum, &[2, 3], &x, &labels));
+    }
+
+    #[test]
+    fn it_computes_correct_loss_grads_on_native() {
+        let backend = get_native_backend();
+        let dims = [3, 4];
+        let x: Vec<f64> = (0..12).map(|i| ((i * 37 + 5) % 17) as f64 / 20f64 + 0.05f64).collect();
+        let labels = [3f64, 0f64, 2f64];
+        let values: Vec<f64> = (0..12).map(|i| ((i * 11) % 7) as f64 / 7f64).collect();
+        let cases = [(LossMode::NegativeLogLikelihood, &labels[..]), (LossMode::MeanSquaredError, &values[..]),
+                     (LossMode::BinaryCrossEntropy, &values[..]), (LossMode::Hinge(0.5), &labels[..])];
+        let h = 1e-6;
+        for &(mode, target) in cases.iter() {
+            for &reduction in [LossReduction::Mean, LossReduction::Sum].iter() {
+                let numeric: Vec<f64> = (0..12).map(|i| {
+                    let (mut plus, mut minus) = (x.clone(), x.clone());
+                    plus[i] += h;
+                    minus[i] -= h;
+                    (loss(&backend, mode, reduction, &dims, &plus, target) - loss(&backend, mode, reduction, &dims, &minus, target)) / (2f64 * h)
+                }).collect();
+                assert_all_close(&numeric, &loss_grad(&backend, mode, reduction, &dims, &x, target), 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_loss_targets_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &[2, 3], &[0f64; 6]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(1)).unwrap();
+        for labels in [[0f64, 3f64], [0.5f64, 1f64], [-1f64, 0f64]].iter() {
+            let mut target = get_tensor(&backend, &[2], labels);
+            assert!(backend.loss(LossMode::NegativeLogLikelihood, LossReduction::Mean, &mut x, &mut target, &mut result).is_err());
+        }
+        let mut target = get_tensor(&backend, &[2], &[0f64, 1f64]);
+        assert!(backend.loss(LossMode::MeanSquaredError, LossReduction::Mean, &mut x, &mut target, &mut result).is_err());
+        assert!(backend.loss(LossMode::Hinge(::std::f64::NAN), LossReduction::Mean, &mut x, &mut target, &mut result).is_err());
+
+        let mut wide_result = SharedTensor::<f64>::new(backend.device(), &(2)).unwrap();
+        assert!(backend.loss(LossMode::NegativeLogLikelihood, LossReduction::Mean, &mut x, &mut target, &mut wide_result).is_err());
+    }
+}
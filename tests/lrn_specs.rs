@@ -221,195 +221,257 @@ mod lrn_spec_cuda {
 #[cfg(feature = "native")]
 mod lrn_spec_native {
 
-    // use co::backend::{Backend, BackendConfig};
-    // use co::framework::IFramework;
-    // use co::frameworks::Native;
-    // use co_nn::*;
-    // use co::memory::MemoryType;
-    // use co::tensor::SharedTensor;
-    // use co::plugin::numeric_helpers::{cast, Float};
-    //
-    // fn get_native_backend() -> Backend<Native> {
-    //     let framework = Native::new();
-    //     let hardwares = framework.hardwares();
-    //     let backend_config = BackendConfig::new(framework, hardwares);
-    //     Backend::new(backend_config).unwrap()
-    // }
-    //
-    // fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
-    //     match mem {
-    //         &mut MemoryType::Native(ref mut mem) => {
-    //             let mut mem_buffer = mem.as_mut_slice::<T>();
-    //             for (index, datum) in data.iter().enumerate() {
-    //                 mem_buffer[index] = *datum;
-    //             }
-    //         },
-    //         #[cfg(any(feature = "opencl", feature = "cuda"))]
-    //         _ => {}
-    //     }
-    // }
-    //
-    // fn get_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>){
-    //     let val = cast::<f64, T>(1f64).unwrap();
-    //     let val2 = cast::<f64, T>(2f64).unwrap();
-    //     let mut x = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
-    //     write_to_memory(x.get_mut(backend.device()).unwrap(), &[val, val, val2]);
-    //
-    //     let result = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
-    //
-    //     (x, result)
-    // }
-    //
-    // fn get_grad_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
-    //     let val = cast::<f64, T>(1f64).unwrap();
-    //     let val2 = cast::<f64, T>(2f64).unwrap();
-    //     let mut x = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
-    //     write_to_memory(x.get_mut(backend.device()).unwrap(), &[val, val, val2]);
-    //
-    //     let mut x_diff = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
-    //     write_to_memory(x_diff.get_mut(backend.device()).unwrap(), &[val, val, val2]);
-    //
-    //     let mut result = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
-    //     write_to_memory(result.get_mut(backend.device()).unwrap(), &[val, val, val2]);
-    //
-    //     let result_diff = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
-    //
-    //     (x, x_diff, result, result_diff)
-    // }
-
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_on_cuda_for_f32() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f32, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.59458125f32, 0.59458125f32, 1.1890286f32], mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_on_cuda_for_f64() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f64, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.594581260843431f64, 0.594581260843431f64, 1.1890287651464355f64], mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_on_native_for_f32_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f32, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn_plain(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.59458125f32, 0.59458125f32, 1.1890286f32], mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_on_native_for_f64_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f64, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn_plain(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.594581260843431f64, 0.594581260843431f64, 1.1890287651464355f64], mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_grad_on_native_for_f32() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.59453666f32, 0.59453666f32, 1.188672f32], mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_grad_on_native_for_f64() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.594536669478436f64, 0.594536669478436f64, 1.188672127844352f64], mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_grad_on_native_for_f32_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.59453666f32, 0.59453666f32, 1.188672f32], mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_lrn_grad_on_native_for_f64_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
-    //
-    //     let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
-    //     match backend.lrn_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 assert_eq!(&[0.594536669478436f64, 0.594536669478436f64, 1.188672127844352f64], mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
+    use co::prelude::*;
+    use co_nn::*;
+    use co::plugin::numeric_helpers::{cast, Float};
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    // single precision results may differ from the cuDNN reference in the last digit.
+    fn assert_approx_eq(expected: &[f32], actual: &[f32]) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!((e - a).abs() <= 1e-6 * e.abs(), "{:?} != {:?}", expected, actual);
+        }
+    }
+
+    fn get_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>){
+        let val = cast::<f64, T>(1f64).unwrap();
+        let val2 = cast::<f64, T>(2f64).unwrap();
+        let mut x = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[val, val, val2]);
+
+        let result = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
+
+        (x, result)
+    }
+
+    fn get_grad_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
+        let val = cast::<f64, T>(1f64).unwrap();
+        let val2 = cast::<f64, T>(2f64).unwrap();
+        let mut x = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[val, val, val2]);
+
+        let mut x_diff = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
+        write_to_memory(x_diff.get_mut(backend.device()).unwrap(), &[val, val, val2]);
+
+        let mut result = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
+        write_to_memory(result.get_mut(backend.device()).unwrap(), &[val, val, val2]);
+
+        let result_diff = SharedTensor::<T>::new(backend.device(), &(1, 1, 3)).unwrap();
+
+        (x, x_diff, result, result_diff)
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f32, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    assert_approx_eq(&[0.59458125f32, 0.59458125f32, 1.1890286f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f64, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[0.594581260843431f64, 0.594581260843431f64, 1.1890287651464355f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_on_native_for_f32_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f32, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn_plain(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    assert_approx_eq(&[0.59458125f32, 0.59458125f32, 1.1890286f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f64, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn_plain(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[0.594581260843431f64, 0.594581260843431f64, 1.1890287651464355f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_grad_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_approx_eq(&[0.59453666f32, 0.59453666f32, 1.188672f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_grad_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[0.594536669478436f64, 0.594536669478436f64, 1.188672127844352f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_grad_on_native_for_f32_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_approx_eq(&[0.59453666f32, 0.59453666f32, 1.188672f32], mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_grad_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = LRN::<f64>::new_lrn_config(&backend, 1u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        match backend.lrn_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    assert_eq!(&[0.594536669478436f64, 0.594536669478436f64, 1.188672127844352f64], mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    fn get_channel_memory(backend: &Backend<Native>, data: &[f64]) -> SharedTensor<f64> {
+        let mut x = SharedTensor::<f64>::new(backend.device(), &(1, 4, 1, 1)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        x
+    }
+
+    fn lrn_across_channels(backend: &Backend<Native>, data: &[f64]) -> Vec<f64> {
+        let mut x = get_channel_memory(backend, data);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(1, 4, 1, 1)).unwrap();
+        let conf = LRN::<f64>::new_lrn_config(backend, 3u32, 1e-1f64, 0.75f64, 2f64).unwrap();
+        backend.lrn(&mut x, &mut result, &conf).unwrap();
+        let out = result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().to_vec();
+        out
+    }
+
+    #[test]
+    fn it_computes_correct_lrn_across_channels_on_native() {
+        let backend = get_native_backend();
+        let out = lrn_across_channels(&backend, &[1f64, 2f64, 3f64, 4f64]);
+        let expected = [0.5599586058135524f64, 1.016125024202914f64, 1.3271490202031375f64, 1.8316257400182596f64];
+        for (o, e) in out.iter().zip(expected.iter()) {
+            assert!((o - e).abs() < 1e-12, "{} != {}", o, e);
+        }
+    }
+
+    #[test]
+    fn it_computes_lrn_grad_across_channels_matching_finite_differences_on_native() {
+        let backend = get_native_backend();
+        let input = [1f64, 2f64, 3f64, 4f64];
+        let output_diff = [0.5f64, -1f64, 2f64, 1f64];
+        let mut result = get_channel_memory(&backend, &input);
+        let mut x = SharedTensor::<f64>::new(backend.device(), &(1, 4, 1, 1)).unwrap();
+        let mut x_diff = get_channel_memory(&backend, &output_diff);
+        let mut result_diff = SharedTensor::<f64>::new(backend.device(), &(1, 4, 1, 1)).unwrap();
+        let conf = LRN::<f64>::new_lrn_config(&backend, 3u32, 1e-1f64, 0.75f64, 2f64).unwrap();
+        backend.lrn_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf).unwrap();
+        let grad = result_diff.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().to_vec();
+
+        let eps = 1e-6f64;
+        for i in 0..4 {
+            let (mut plus, mut minus) = (input.to_vec(), input.to_vec());
+            plus[i] += eps;
+            minus[i] -= eps;
+            let (out_plus, out_minus) = (lrn_across_channels(&backend, &plus), lrn_across_channels(&backend, &minus));
+            let numeric = (0..4).fold(0f64, |acc, j| acc + output_diff[j] * (out_plus[j] - out_minus[j]) / (2f64 * eps));
+            assert!((grad[i] - numeric).abs() < 1e-6, "{} != {}", grad[i], numeric);
+        }
+    }
+
+    #[test]
+    fn it_rejects_an_empty_lrn_window_on_native() {
+        let backend = get_native_backend();
+        assert!(LRN::<f64>::new_lrn_config(&backend, 0u32, 1e-4f64, 0.75f64, 2f64).is_err());
+    }
+
+    #[test]
+    fn it_inverts_the_lrn_window_on_native() {
+        let backend = get_native_backend();
+        for n in 1..7u32 {
+            let conf = LRN::<f64>::new_lrn_config(&backend, n, 1e-4f64, 0.75f64, 2f64).unwrap();
+            for channels in 1..9 {
+                for c in 0..channels {
+                    let expected = (0..channels).filter(|&j| conf.window(j, channels).any(|w| w == c)).collect::<Vec<usize>>();
+                    assert_eq!(expected, conf.inverse_window(c, channels).collect::<Vec<usize>>(), "n = {}, channels = {}, c = {}", n, channels, c);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,252 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod native_threads_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+    use co_nn::frameworks::native::{NativeThreads, ThreadedBackend};
+    use co_nn::frameworks::native::parallel::MIN_ELEMENTS_PER_THREAD;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn get_threaded_backend(num_threads: usize) -> ThreadedBackend {
+        ThreadedBackend::new(get_native_backend(), num_threads)
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn read_from_memory(backend: &ThreadedBackend, tensor: &SharedTensor<f32>) -> Vec<u32> {
+        tensor.get(backend.device()).unwrap().as_native().unwrap()
+            .as_slice::<f32>().iter().map(|value| value.to_bits()).collect()
+    }
+
+    fn get_tensor(backend: &ThreadedBackend, dims: &[usize], seed: usize) -> SharedTensor<f32> {
+        let size = dims.iter().fold(1, |acc, d| acc * d);
+        let data: Vec<f32> = (0..size).map(|i| ((i * 7919 + seed) % 2000) as f32 / 200f32 - 5f32).collect();
+        let mut x = SharedTensor::<f32>::new(backend.device(), &dims.to_vec()).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &data);
+        x
+    }
+
+    /// Runs every threaded operation and returns the bits of all results.
+    fn run_all(backend: &ThreadedBackend, dims: &[usize]) -> Vec<Vec<u32>> {
+        let mut results = vec![];
+        let mut x = get_tensor(backend, dims, 0);
+        let mut x_diff = get_tensor(backend, dims, 17);
+        let mut result = get_tensor(backend, dims, 0);
+        let mut result_diff = get_tensor(backend, dims, 0);
+
+        backend.sigmoid(&mut x, &mut result).unwrap();
+        results.push(read_from_memory(backend, &result));
+        backend.sigmoid_grad(&mut x, &mut x_diff, &mut result, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+        backend.relu(&mut x, &mut result).unwrap();
+        results.push(read_from_memory(backend, &result));
+        backend.relu_grad(&mut x, &mut x_diff, &mut result, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+        backend.tanh(&mut x, &mut result).unwrap();
+        results.push(read_from_memory(backend, &result));
+        backend.tanh_grad(&mut x, &mut x_diff, &mut result, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+
+        let mut pointwise = get_tensor(backend, dims, 0);
+        let mut pointwise_diff = get_tensor(backend, dims, 17);
+        backend.sigmoid_pointwise(&mut pointwise).unwrap();
+        results.push(read_from_memory(backend, &pointwise));
+        backend.tanh_pointwise_grad(&mut pointwise, &mut pointwise_diff).unwrap();
+        results.push(read_from_memory(backend, &pointwise_diff));
+        backend.relu_pointwise(&mut pointwise_diff).unwrap();
+        results.push(read_from_memory(backend, &pointwise_diff));
+
+        backend.activation(ActivationMode::Gelu, &mut x, &mut result).unwrap();
+        results.push(read_from_memory(backend, &result));
+        backend.activation_grad(ActivationMode::Gelu, &mut result, &mut x_diff, &mut x, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+        backend.activation_pointwise_grad(ActivationMode::Elu(1.0), &mut pointwise, &mut pointwise_diff).unwrap();
+        results.push(read_from_memory(backend, &pointwise_diff));
+
+        backend.softmax(&mut x, &mut result).unwrap();
+        results.push(read_from_memory(backend, &result));
+        backend.softmax_grad(&mut result, &mut x_diff, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+        backend.log_softmax_axis(&mut x, &mut result, 1).unwrap();
+        results.push(read_from_memory(backend, &result));
+        backend.log_softmax_axis_grad(&mut result, &mut x_diff, &mut result_diff, 1).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+
+        let pooling = Pooling::<f32>::new_pooling_config(backend, &[2, 2], &[0, 0], &[2, 2]).unwrap();
+        let mut pooled = SharedTensor::<f32>::new(backend.device(), &(dims[0], dims[1], dims[2] / 2, dims[3] / 2)).unwrap();
+        backend.pooling_max(&mut x, &mut pooled, &pooling).unwrap();
+        results.push(read_from_memory(backend, &pooled));
+        backend.pooling_avg(&mut x, &mut pooled, &pooling).unwrap();
+        results.push(read_from_memory(backend, &pooled));
+        let mut pooled_diff = get_tensor(backend, &[dims[0], dims[1], dims[2] / 2, dims[3] / 2], 17);
+        backend.pooling_avg_grad(&mut pooled, &mut pooled_diff, &mut x, &mut result_diff, &pooling).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+
+        let dropout = Dropout::<f32>::new_dropout_config(backend, 0.5, 3).unwrap();
+        let mut mask = get_tensor(backend, dims, 0);
+        backend.dropout(&mut x, &mut result, &mut mask, 0, &dropout).unwrap();
+        results.push(read_from_memory(backend, &result));
+
+        let lrn = LRN::<f32>::new_lrn_config(backend, 3u32, 1e-4f64, 0.75f64, 2f64).unwrap();
+        backend.lrn(&mut x, &mut result, &lrn).unwrap();
+        results.push(read_from_memory(backend, &result));
+
+        for &(mode, features) in &[(BatchNormMode::Spatial, dims[1]), (BatchNormMode::PerActivation, dims[1] * dims[2] * dims[3])] {
+            // a momentum of 1 replaces the running statistics with the batch statistics.
+            let batch_norm = BatchNormalization::<f32>::new_batch_normalization_config(backend, mode, 1e-5, 1.0).unwrap();
+            let mut gamma = get_tensor(backend, &[features], 3);
+            let mut beta = get_tensor(backend, &[features], 5);
+            let mut running_mean = get_tensor(backend, &[features], 0);
+            let mut running_variance = get_tensor(backend, &[features], 0);
+            let mut saved_mean = get_tensor(backend, &[features], 0);
+            let mut saved_inv_variance = get_tensor(backend, &[features], 0);
+            backend.batch_normalization_training(&mut x, &mut result, &mut gamma, &mut beta, &mut running_mean, &mut running_variance,
+                                                 &mut saved_mean, &mut saved_inv_variance, &batch_norm).unwrap();
+            results.push(read_from_memory(backend, &result));
+            results.push(read_from_memory(backend, &running_variance));
+            backend.batch_normalization_inference(&mut x, &mut result, &mut gamma, &mut beta, &mut running_mean, &mut running_variance,
+                                                  &batch_norm).unwrap();
+            results.push(read_from_memory(backend, &result));
+            let mut gamma_diff = get_tensor(backend, &[features], 0);
+            let mut beta_diff = get_tensor(backend, &[features], 0);
+            backend.batch_normalization_grad(&mut x, &mut x_diff, &mut gamma, &mut saved_mean, &mut saved_inv_variance,
+                                             &mut result_diff, &mut gamma_diff, &mut beta_diff, &batch_norm).unwrap();
+            results.push(read_from_memory(backend, &result_diff));
+            results.push(read_from_memory(backend, &gamma_diff));
+        }
+
+        let mut filter = get_tensor(backend, &[dims[1], dims[1], 3, 3], 7);
+        let mut filter_diff = get_tensor(backend, &[dims[1], dims[1], 3, 3], 0);
+        let conv = backend.new_convolution_config(&x, &result, &mut filter, ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::ImplicitGEMM,
+                                                  ConvBackwardDataAlgo::ImplicitGEMM, &[1, 1], &[1, 1], &[1, 1], 1).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f32>::workspace_size(&conv))).unwrap();
+        backend.convolution(&mut filter, &mut x, &mut result, &mut workspace, &conv).unwrap();
+        results.push(read_from_memory(backend, &result));
+        backend.convolution_grad_filter(&mut x, &mut x_diff, &mut filter_diff, &mut workspace, &conv).unwrap();
+        results.push(read_from_memory(backend, &filter_diff));
+        backend.convolution_grad_data(&mut filter, &mut x_diff, &mut result_diff, &mut workspace, &conv).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+
+        let (batch, inputs, outputs) = (dims[0], dims[1] * dims[2] * dims[3], 16);
+        let mut weight = get_tensor(backend, &[outputs, inputs], 9);
+        let mut weight_diff = get_tensor(backend, &[outputs, inputs], 0);
+        let mut bias = get_tensor(backend, &[outputs], 11);
+        let mut bias_diff = get_tensor(backend, &[outputs], 0);
+        let mut linear = get_tensor(backend, &[batch, outputs], 0);
+        let mut linear_diff = get_tensor(backend, &[batch, outputs], 13);
+        backend.linear(&mut weight, &mut bias, &mut x, &mut linear).unwrap();
+        results.push(read_from_memory(backend, &linear));
+        backend.linear_grad_input(&mut weight, &mut linear_diff, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+        backend.linear_grad_weight(&mut x, &mut linear_diff, &mut weight_diff).unwrap();
+        results.push(read_from_memory(backend, &weight_diff));
+        backend.linear_grad_bias(&mut linear_diff, &mut bias_diff).unwrap();
+        results.push(read_from_memory(backend, &bias_diff));
+
+        let layer_norm = LayerNormalization::<f32>::new_layer_normalization_config(backend, 3, 1e-5).unwrap();
+        let group_norm = GroupNormalization::<f32>::new_group_normalization_config(backend, 4, 1e-5).unwrap();
+        for &(params, stats) in &[(&dims[1..], batch), (&dims[1..2], batch * 4)] {
+            let mut gamma = get_tensor(backend, params, 3);
+            let mut beta = get_tensor(backend, params, 5);
+            let mut gamma_diff = get_tensor(backend, params, 0);
+            let mut beta_diff = get_tensor(backend, params, 0);
+            let mut saved_mean = get_tensor(backend, &[stats], 0);
+            let mut saved_inv_variance = get_tensor(backend, &[stats], 0);
+            if params.len() == 3 {
+                backend.layer_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &layer_norm).unwrap();
+                backend.layer_normalization_grad(&mut x, &mut x_diff, &mut gamma, &mut saved_mean, &mut saved_inv_variance,
+                                                 &mut result_diff, &mut gamma_diff, &mut beta_diff, &layer_norm).unwrap();
+            } else {
+                backend.group_normalization(&mut x, &mut result, &mut gamma, &mut beta, &mut saved_mean, &mut saved_inv_variance, &group_norm).unwrap();
+                backend.group_normalization_grad(&mut x, &mut x_diff, &mut gamma, &mut saved_mean, &mut saved_inv_variance,
+                                                 &mut result_diff, &mut gamma_diff, &mut beta_diff, &group_norm).unwrap();
+            }
+            results.push(read_from_memory(backend, &result));
+            results.push(read_from_memory(backend, &saved_inv_variance));
+            results.push(read_from_memory(backend, &result_diff));
+            results.push(read_from_memory(backend, &gamma_diff));
+            results.push(read_from_memory(backend, &beta_diff));
+        }
+
+        let mut loss = get_tensor(backend, &[1], 0);
+        backend.loss(LossMode::MeanSquaredError, LossReduction::Mean, &mut x, &mut x_diff, &mut loss).unwrap();
+        results.push(read_from_memory(backend, &loss));
+        backend.loss_grad(LossMode::MeanSquaredError, LossReduction::Mean, &mut x, &mut x_diff, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+        let mut probabilities = get_tensor(backend, dims, 0);
+        backend.softmax(&mut x_diff, &mut probabilities).unwrap();
+        backend.softmax_cross_entropy(CrossEntropyTarget::Probabilities, LossReduction::Mean, &mut x, &mut probabilities, &mut loss).unwrap();
+        results.push(read_from_memory(backend, &loss));
+        backend.softmax_cross_entropy_grad(CrossEntropyTarget::Probabilities, LossReduction::Mean, &mut x, &mut probabilities, &mut result_diff).unwrap();
+        results.push(read_from_memory(backend, &result_diff));
+        results
+    }
+
+    #[test]
+    fn it_computes_identical_results_for_any_thread_count() {
+        let dims = [4, 8, 64, 64];
+        assert!(dims.iter().fold(1, |acc, d| acc * d) >= 4 * MIN_ELEMENTS_PER_THREAD);
+
+        let backend = get_threaded_backend(1);
+        assert_eq!(1, backend.num_threads());
+        let single = run_all(&backend, &dims);
+
+        for &threads in &[2, 3, 4] {
+            let backend = get_threaded_backend(threads);
+            assert_eq!(threads, backend.num_threads());
+            let multi = run_all(&backend, &dims);
+            for (index, (expected, actual)) in single.iter().zip(multi.iter()).enumerate() {
+                assert!(expected == actual, "operation {} differs with {} threads", index, threads);
+            }
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_thread_count_per_backend() {
+        let dims = [4, 8, 64, 64];
+        let single = get_threaded_backend(1);
+        let multi = get_threaded_backend(4);
+        let expected = run_all(&single, &dims);
+        assert!(expected == run_all(&multi, &dims));
+        assert_eq!(1, single.num_threads());
+        assert_eq!(4, multi.num_threads());
+
+        let default = get_native_backend();
+        assert!(default.num_threads() >= 1);
+        assert_eq!(default.num_threads(), get_threaded_backend(0).num_threads());
+    }
+
+    #[test]
+    fn it_runs_operations_of_one_pool_from_several_threads() {
+        let dims = [4, 8, 64, 64];
+        let backend = Arc::new(get_threaded_backend(3));
+        let expected = run_all(&backend, &dims);
+        let handles: Vec<_> = (0..3).map(|_| {
+            let backend = backend.clone();
+            thread::spawn(move || run_all(&backend, &dims))
+        }).collect();
+        for handle in handles {
+            assert!(expected == handle.join().unwrap());
+        }
+    }
+}
@@ -239,220 +239,340 @@ mod pooling_spec_cuda {
             Err(err) => { println!("{:?}", err); assert!(false) }
         }
     }
+
+    #[test]
+    fn it_computes_correct_pooling_avg_with_padding_on_cuda_for_f32() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        for &(avg_mode, expected, expected_diff) in &[
+            (PoolingAvgMode::ExcludePadding, [1f32, 2.5f32, 5.5f32, 7f32], [1f32, 1f32, 1f32, 1.5f32, 1f32, 1f32, 1.5f32, 1f32, 1f32]),
+            (PoolingAvgMode::IncludePadding, [0.25f32, 1.25f32, 2.75f32, 7f32], [0.25f32, 0.5f32, 0.5f32, 0.75f32, 1f32, 1f32, 0.75f32, 1f32, 1f32])] {
+            let mut x = SharedTensor::<f32>::new(native.device(), &(1, 1, 3, 3)).unwrap();
+            write_to_memory(x.get_mut(native.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+            let mut x_diff = SharedTensor::<f32>::new(native.device(), &(1, 1, 2, 2)).unwrap();
+            write_to_memory(x_diff.get_mut(native.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32]);
+            let mut result = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+            let mut result_diff = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+
+            let conf = Pooling::<f32>::new_pooling_avg_config(&backend, &vec!(2,2), &vec!(1,1), &vec!(2,2), avg_mode).unwrap();
+            backend.pooling_avg(&mut x, &mut result, &conf).unwrap();
+            backend.pooling_avg_grad(&mut result, &mut x_diff, &mut x, &mut result_diff, &conf).unwrap();
+            result.add_device(native.device()).unwrap();
+            result.sync(native.device()).unwrap();
+            assert_eq!(&expected, result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f32>());
+            result_diff.add_device(native.device()).unwrap();
+            result_diff.sync(native.device()).unwrap();
+            assert_eq!(&expected_diff, result_diff.get(native.device()).unwrap().as_native().unwrap().as_slice::<f32>());
+        }
+    }
 }
 
+
 #[cfg(test)]
 #[cfg(feature = "native")]
 mod pooling_spec_native {
-    // use co::backend::{Backend, BackendConfig};
-    // use co::framework::IFramework;
-    // use co::frameworks::Native;
-    // use co_nn::*;
-    // use co::memory::MemoryType;
-    // use co::tensor::SharedTensor;
-    // use co::plugin::numeric_helpers::{cast, Float};
-    //
-    // fn get_native_backend() -> Backend<Native> {
-    //     let framework = Native::new();
-    //     let hardwares = framework.hardwares();
-    //     let backend_config = BackendConfig::new(framework, hardwares);
-    //     Backend::new(backend_config).unwrap()
-    // }
-    //
-    // fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
-    //     match mem {
-    //         &mut MemoryType::Native(ref mut mem) => {
-    //             let mut mem_buffer = mem.as_mut_slice::<T>();
-    //             for (index, datum) in data.iter().enumerate() {
-    //                 mem_buffer[index] = *datum;
-    //             }
-    //         },
-    //         #[cfg(any(feature = "opencl", feature = "cuda"))]
-    //         _ => {}
-    //     }
-    // }
-    //
-    // fn get_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>){
-    //     let val = cast::<f32, T>(1f32).unwrap();
-    //     let val2 = cast::<f32, T>(2f32).unwrap();
-    //     let mut x = SharedTensor::<T>::new(backend.device(), &(4, 4, 4, 4)).unwrap();
-    //     let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x.capacity()).collect::<Vec<T>>();
-    //     payload[0] = val2;
-    //     write_to_memory(x.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let result = SharedTensor::<T>::new(backend.device(), &(4, 4, 2, 2)).unwrap();
-    //
-    //     (x, result)
-    // }
-    //
-    // fn get_grad_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
-    //     let val = cast::<f64, T>(1f64).unwrap();
-    //     let val2 = cast::<f64, T>(2f64).unwrap();
-    //     let mut x = SharedTensor::<T>::new(backend.device(), &(4, 4, 4, 4)).unwrap();
-    //     let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x.capacity()).collect::<Vec<T>>();
-    //     payload[0] = val2;
-    //     write_to_memory(x.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let mut x_diff = SharedTensor::<T>::new(backend.device(), &(4, 4, 4, 4)).unwrap();
-    //     let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x_diff.capacity()).collect::<Vec<T>>();
-    //     payload[0] = val2;
-    //     write_to_memory(x_diff.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let mut result = SharedTensor::<T>::new(backend.device(), &(4, 4, 2, 2)).unwrap();
-    //     let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(result.capacity()).collect::<Vec<T>>();
-    //     payload[0] = val2;
-    //     write_to_memory(result.get_mut(backend.device()).unwrap(), payload);
-    //
-    //     let result_diff = SharedTensor::<T>::new(backend.device(), &(4, 4, 2, 2)).unwrap();
-    //
-    //     (x, x_diff, result, result_diff)
-    // }
-
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_on_native_for_f32() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f32, Native>(&backend);
-    //
-    //     let conf = Pooling::<f32>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f32] = &mut ::std::iter::repeat(1f32).take(result.capacity()).collect::<Vec<f32>>();
-    //                 payload[0] = 2f32;
-    //                 assert_eq!(payload, mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_on_native_for_f64() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f64, Native>(&backend);
-    //
-    //     let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f64] = &mut ::std::iter::repeat(1f64).take(result.capacity()).collect::<Vec<f64>>();
-    //                 payload[0] = 2f64;
-    //                 assert_eq!(payload, mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_on_native_for_f32_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f32, Native>(&backend);
-    //
-    //     let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max_plain(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f32] = &mut ::std::iter::repeat(1f32).take(result.capacity()).collect::<Vec<f32>>();
-    //                 payload[0] = 2f32;
-    //                 assert_eq!(payload, mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_on_native_for_f64_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut result) = get_memory::<f64, Native>(&backend);
-    //
-    //     let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max_plain(&mut x, &mut result, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
-    //                 let mut payload: &mut [f64] = &mut ::std::iter::repeat(1f64).take(result.capacity()).collect::<Vec<f64>>();
-    //                 payload[0] = 2f64;
-    //                 assert_eq!(payload, mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_grad_on_native_for_f32() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
-    //
-    //     let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 let payload: &[f32] = &vec!(2f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32);
-    //                 assert_eq!(payload, mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_grad_on_native_for_f64() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
-    //
-    //     let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 let payload: &[f64] = &vec!(2f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64);
-    //                 assert_eq!(payload, mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_grad_on_native_for_f32_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
-    //
-    //     let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 let payload: &[f32] = &vec!(2f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32, 1f32, 1f32, 0f32, 0f32);
-    //                 assert_eq!(payload, mem.as_slice::<f32>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
-    //
-    // #[test]
-    // #[ignore]
-    // fn it_computes_correct_pooling_max_grad_on_native_for_f64_plain() {
-    //     let backend = get_native_backend();
-    //     let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
-    //
-    //     let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,1)).unwrap();
-    //     match backend.pooling_max_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
-    //         Ok(_) => {
-    //             if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
-    //                 let payload: &[f64] = &vec!(2f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64, 1f64, 1f64, 0f64, 0f64);
-    //                 assert_eq!(payload, mem.as_slice::<f64>());
-    //             }
-    //         },
-    //         Err(err) => { println!("{:?}", err); assert!(false) }
-    //     }
-    // }
+    use co::prelude::*;
+    use co_nn::*;
+    use co::plugin::numeric_helpers::{cast, Float};
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn get_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>){
+        let val = cast::<f32, T>(1f32).unwrap();
+        let val2 = cast::<f32, T>(2f32).unwrap();
+        let mut x = SharedTensor::<T>::new(backend.device(), &(4, 4, 4, 4)).unwrap();
+        let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x.capacity()).collect::<Vec<T>>();
+        payload[0] = val2;
+        write_to_memory(x.get_mut(backend.device()).unwrap(), payload);
+
+        let result = SharedTensor::<T>::new(backend.device(), &(4, 4, 2, 2)).unwrap();
+
+        (x, result)
+    }
+
+    fn get_grad_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>, SharedTensor<T>){
+        let val = cast::<f64, T>(1f64).unwrap();
+        let val2 = cast::<f64, T>(2f64).unwrap();
+        let mut x = SharedTensor::<T>::new(backend.device(), &(4, 4, 2, 2)).unwrap();
+        let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x.capacity()).collect::<Vec<T>>();
+        payload[0] = val2;
+        write_to_memory(x.get_mut(backend.device()).unwrap(), payload);
+
+        let mut x_diff = SharedTensor::<T>::new(backend.device(), &(4, 4, 2, 2)).unwrap();
+        let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(x_diff.capacity()).collect::<Vec<T>>();
+        payload[0] = val2;
+        write_to_memory(x_diff.get_mut(backend.device()).unwrap(), payload);
+
+        let mut result = SharedTensor::<T>::new(backend.device(), &(4, 4, 4, 4)).unwrap();
+        let mut payload: &mut [T] = &mut ::std::iter::repeat(val).take(result.capacity()).collect::<Vec<T>>();
+        payload[0] = val2;
+        write_to_memory(result.get_mut(backend.device()).unwrap(), payload);
+
+        let result_diff = SharedTensor::<T>::new(backend.device(), &(4, 4, 4, 4)).unwrap();
+
+        (x, x_diff, result, result_diff)
+    }
+
+    /// The gradient of every 2x2 window goes to its first maximum, the top left element.
+    fn get_max_grad_payload<T: Float>() -> Vec<T> {
+        let mut payload = (0..256).map(|i| if i % 2 == 0 && (i / 4) % 2 == 0 { T::one() } else { T::zero() }).collect::<Vec<T>>();
+        payload[0] = cast::<f64, T>(2f64).unwrap();
+        payload
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f32, Native>(&backend);
+
+        let conf = Pooling::<f32>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f32] = &mut ::std::iter::repeat(1f32).take(result.capacity()).collect::<Vec<f32>>();
+                    payload[0] = 2f32;
+                    assert_eq!(payload, mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f64, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f64] = &mut ::std::iter::repeat(1f64).take(result.capacity()).collect::<Vec<f64>>();
+                    payload[0] = 2f64;
+                    assert_eq!(payload, mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_on_native_for_f32_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f32, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max_plain(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f32] = &mut ::std::iter::repeat(1f32).take(result.capacity()).collect::<Vec<f32>>();
+                    payload[0] = 2f32;
+                    assert_eq!(payload, mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f64, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max_plain(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f64] = &mut ::std::iter::repeat(1f64).take(result.capacity()).collect::<Vec<f64>>();
+                    payload[0] = 2f64;
+                    assert_eq!(payload, mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_grad_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    let payload: &[f32] = &get_max_grad_payload::<f32>();
+                    assert_eq!(payload, mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_grad_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max_grad(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    let payload: &[f64] = &get_max_grad_payload::<f64>();
+                    assert_eq!(payload, mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_grad_on_native_for_f32_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f32, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    let payload: &[f32] = &get_max_grad_payload::<f32>();
+                    assert_eq!(payload, mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_grad_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut x_diff, mut result, mut result_diff) = get_grad_memory::<f64, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_max_grad_plain(&mut x, &mut x_diff, &mut result, &mut result_diff, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+                    let payload: &[f64] = &get_max_grad_payload::<f64>();
+                    assert_eq!(payload, mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_max_with_padding_on_native() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+
+        let conf = Pooling::<f32>::new_pooling_config(&backend, &vec!(2,2), &vec!(1,1), &vec!(2,2)).unwrap();
+        backend.pooling_max(&mut x, &mut result, &conf).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+            assert_eq!(&[1f32, 3f32, 7f32, 9f32], mem.as_slice::<f32>());
+        }
+
+        let mut x_diff = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+        write_to_memory(x_diff.get_mut(backend.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32]);
+        let mut result_diff = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+        backend.pooling_max_grad(&mut result, &mut x_diff, &mut x, &mut result_diff, &conf).unwrap();
+        if let Some(mem) = result_diff.get(backend.device()).unwrap().as_native() {
+            assert_eq!(&[1f32, 0f32, 2f32, 0f32, 0f32, 0f32, 3f32, 0f32, 4f32], mem.as_slice::<f32>());
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_avg_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f32, Native>(&backend);
+
+        let conf = Pooling::<f32>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_avg(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f32] = &mut ::std::iter::repeat(1f32).take(result.capacity()).collect::<Vec<f32>>();
+                    payload[0] = 1.25f32;
+                    assert_eq!(payload, mem.as_slice::<f32>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_avg_on_native_for_f64_plain() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_memory::<f64, Native>(&backend);
+
+        let conf = Pooling::<f64>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        match backend.pooling_avg_plain(&mut x, &mut result, &conf) {
+            Ok(_) => {
+                if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+                    let mut payload: &mut [f64] = &mut ::std::iter::repeat(1f64).take(result.capacity()).collect::<Vec<f64>>();
+                    payload[0] = 1.25f64;
+                    assert_eq!(payload, mem.as_slice::<f64>());
+                }
+            },
+            Err(err) => { println!("{:?}", err); assert!(false) }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_pooling_avg_with_padding_on_native() {
+        let backend = get_native_backend();
+        for &(avg_mode, expected, expected_diff) in &[
+            (PoolingAvgMode::ExcludePadding, [1f32, 2.5f32, 5.5f32, 7f32], [1f32, 1f32, 1f32, 1.5f32, 1f32, 1f32, 1.5f32, 1f32, 1f32]),
+            (PoolingAvgMode::IncludePadding, [0.25f32, 1.25f32, 2.75f32, 7f32], [0.25f32, 0.5f32, 0.5f32, 0.75f32, 1f32, 1f32, 0.75f32, 1f32, 1f32])] {
+            let mut x = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+            write_to_memory(x.get_mut(backend.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+            let mut x_diff = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+            write_to_memory(x_diff.get_mut(backend.device()).unwrap(), &[1f32, 2f32, 3f32, 4f32]);
+            let mut result = SharedTensor::<f32>::new(backend.device(), &(1, 1, 2, 2)).unwrap();
+            let mut result_diff = SharedTensor::<f32>::new(backend.device(), &(1, 1, 3, 3)).unwrap();
+
+            let conf = Pooling::<f32>::new_pooling_avg_config(&backend, &vec!(2,2), &vec!(1,1), &vec!(2,2), avg_mode).unwrap();
+            backend.pooling_avg(&mut x, &mut result, &conf).unwrap();
+            assert_eq!(&expected, result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f32>());
+            backend.pooling_avg_grad(&mut result, &mut x_diff, &mut x, &mut result_diff, &conf).unwrap();
+            assert_eq!(&expected_diff, result_diff.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f32>());
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_pooling_config_on_native() {
+        let backend = get_native_backend();
+        assert!(Pooling::<f32>::new_pooling_config(&backend, &vec!(2,2,2), &vec!(0,0), &vec!(1,1)).is_err());
+        assert!(Pooling::<f32>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(0,1)).is_err());
+    }
+
+    #[test]
+    fn it_rejects_mismatched_pooling_output_shapes_on_native() {
+        let backend = get_native_backend();
+        let conf = Pooling::<f32>::new_pooling_config(&backend, &vec!(2,2), &vec!(0,0), &vec!(2,2)).unwrap();
+        let (mut x, _) = get_memory::<f32, Native>(&backend);
+        let mut x_diff = SharedTensor::<f32>::new(backend.device(), &(4, 4, 4, 4)).unwrap();
+        for dims in &[(4, 4, 3, 2), (4, 4, 2, 3), (4, 4, 1, 1)] {
+            let mut result = SharedTensor::<f32>::new(backend.device(), dims).unwrap();
+            assert!(backend.pooling_max(&mut x, &mut result, &conf).is_err());
+            assert!(backend.pooling_avg(&mut x, &mut result, &conf).is_err());
+            let mut result_diff = SharedTensor::<f32>::new(backend.device(), dims).unwrap();
+            assert!(backend.pooling_max_grad(&mut result, &mut result_diff, &mut x, &mut x_diff, &conf).is_err());
+        }
+    }
 }
@@ -0,0 +1,101 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod simd_spec_native {
+
+    use co::plugin::numeric_helpers::Float;
+    use co_nn::frameworks::native::helper;
+    use co_nn::frameworks::native::simd::{self, Activations, Function};
+
+    fn inputs<T: Float>() -> Vec<T> {
+        let mut values: Vec<T> = (-2000..2001).map(|i| T::from(i as f64 / 100f64).unwrap()).collect();
+        for &value in &[1e-7f64, -1e-7, 0.6249, 0.6251, -0.6251, 87f64, -87f64, 100f64, -100f64, 700f64, -700f64, 1e300, -1e300] {
+            values.push(T::from(value).unwrap());
+        }
+        values
+    }
+
+    fn assert_close<T: Float + ::std::fmt::Debug>(function: Function, input: T, actual: T, expected: T, tolerance: T) {
+        if actual == expected {
+            return;
+        }
+        let error = (actual - expected).abs();
+        let limit = tolerance * expected.abs().max(T::min_positive_value().sqrt());
+        assert!(error <= limit, "{:?} of {:?}: expected {:?}, got {:?}", function, input, expected, actual);
+    }
+
+    fn check_forward<T: Activations + ::std::fmt::Debug>(tolerance: T) {
+        let x = inputs::<T>();
+        for &(function, f) in &[(Function::Sigmoid, helper::sigmoid as fn(&T) -> T),
+                                (Function::Relu, helper::relu as fn(&T) -> T),
+                                (Function::Tanh, helper::tanh as fn(&T) -> T)] {
+            let mut y = vec![T::zero(); x.len()];
+            simd::forward(function, &x, &mut y);
+            for (input, actual) in x.iter().zip(y.iter()) {
+                assert_close(function, *input, *actual, f(input), tolerance);
+            }
+        }
+    }
+
+    fn check_backward<T: Activations + ::std::fmt::Debug>() {
+        let y = inputs::<T>();
+        let dy: Vec<T> = y.iter().rev().cloned().collect();
+        for &(function, f) in &[(Function::Sigmoid, helper::sigmoid_grad as fn(&T, &T) -> T),
+                                (Function::Relu, helper::relu_grad as fn(&T, &T) -> T),
+                                (Function::Tanh, helper::tanh_grad as fn(&T, &T) -> T)] {
+            let mut dx = vec![T::zero(); y.len()];
+            simd::backward(function, &y, &dy, &mut dx);
+            for ((output, diff), actual) in y.iter().zip(dy.iter()).zip(dx.iter()) {
+                assert_eq!(f(output, diff), *actual);
+            }
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_activations_for_f32() {
+        check_forward::<f32>(4e-7);
+    }
+
+    #[test]
+    fn it_computes_correct_activations_for_f64() {
+        check_forward::<f64>(4e-15);
+    }
+
+    #[test]
+    fn it_computes_exact_activation_gradients() {
+        check_backward::<f32>();
+        check_backward::<f64>();
+    }
+
+    #[test]
+    fn it_propagates_nan() {
+        let x = [::std::f32::NAN, ::std::f32::INFINITY, ::std::f32::NEG_INFINITY];
+        let mut y = [0f32; 3];
+        simd::forward(Function::Sigmoid, &x, &mut y);
+        assert!(y[0].is_nan());
+        assert_eq!([1f32, 0f32], [y[1], y[2]]);
+        simd::forward(Function::Tanh, &x, &mut y);
+        assert!(y[0].is_nan());
+        assert_eq!([1f32, -1f32], [y[1], y[2]]);
+        simd::forward(Function::Relu, &x, &mut y);
+        assert_eq!([0f32, ::std::f32::INFINITY, 0f32], y);
+    }
+
+    #[test]
+    fn it_computes_tails_like_full_vectors() {
+        let x = inputs::<f32>();
+        for &function in &[Function::Sigmoid, Function::Relu, Function::Tanh] {
+            let mut full = vec![0f32; x.len()];
+            simd::forward(function, &x, &mut full);
+            for start in 0..9 {
+                for len in 1..18 {
+                    let mut part = x[start..start + len].to_vec();
+                    simd::forward_in_place(function, &mut part);
+                    assert_eq!(&full[start..start + len], &part[..]);
+                }
+            }
+        }
+    }
+}
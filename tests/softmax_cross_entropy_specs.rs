@@ -0,0 +1,141 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod softmax_cross_entropy_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+
+    fn get_native_backend() -> Backend<Native> {
+        Backend::<Native>::default().unwrap()
+    }
+
+    fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
+        match mem {
+            &mut MemoryType::Native(ref mut mem) => {
+                let mut mem_buffer = mem.as_mut_slice::<T>();
+                for (index, datum) in data.iter().enumerate() {
+                    mem_buffer[index] = *datum;
+                }
+            },
+            #[cfg(any(feature = "opencl", feature = "cuda"))]
+            _ => {}
+        }
+    }
+
+    fn get_tensor(backend: &Backend<Native>, dims: &[usize], data: &[f64]) -> SharedTensor<f64> {
+        let mut x = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        x
+    }
+
+    fn read_tensor(backend: &Backend<Native>, x: &SharedTensor<f64>) -> Vec<f64> {
+        x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().to_vec()
+    }
+
+    fn assert_all_close(expected: &[f64], actual: &[f64], tolerance: f64) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!((e - a).abs() <= tolerance, "expected {:?}, got {:?}", expected, actual);
+        }
+    }
+
+    const DIMS: [usize; 2] = [3, 4];
+    const LABELS: [f64; 3] = [3f64, 0f64, 1f64];
+
+    fn logits() -> Vec<f64> {
+        (0..12).map(|i| ((i * 37 + 5) % 17) as f64 / 4f64 - 2f64).collect()
+    }
+
+    fn one_hot(labels: &[f64], classes: usize) -> Vec<f64> {
+        labels.iter().flat_map(|&label| (0..classes).map(move |class| if class == label as usize { 1f64 } else { 0f64 })).collect()
+    }
+
+    fn cross_entropy(backend: &Backend<Native>, kind: CrossEntropyTarget, reduction: LossReduction, x: &[f64], target: &[f64]) -> Vec<f64> {
+        let mut x = get_tensor(backend, &DIMS, x);
+        let mut target = get_tensor(backend, &[target.len()], target);
+        let outputs = if reduction == LossReduction::None { DIMS[0] } else { 1 };
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(outputs)).unwrap();
+        backend.softmax_cross_entropy(kind, reduction, &mut x, &mut target, &mut result).unwrap();
+        read_tensor(backend, &result)
+    }
+
+    fn cross_entropy_grad(backend: &Backend<Native>, kind: CrossEntropyTarget, reduction: LossReduction, x: &[f64], target: &[f64]) -> Vec<f64> {
+        let mut x = get_tensor(backend, &DIMS, x);
+        let mut target = get_tensor(backend, &[target.len()], target);
+        let mut result_diff = SharedTensor::<f64>::new(backend.device(), &DIMS.to_vec()).unwrap();
+        backend.softmax_cross_entropy_grad(kind, reduction, &mut x, &mut target, &mut result_diff).unwrap();
+        read_tensor(backend, &result_diff)
+    }
+
+    #[test]
+    fn it_matches_log_softmax_and_negative_log_likelihood_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &DIMS, &logits());
+        let mut log_probabilities = SharedTensor::<f64>::new(backend.device(), &DIMS.to_vec()).unwrap();
+        backend.log_softmax(&mut x, &mut log_probabilities).unwrap();
+        let mut labels = get_tensor(&backend, &[3], &LABELS);
+        for &reduction in [LossReduction::Mean, LossReduction::Sum, LossReduction::None].iter() {
+            let outputs = if reduction == LossReduction::None { 3 } else { 1 };
+            let mut result = SharedTensor::<f64>::new(backend.device(), &(outputs)).unwrap();
+            backend.loss(LossMode::NegativeLogLikelihood, reduction, &mut log_probabilities, &mut labels, &mut result).unwrap();
+            assert_all_close(&read_tensor(&backend, &result), &cross_entropy(&backend, CrossEntropyTarget::ClassIndices, reduction, &logits(), &LABELS), 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_computes_the_softmax_minus_target_gradient_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &DIMS, &logits());
+        let mut probabilities = SharedTensor::<f64>::new(backend.device(), &DIMS.to_vec()).unwrap();
+        backend.softmax(&mut x, &mut probabilities).unwrap();
+        let expected: Vec<f64> = read_tensor(&backend, &probabilities).iter().zip(one_hot(&LABELS, 4).iter())
+            .map(|(p, y)| (p - y) / 3f64).collect();
+        assert_all_close(&expected, &cross_entropy_grad(&backend, CrossEntropyTarget::ClassIndices, LossReduction::Mean, &logits(), &LABELS), 1e-12);
+        // one-hot probabilities are the same target as the class labels.
+        assert_all_close(&expected, &cross_entropy_grad(&backend, CrossEntropyTarget::Probabilities, LossReduction::Mean, &logits(), &one_hot(&LABELS, 4)), 1e-12);
+    }
+
+    #[test]
+    fn it_computes_correct_probability_cross_entropy_grads_on_native() {
+        let backend = get_native_backend();
+        let target: Vec<f64> = [0.1f64, 0.2f64, 0.3f64, 0.4f64, 0.5f64, 0f64, 0.25f64, 0.25f64, 0f64, 0f64, 1f64, 0f64].to_vec();
+        let h = 1e-6;
+        for &reduction in [LossReduction::Mean, LossReduction::Sum, LossReduction::None].iter() {
+            let numeric: Vec<f64> = (0..12).map(|i| {
+                let (mut plus, mut minus) = (logits(), logits());
+                plus[i] += h;
+                minus[i] -= h;
+                let sum = |values: Vec<f64>| values.iter().fold(0f64, |acc, v| acc + v);
+                (sum(cross_entropy(&backend, CrossEntropyTarget::Probabilities, reduction, &plus, &target))
+                 - sum(cross_entropy(&backend, CrossEntropyTarget::Probabilities, reduction, &minus, &target))) / (2f64 * h)
+            }).collect();
+            assert_all_close(&numeric, &cross_entropy_grad(&backend, CrossEntropyTarget::Probabilities, reduction, &logits(), &target), 1e-6);
+        }
+    }
+
+    #[test]
+    fn it_computes_stable_cross_entropy_for_large_logits_on_native() {
+        let backend = get_native_backend();
+        let mut logits = vec![0f64; 12];
+        logits[0] = 1000f64;
+        logits[4] = -1000f64;
+        let losses = cross_entropy(&backend, CrossEntropyTarget::ClassIndices, LossReduction::None, &logits, &LABELS);
+        assert_all_close(&[1000f64, 1000f64 + 3f64.ln(), 4f64.ln()], &losses, 1e-9);
+        assert!(cross_entropy_grad(&backend, CrossEntropyTarget::ClassIndices, LossReduction::Sum, &logits, &LABELS).iter().all(|g| g.is_finite()));
+    }
+
+    #[test]
+    fn it_rejects_invalid_cross_entropy_targets_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &DIMS, &logits());
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(1)).unwrap();
+        let mut labels = get_tensor(&backend, &[3], &[0f64, 4f64, 1f64]);
+        assert!(backend.softmax_cross_entropy(CrossEntropyTarget::ClassIndices, LossReduction::Mean, &mut x, &mut labels, &mut result).is_err());
+        let mut labels = get_tensor(&backend, &[3], &LABELS);
+        assert!(backend.softmax_cross_entropy(CrossEntropyTarget::Probabilities, LossReduction::Mean, &mut x, &mut labels, &mut result).is_err());
+        assert!(backend.softmax_cross_entropy(CrossEntropyTarget::ClassIndices, LossReduction::None, &mut x, &mut labels, &mut result).is_err());
+    }
+}
@@ -271,6 +271,163 @@ mod softmax_spec_cuda {
             Err(err) => { println!("{:?}", err); assert!(false) }
         }
     }
+
+    fn assert_approx_eq(expected: &[f64], actual: &[f64]) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!((e - a).abs() < 1e-12, "{:?} != {:?}", expected, actual);
+        }
+    }
+
+    fn get_axis_memory(backend: &Backend<Cuda>, native: &Backend<Native>, dims: &[usize], data: &[f64]) -> (SharedTensor<f64>, SharedTensor<f64>) {
+        let mut x = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        x.add_device(native.device()).unwrap();
+        x.sync(native.device()).unwrap();
+        write_to_memory(x.get_mut(native.device()).unwrap(), data);
+        x.sync(backend.device()).unwrap();
+
+        let mut result = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        result.add_device(native.device()).unwrap();
+
+        (x, result)
+    }
+
+    #[test]
+    fn it_computes_softmax_per_sample_on_cuda_for_2d() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &native, &[2, 3], &[1f64, 2f64, 3f64, 1f64, 1f64, 1f64]);
+        let expected = [0.09003057317038046f64, 0.24472847105479767f64, 0.6652409557748219f64,
+                        0.3333333333333333f64, 0.3333333333333333f64, 0.3333333333333333f64];
+
+        backend.softmax(&mut x, &mut result).unwrap();
+        result.sync(native.device()).unwrap();
+        assert_approx_eq(&expected, result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+        result.sync(backend.device()).unwrap();
+        backend.softmax_axis(&mut x, &mut result, 1).unwrap();
+        result.sync(native.device()).unwrap();
+        assert_approx_eq(&expected, result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_along_axis_on_cuda_for_3d() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &native, &[2, 2, 2], &[0f64, 1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64]);
+
+        backend.softmax_axis(&mut x, &mut result, 1).unwrap();
+        result.sync(native.device()).unwrap();
+        assert_approx_eq(&[0.11920292202211755f64, 0.11920292202211755f64, 0.8807970779778824f64, 0.8807970779778824f64,
+                           0.11920292202211755f64, 0.11920292202211756f64, 0.8807970779778824f64, 0.8807970779778824f64],
+                         result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_along_channels_on_cuda_for_4d() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &native, &[1, 3, 1, 2], &[0f64, 1f64, 2f64, 0.5f64, -1f64, 3f64]);
+
+        backend.softmax_axis(&mut x, &mut result, 1).unwrap();
+        result.sync(native.device()).unwrap();
+        assert_approx_eq(&[0.11419519938459446f64, 0.11116562230242112f64, 0.8437947344813395f64,
+                           0.06742535823245292f64, 0.04201006613406605f64, 0.8214090194651259f64],
+                         result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_log_softmax_along_axis_on_cuda_for_3d() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &native, &[2, 2, 2], &[0f64, 1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64]);
+
+        backend.log_softmax_axis(&mut x, &mut result, 1).unwrap();
+        result.sync(native.device()).unwrap();
+        let expected = [0.11920292202211755f64, 0.11920292202211755f64, 0.8807970779778824f64, 0.8807970779778824f64,
+                        0.11920292202211755f64, 0.11920292202211756f64, 0.8807970779778824f64, 0.8807970779778824f64]
+            .iter().map(|v| v.ln()).collect::<Vec<f64>>();
+        assert_approx_eq(&expected, result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_grad_along_axis_on_cuda_for_3d() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let y = [0.11920292202211755f64, 0.11920292202211755f64, 0.8807970779778824f64, 0.8807970779778824f64,
+                 0.11920292202211755f64, 0.11920292202211756f64, 0.8807970779778824f64, 0.8807970779778824f64];
+        let dy = [1f64, -1f64, 2f64, 0.5f64, 0f64, 3f64, -2f64, 1f64];
+        let (mut x, mut result_diff) = get_axis_memory(&backend, &native, &[2, 2, 2], &y);
+        let (mut x_diff, _) = get_axis_memory(&backend, &native, &[2, 2, 2], &dy);
+
+        backend.softmax_axis_grad(&mut x, &mut x_diff, &mut result_diff, 1).unwrap();
+        result_diff.sync(native.device()).unwrap();
+        assert_approx_eq(&[-0.1049935854035065f64, -0.1574903781052598f64, 0.10499358540350653f64, 0.1574903781052598f64,
+                           0.209987170807013f64, 0.20998717080701304f64, -0.20998717080701307f64, -0.20998717080701307f64],
+                         result_diff.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+
+        let (log_x, _) = get_axis_memory(&backend, &native, &[2, 2, 2], &y.iter().map(|v| v.ln()).collect::<Vec<f64>>());
+        result_diff.sync(backend.device()).unwrap();
+        backend.log_softmax_axis_grad_plain(&log_x, &x_diff, &mut result_diff, 1).unwrap();
+        result_diff.sync(native.device()).unwrap();
+        assert_approx_eq(&[0.6423912339336474f64, -0.9403985389889412f64, -0.6423912339336475f64, 0.9403985389889412f64,
+                           0.2384058440442351f64, 2.5231883119115297f64, -0.23840584404423515f64, -2.5231883119115297f64],
+                         result_diff.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_along_the_last_axis_on_cuda_for_3d() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &native, &[2, 2, 2], &[0f64, 1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64]);
+
+        // every pair of neighbours is normalized on its own.
+        backend.softmax_axis(&mut x, &mut result, 2).unwrap();
+        result.sync(native.device()).unwrap();
+        assert_approx_eq(&[0.2689414213699951f64, 0.7310585786300049f64, 0.2689414213699951f64, 0.7310585786300049f64,
+                           0.2689414213699951f64, 0.7310585786300049f64, 0.2689414213699951f64, 0.7310585786300049f64],
+                         result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+        result.sync(backend.device()).unwrap();
+        backend.log_softmax_axis_plain(&x, &mut result, 2).unwrap();
+        result.sync(native.device()).unwrap();
+        assert_approx_eq(&[-1.3132616875182228f64, -0.3132616875182228f64, -1.3132616875182228f64, -0.3132616875182228f64,
+                           -1.3132616875182228f64, -0.3132616875182228f64, -1.3132616875182228f64, -0.3132616875182228f64],
+                         result.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_grad_along_the_last_axis_on_cuda_for_3d() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let y = [0.2689414213699951f64, 0.7310585786300049f64, 0.2689414213699951f64, 0.7310585786300049f64,
+                 0.2689414213699951f64, 0.7310585786300049f64, 0.2689414213699951f64, 0.7310585786300049f64];
+        let dy = [1f64, -1f64, 2f64, 0.5f64, 0f64, 3f64, -2f64, 1f64];
+        let (mut x, mut result_diff) = get_axis_memory(&backend, &native, &[2, 2, 2], &y);
+        let (mut x_diff, _) = get_axis_memory(&backend, &native, &[2, 2, 2], &dy);
+
+        backend.softmax_axis_grad(&mut x, &mut x_diff, &mut result_diff, 2).unwrap();
+        result_diff.sync(native.device()).unwrap();
+        assert_approx_eq(&[0.3932238664829637f64, -0.3932238664829637f64, 0.2949178998622228f64, -0.2949178998622227f64,
+                           -0.5898357997244456f64, 0.5898357997244454f64, -0.5898357997244456f64, 0.5898357997244456f64],
+                         result_diff.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+
+        let (mut log_x, _) = get_axis_memory(&backend, &native, &[2, 2, 2], &y.iter().map(|v| v.ln()).collect::<Vec<f64>>());
+        result_diff.sync(backend.device()).unwrap();
+        backend.log_softmax_axis_grad(&mut log_x, &mut x_diff, &mut result_diff, 2).unwrap();
+        result_diff.sync(native.device()).unwrap();
+        assert_approx_eq(&[1f64, -1f64, 1.3276464465750122f64, -1.3276464465750122f64,
+                           -0.8068242641099853f64, 0.8068242641099852f64, -1.7310585786300048f64, 1.7310585786300048f64],
+                         result_diff.get(native.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_rejects_an_out_of_range_softmax_axis_on_cuda() {
+        let backend = get_cuda_backend();
+        let native = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &native, &[2, 3], &[0f64; 6]);
+
+        assert!(backend.softmax_axis(&mut x, &mut result, 2).is_err());
+        assert!(backend.log_softmax_axis(&mut x, &mut result, 2).is_err());
+    }
 }
 
 #[cfg(test)]
@@ -506,4 +663,154 @@ mod softmax_spec_native {
         }
     }
 
+    fn assert_approx_eq(expected: &[f64], actual: &[f64]) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!((e - a).abs() < 1e-12, "{:?} != {:?}", expected, actual);
+        }
+    }
+
+    fn get_axis_memory(backend: &Backend<Native>, dims: &[usize], data: &[f64]) -> (SharedTensor<f64>, SharedTensor<f64>) {
+        let mut x = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        let result = SharedTensor::<f64>::new(backend.device(), &dims.to_vec()).unwrap();
+
+        (x, result)
+    }
+
+    #[test]
+    fn it_computes_softmax_per_sample_on_native_for_2d() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &[2, 3], &[1f64, 2f64, 3f64, 1f64, 1f64, 1f64]);
+        let expected = [0.09003057317038046f64, 0.24472847105479767f64, 0.6652409557748219f64,
+                        0.3333333333333333f64, 0.3333333333333333f64, 0.3333333333333333f64];
+
+        backend.softmax(&mut x, &mut result).unwrap();
+        assert_approx_eq(&expected, result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+        backend.softmax_axis(&mut x, &mut result, 1).unwrap();
+        assert_approx_eq(&expected, result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_along_axis_on_native_for_3d() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &[2, 2, 2], &[0f64, 1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64]);
+
+        backend.softmax_axis(&mut x, &mut result, 1).unwrap();
+        assert_approx_eq(&[0.11920292202211755f64, 0.11920292202211755f64, 0.8807970779778824f64, 0.8807970779778824f64,
+                           0.11920292202211755f64, 0.11920292202211756f64, 0.8807970779778824f64, 0.8807970779778824f64],
+                         result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_along_channels_on_native_for_4d() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &[1, 3, 1, 2], &[0f64, 1f64, 2f64, 0.5f64, -1f64, 3f64]);
+
+        backend.softmax_axis_plain(&x, &mut result, 1).unwrap();
+        assert_approx_eq(&[0.11419519938459446f64, 0.11116562230242112f64, 0.8437947344813395f64,
+                           0.06742535823245292f64, 0.04201006613406605f64, 0.8214090194651259f64],
+                         result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+        // without an axis the whole sample is normalized.
+        backend.softmax(&mut x, &mut result).unwrap();
+        let sum = result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().iter().fold(0f64, |acc, v| acc + v);
+        assert!((sum - 1f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn it_computes_log_softmax_along_axis_on_native_for_3d() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &[2, 2, 2], &[0f64, 1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64]);
+
+        backend.log_softmax_axis(&mut x, &mut result, 1).unwrap();
+        let expected = [0.11920292202211755f64, 0.11920292202211755f64, 0.8807970779778824f64, 0.8807970779778824f64,
+                        0.11920292202211755f64, 0.11920292202211756f64, 0.8807970779778824f64, 0.8807970779778824f64]
+            .iter().map(|v| v.ln()).collect::<Vec<f64>>();
+        assert_approx_eq(&expected, result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_softmax_grad_along_axis_on_native_for_3d() {
+        let backend = get_native_backend();
+        let y = [0.11920292202211755f64, 0.11920292202211755f64, 0.8807970779778824f64, 0.8807970779778824f64,
+                 0.11920292202211755f64, 0.11920292202211756f64, 0.8807970779778824f64, 0.8807970779778824f64];
+        let (mut x, mut result_diff) = get_axis_memory(&backend, &[2, 2, 2], &y);
+        let (mut x_diff, _) = get_axis_memory(&backend, &[2, 2, 2], &[1f64, -1f64, 2f64, 0.5f64, 0f64, 3f64, -2f64, 1f64]);
+
+        backend.softmax_axis_grad(&mut x, &mut x_diff, &mut result_diff, 1).unwrap();
+        assert_approx_eq(&[-0.1049935854035065f64, -0.1574903781052598f64, 0.10499358540350653f64, 0.1574903781052598f64,
+                           0.209987170807013f64, 0.20998717080701304f64, -0.20998717080701307f64, -0.20998717080701307f64],
+                         result_diff.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+
+        let log_y = y.iter().map(|v| v.ln()).collect::<Vec<f64>>();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &log_y);
+        backend.log_softmax_axis_grad_plain(&x, &x_diff, &mut result_diff, 1).unwrap();
+        assert_approx_eq(&[0.6423912339336474f64, -0.9403985389889412f64, -0.6423912339336475f64, 0.9403985389889412f64,
+                           0.2384058440442351f64, 2.5231883119115297f64, -0.23840584404423515f64, -2.5231883119115297f64],
+                         result_diff.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_rejects_an_out_of_range_softmax_axis_on_native() {
+        let backend = get_native_backend();
+        let (mut x, mut result) = get_axis_memory(&backend, &[2, 3], &[0f64; 6]);
+
+        assert!(backend.softmax_axis(&mut x, &mut result, 2).is_err());
+        assert!(backend.log_softmax_axis(&mut x, &mut result, 2).is_err());
+    }
+
+    #[test]
+    fn it_computes_stable_softmax_for_large_logits_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f32>::new(backend.device(), &(2, 3)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[1000f32, 1000f32, 1000f32, 100f32, 0f32, -1000f32]);
+        let mut result = SharedTensor::<f32>::new(backend.device(), &(2, 3)).unwrap();
+
+        backend.softmax(&mut x, &mut result).unwrap();
+        let out = result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f32>().to_vec();
+        assert!(out.iter().all(|v| v.is_finite()), "{:?}", out);
+        assert!((out[0] - 1f32 / 3f32).abs() < 1e-6 && (out[1] - 1f32 / 3f32).abs() < 1e-6 && (out[2] - 1f32 / 3f32).abs() < 1e-6);
+        assert_eq!(1f32, out[3]);
+        assert!(out[4] < 1e-40 && out[5] == 0f32, "{:?}", out);
+    }
+
+    #[test]
+    fn it_computes_stable_softmax_for_large_logits_on_native_for_f64() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f64>::new(backend.device(), &(2, 2)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[1e308f64, 1e308f64, -1e4f64, -1e4f64 + 2f64.ln()]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 2)).unwrap();
+
+        backend.softmax_plain(&x, &mut result).unwrap();
+        assert_approx_eq(&[0.5f64, 0.5f64, 1f64 / 3f64, 2f64 / 3f64],
+                         result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_stable_log_softmax_for_large_logits_on_native() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f64>::new(backend.device(), &(1, 2)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[800f64, 0f64]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(1, 2)).unwrap();
+
+        backend.log_softmax(&mut x, &mut result).unwrap();
+        assert_approx_eq(&[0f64, -800f64], result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+    }
+
+    #[test]
+    fn it_computes_uniform_softmax_for_negative_infinite_logits_on_native() {
+        let backend = get_native_backend();
+        let mut x = SharedTensor::<f64>::new(backend.device(), &(2, 2)).unwrap();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[f64::NEG_INFINITY, f64::NEG_INFINITY, 0f64, f64::NEG_INFINITY]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(2, 2)).unwrap();
+
+        backend.softmax(&mut x, &mut result).unwrap();
+        assert_approx_eq(&[0.5f64, 0.5f64, 1f64, 0f64], result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>());
+
+        backend.log_softmax(&mut x, &mut result).unwrap();
+        let out = result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f64>().to_vec();
+        assert!((out[0] + 2f64.ln()).abs() < 1e-12 && (out[1] + 2f64.ln()).abs() < 1e-12, "{:?}", out);
+        assert_eq!(0f64, out[2]);
+        assert_eq!(f64::NEG_INFINITY, out[3]);
+    }
 }
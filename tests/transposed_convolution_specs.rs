@@ -0,0 +1,146 @@
+extern crate collenchyma_nn as co_nn;
+extern crate collenchyma as co;
+
+#[cfg(feature = "native")]
+mod common;
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod transposed_convolution_spec_native {
+
+    use co::prelude::*;
+    use co_nn::*;
+    use common::*;
+
+    type Algos = (ConvBackwardDataAlgo, ConvBackwardFilterAlgo, ConvForwardAlgo);
+
+    const ALGOS: [Algos; 3] = [(ConvBackwardDataAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::Auto, ConvForwardAlgo::GEMM),
+                               (ConvBackwardDataAlgo::FFT, ConvBackwardFilterAlgo::FFT, ConvForwardAlgo::FFT),
+                               (ConvBackwardDataAlgo::Auto, ConvBackwardFilterAlgo::ImplicitGEMM, ConvForwardAlgo::ImplicitGEMM)];
+
+    // a stride of 2, a padding of 1 and an output padding of 1 turn the 3x2 input into a 6x3 output.
+    const SRC: [usize; 4] = [2, 2, 3, 2];
+    const FILTER: [usize; 4] = [2, 3, 3, 2];
+    const DEST: [usize; 4] = [2, 3, 6, 3];
+
+    fn len(dims: &[usize]) -> usize {
+        dims.iter().fold(1, |acc, d| acc * d)
+    }
+
+    fn get_workspace(backend: &Backend<Native>, size: usize) -> SharedTensor<u8> {
+        SharedTensor::<u8>::new(backend.device(), &(size)).unwrap()
+    }
+
+    /// Runs the transposed convolution of `x` with `filter` and both of its gradients for the output gradient `dy`
+    /// and returns the output, the data gradient and the filter gradient.
+    fn transposed(backend: &Backend<Native>, algos: Algos, x: &[f64], filter: &[f64], dy: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let mut x = get_tensor(backend, &SRC, x);
+        let mut filter = get_tensor(backend, &FILTER, filter);
+        let mut dy = get_tensor(backend, &DEST, dy);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &DEST.to_vec()).unwrap();
+        let mut dx = SharedTensor::<f64>::new(backend.device(), &SRC.to_vec()).unwrap();
+        let mut filter_diff = SharedTensor::<f64>::new(backend.device(), &FILTER.to_vec()).unwrap();
+        let conf = backend.new_transposed_convolution_config(&x, &result, &mut filter, algos.0, algos.1, algos.2, &[2, 2], &[1, 1], &[1, 1]).unwrap();
+        let mut workspace = get_workspace(backend, ConvolutionConfig::<f64>::workspace_size(&conf));
+        backend.transposed_convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf).unwrap();
+        backend.transposed_convolution_grad_data(&mut filter, &mut dy, &mut dx, &mut workspace, &conf).unwrap();
+        backend.transposed_convolution_grad_filter(&mut x, &mut dy, &mut filter_diff, &mut workspace, &conf).unwrap();
+        (read_tensor(backend, &result), read_tensor(backend, &dx), read_tensor(backend, &filter_diff))
+    }
+
+    /// Runs the regular convolution of the `DEST` shaped `y` with `filter` that the transposed convolution reverses.
+    fn convolution(backend: &Backend<Native>, y: &[f64], filter: &[f64]) -> Vec<f64> {
+        let mut y = get_tensor(backend, &DEST, y);
+        let mut filter = get_tensor(backend, &FILTER, filter);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &SRC.to_vec()).unwrap();
+        let conf = backend.new_convolution_config(&y, &result, &mut filter, ConvForwardAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM,
+                                                  ConvBackwardDataAlgo::ImplicitGEMM, &[2, 2], &[1, 1], &[1, 1], 1).unwrap();
+        let mut workspace = get_workspace(backend, ConvolutionConfig::<f64>::workspace_size(&conf));
+        backend.convolution(&mut filter, &mut y, &mut result, &mut workspace, &conf).unwrap();
+        read_tensor(backend, &result)
+    }
+
+    #[test]
+    fn it_infers_the_transposed_convolution_output_shape_on_native() {
+        let backend = get_native_backend();
+        let shape = |src: &[usize], filter: &[usize], stride: &[i32], padding: &[i32], output_padding: &[i32]| {
+            TransposedConvolution::<f32>::transposed_convolution_output_shape(&backend, src, filter, stride, padding, output_padding)
+        };
+        assert_eq!(vec![1, 3, 5, 5], shape(&[1, 2, 3, 3], &[2, 3, 3, 3], &[1, 1], &[0, 0], &[0, 0]).unwrap());
+        assert_eq!(vec![1, 3, 5, 5], shape(&[1, 2, 3, 3], &[2, 3, 3, 3], &[2, 2], &[1, 1], &[0, 0]).unwrap());
+        assert_eq!(vec![4, 3, 6, 5], shape(&[4, 2, 3, 3], &[2, 3, 3, 3], &[2, 2], &[1, 1], &[1, 0]).unwrap());
+        // a stride of 2 with a 2x2 filter doubles the size of the input.
+        assert_eq!(vec![1, 1, 8, 14], shape(&[1, 1, 4, 7], &[1, 1, 2, 2], &[2, 2], &[0, 0], &[0, 0]).unwrap());
+
+        assert!(shape(&[1, 2, 3, 3], &[2, 3, 3, 3], &[2, 2], &[1, 1], &[2, 0]).is_err());
+        assert!(shape(&[1, 2, 3, 3], &[2, 3, 3, 3], &[1, 1], &[0, 0], &[1, 0]).is_err());
+        assert!(shape(&[1, 3, 3, 3], &[2, 3, 3, 3], &[1, 1], &[0, 0], &[0, 0]).is_err());
+        assert!(shape(&[1, 1, 1, 1], &[1, 1, 1, 1], &[1, 1], &[1, 1], &[0, 0]).is_err());
+        assert!(shape(&[1, 2, 3], &[2, 3, 3, 3], &[1, 1], &[0, 0], &[0, 0]).is_err());
+    }
+
+    #[test]
+    fn it_upsamples_with_a_transposed_convolution_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &[1, 1, 2, 2], &[1f64, 2f64, 3f64, 4f64]);
+        let mut filter = get_tensor(&backend, &[1, 1, 2, 2], &[1f64, 10f64, 100f64, 1000f64]);
+        let mut result = SharedTensor::<f64>::new(backend.device(), &(1, 1, 4, 4)).unwrap();
+        let conf = backend.new_transposed_convolution_config(&x, &result, &mut filter, ConvBackwardDataAlgo::ImplicitGEMM,
+                                                             ConvBackwardFilterAlgo::ImplicitGEMM, ConvForwardAlgo::GEMM,
+                                                             &[2, 2], &[0, 0], &[0, 0]).unwrap();
+        let mut workspace = SharedTensor::<u8>::new(backend.device(), &(ConvolutionConfig::<f64>::workspace_size(&conf))).unwrap();
+        backend.transposed_convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf).unwrap();
+        assert_eq!(vec![1f64, 10f64, 2f64, 20f64, 100f64, 1000f64, 200f64, 2000f64,
+                        3f64, 30f64, 4f64, 40f64, 300f64, 3000f64, 400f64, 4000f64], read_tensor(&backend, &result));
+    }
+
+    #[test]
+    fn it_computes_the_adjoint_of_the_convolution_on_native() {
+        let backend = get_native_backend();
+        let (x, filter, y) = (random_data(len(&SRC), 0), random_data(len(&FILTER), 7), random_data(len(&DEST), 11));
+        let conv_y = convolution(&backend, &y, &filter);
+        for &algos in ALGOS.iter() {
+            // <transposed(x), y> = <x, conv(y)>, so the output padding rows and columns are covered as well.
+            let (result, dx, _) = transposed(&backend, algos, &x, &filter, &y);
+            assert_all_close(&[dot(&x, &conv_y)], &[dot(&result, &y)], 1e-9);
+            // the data gradient of a transposed convolution is the regular convolution.
+            assert_all_close(&conv_y, &dx, 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_transposed_convolution_filter_grad_on_native() {
+        let backend = get_native_backend();
+        let (x, filter, dy) = (random_data(len(&SRC), 0), random_data(len(&FILTER), 7), random_data(len(&DEST), 11));
+        for &algos in ALGOS.iter() {
+            // the output is linear in the filter, so <transposed(x, filter), dy> = <filter, grad_filter(x, dy)>.
+            let (result, _, filter_diff) = transposed(&backend, algos, &x, &filter, &dy);
+            assert_all_close(&[dot(&result, &dy)], &[dot(&filter, &filter_diff)], 1e-9);
+            let loss = |filter: &[f64]| dot(&transposed(&backend, algos, &x, filter, &dy).0, &dy);
+            assert_all_close(&numeric_gradient(&filter, &loss), &filter_diff, 1e-6);
+        }
+    }
+
+    #[test]
+    fn it_rejects_mismatched_transposed_convolution_shapes_on_native() {
+        let backend = get_native_backend();
+        let mut x = get_tensor(&backend, &SRC, &random_data(len(&SRC), 0));
+        let mut filter = get_tensor(&backend, &FILTER, &random_data(len(&FILTER), 7));
+        // without output padding the output is one row and column smaller.
+        let result = SharedTensor::<f64>::new(backend.device(), &DEST.to_vec()).unwrap();
+        assert!(backend.new_transposed_convolution_config(&x, &result, &mut filter, ConvBackwardDataAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM,
+                                                          ConvForwardAlgo::GEMM, &[2, 2], &[1, 1], &[0, 0]).is_err());
+
+        let conf = backend.new_transposed_convolution_config(&x, &result, &mut filter, ConvBackwardDataAlgo::ImplicitGEMM, ConvBackwardFilterAlgo::ImplicitGEMM,
+                                                             ConvForwardAlgo::GEMM, &[2, 2], &[1, 1], &[1, 1]).unwrap();
+        let mut small_result = SharedTensor::<f64>::new(backend.device(), &(2, 3, 5, 3)).unwrap();
+        let mut workspace = get_workspace(&backend, ConvolutionConfig::<f64>::workspace_size(&conf));
+        assert!(backend.transposed_convolution(&mut filter, &mut x, &mut small_result, &mut workspace, &conf).is_err());
+
+        // a filter with the configured number of elements but another shape.
+        let mut transposed_filter = get_tensor(&backend, &[2, 3, 2, 3], &random_data(len(&FILTER), 7));
+        let mut result = SharedTensor::<f64>::new(backend.device(), &DEST.to_vec()).unwrap();
+        assert!(backend.transposed_convolution(&mut filter, &mut x, &mut result, &mut workspace, &conf).is_ok());
+        assert!(backend.transposed_convolution(&mut transposed_filter, &mut x, &mut result, &mut workspace, &conf).is_err());
+    }
+}